@@ -1,11 +1,14 @@
 use cosmwasm_std::{
     entry_point,
     // debug_print,
+    from_binary,
     to_binary,
     Addr,
+    BankMsg,
     Binary,
+    BlockInfo,
+    Coin,
     CosmosMsg,
-    Deps,
     DepsMut,
     Env,
     MessageInfo,
@@ -13,36 +16,50 @@ use cosmwasm_std::{
     StdError,
     StdResult,
     Storage,
+    Uint128,
     Uint256,
 };
 use secret_toolkit::{
-    crypto::sha_256,
-    permit::RevokedPermits,
+    crypto::{sha_256, ContractPrng},
+    permit::{validate, Permit, RevokedPermits},
     utils::space_pad,
     viewing_key::{ViewingKey, ViewingKeyStore},
 };
 
 use crate::{
     msg::{
-        ExecuteAnswer, ExecuteMsg, InstantiateMsg, ResponseStatus::Success, SendAction,
-        TransferAction,
+        ExecuteAnswer, ExecuteMsg, InstantiateMsg, PermitTransfer, ResponseStatus::Success,
+        SendAction, Snip20ReceiveMsg, TransferAction,
     },
+    notifications::{render_notifications, Notification, NotificationData},
     receiver::Snip1155ReceiveMsg,
+    snip20::Snip20HandleMsg,
     state::{
-        balances_r, balances_w, blockinfo_w, contr_conf_r, contr_conf_w,
+        admin_actions_r, admin_actions_w, airdrop_claims_r, airdrop_claims_w, airdrops_r,
+        airdrops_w, append_curator_token_id, balances_r, balances_w, blockinfo_w, contr_conf_r,
+        contr_conf_w, curator_quotas_r, curator_quotas_w, curator_token_id_count, edition_series_r,
+        edition_series_w, escrows_r, escrows_w,
         expiration::Expiration,
-        get_receiver_hash,
+        frozen_accounts_r, frozen_accounts_w, may_load_receiver,
         metadata::Metadata,
+        nft_users_w,
         permissions::{may_load_any_permission, new_permission, update_permission, Permission},
-        set_receiver_hash,
+        remove_receiver, sale_purchases_r, sale_purchases_w, sales_r, sales_w, set_receiver,
         state_structs::{
-            ContractConfig, CurateTokenId, StoredTokenInfo, TknConfig, TokenAmount, TokenInfoMsg,
+            AdminAction, AdminProposal, AirdropRound, ContractConfig, ContractStatus,
+            CurateTokenId, EditionSeries, Escrow, Namespace, NftUser, PaymentToken,
+            QueuedAdminAction, ReceiverRegistration, Role, RoleHolder, Sale, Snip20Price,
+            StoredTokenInfo, SwapOffer, TknConfig, TokenAmount, TokenIdBalance, TokenInfoMsg,
+            TransferPolicy, VestingSchedule,
         },
-        tkn_info_r, tkn_info_w, tkn_tot_supply_r, tkn_tot_supply_w,
+        swap_offers_r, swap_offers_w, tkn_info_r, tkn_info_w, tkn_tot_supply_r, tkn_tot_supply_w,
+        transfer_allowlists_w, transfer_approvals_r, transfer_approvals_w, transfer_nonces_r,
+        transfer_nonces_w, transfer_policies_r, transfer_policies_w, transfer_policy_violation,
         txhistory::{
-            append_new_owner, may_get_current_owner, store_burn, store_mint, store_transfer,
+            append_new_owner, may_get_current_owner, store_burn, store_escrow, store_freeze,
+            store_mint, store_transfer, StoredTxAction,
         },
-        PREFIX_REVOKED_PERMITS, RESPONSE_BLOCK_SIZE,
+        vesting_r, vesting_w, PREFIX_REVOKED_PERMITS, RESPONSE_BLOCK_SIZE,
     },
 };
 
@@ -89,17 +106,42 @@ pub fn instantiate(
         tx_cnt: 0u64,
         prng_seed: prng_seed.to_vec(),
         contract_address: env.contract.address.clone(),
+        swap_offer_cnt: 0u64,
+        escrow_cnt: 0u64,
+        payment_tokens: vec![],
+        treasury: msg.treasury,
+        namespaces: vec![],
+        roles: vec![],
+        status: ContractStatus::NormalRun,
+        pending_admin: None,
+        admin_action_delay: msg.admin_action_delay.unwrap_or(0),
+        admin_action_cnt: 0,
     };
+    let messages = exec_add_payment_tokens(&env, &mut config, msg.payment_tokens)?;
 
-    // set initial balances
+    // set initial balances. No receiver can have registered yet, so no callbacks are sent
+    let mut notifications = vec![];
     for initial_token in msg.initial_tokens {
-        exec_curate_token_id(&mut deps, &env, &info, &mut config, initial_token, None)?;
+        exec_curate_token_id(
+            &mut deps,
+            &env,
+            &info,
+            &mut config,
+            initial_token,
+            None,
+            false,
+            &mut vec![],
+            &mut notifications,
+        )?;
     }
 
     // save contract config -- where tx_cnt would have increased post initial balances
     contr_conf_w(deps.storage).save(&config)?;
 
-    Ok(Response::default())
+    let attributes = render_notifications(deps.storage, deps.api, notifications)?;
+    Ok(Response::default()
+        .add_messages(messages)
+        .add_attributes(attributes))
 }
 
 /////////////////////////////////////////////////////////////////////////////////
@@ -114,17 +156,51 @@ pub fn execute(deps: DepsMut, env: Env, info: MessageInfo, msg: ExecuteMsg) -> S
     // allowance expiration. Remove this after BlockInfo becomes available to queries
     blockinfo_w(deps.storage).save(&env.block)?;
 
+    // a stopped contract only accepts messages that can restart it
+    let status = contr_conf_r(deps.storage).load()?.status;
+    if status == ContractStatus::StopAll
+        && !matches!(
+            msg,
+            ExecuteMsg::SetContractStatus { .. }
+                | ExecuteMsg::GrantRole { .. }
+                | ExecuteMsg::RevokeRole { .. }
+                | ExecuteMsg::QueueAdminAction { .. }
+                | ExecuteMsg::ExecuteAdminAction { .. }
+                | ExecuteMsg::CancelAdminAction { .. }
+        )
+    {
+        return Err(StdError::generic_err(
+            "This contract is stopped and this action is not allowed",
+        ));
+    }
+
     let response = match msg {
         ExecuteMsg::CurateTokenIds {
             initial_tokens,
             memo,
+            notify_receivers,
             padding: _,
-        } => try_curate_token_ids(deps, env, info, initial_tokens, memo),
+        } => try_curate_token_ids(
+            deps,
+            env,
+            info,
+            initial_tokens,
+            memo,
+            notify_receivers.unwrap_or(false),
+        ),
         ExecuteMsg::MintTokens {
             mint_tokens,
             memo,
+            notify_receivers,
             padding: _,
-        } => try_mint_tokens(deps, env, info, mint_tokens, memo),
+        } => try_mint_tokens(
+            deps,
+            env,
+            info,
+            mint_tokens,
+            memo,
+            notify_receivers.unwrap_or(false),
+        ),
         ExecuteMsg::BurnTokens {
             burn_tokens,
             memo,
@@ -148,12 +224,28 @@ pub fn execute(deps: DepsMut, env: Env, info: MessageInfo, msg: ExecuteMsg) -> S
             recipient,
             amount,
             memo,
+            decoys,
+            entropy,
             padding: _,
-        } => try_transfer(deps, env, info, token_id, from, recipient, amount, memo),
+        } => try_transfer(
+            deps,
+            env,
+            info,
+            TransferAction {
+                token_id,
+                from,
+                recipient,
+                amount,
+                memo,
+                decoys,
+            },
+            entropy,
+        ),
         ExecuteMsg::BatchTransfer {
             actions,
+            entropy,
             padding: _,
-        } => try_batch_transfer(deps, env, info, actions),
+        } => try_batch_transfer(deps, env, info, actions, entropy),
         ExecuteMsg::Send {
             token_id,
             from,
@@ -162,6 +254,8 @@ pub fn execute(deps: DepsMut, env: Env, info: MessageInfo, msg: ExecuteMsg) -> S
             amount,
             msg,
             memo,
+            decoys,
+            entropy,
             padding: _,
         } => try_send(
             deps,
@@ -175,12 +269,237 @@ pub fn execute(deps: DepsMut, env: Env, info: MessageInfo, msg: ExecuteMsg) -> S
                 amount,
                 msg,
                 memo,
+                decoys,
             },
+            entropy,
         ),
         ExecuteMsg::BatchSend {
             actions,
+            entropy,
+            padding: _,
+        } => try_batch_send(deps, env, info, actions, entropy),
+        ExecuteMsg::TransferWithPermit { permit, padding: _ } => {
+            try_transfer_with_permit(deps, env, info, permit)
+        }
+        ExecuteMsg::CreateSwapOffer {
+            offer_token_id,
+            offer_amount,
+            ask_token_id,
+            ask_amount,
+            counterparty,
+            expiration,
+            padding: _,
+        } => try_create_swap_offer(
+            deps,
+            env,
+            info,
+            offer_token_id,
+            offer_amount,
+            ask_token_id,
+            ask_amount,
+            counterparty,
+            expiration,
+        ),
+        ExecuteMsg::AcceptSwapOffer {
+            offer_id,
+            padding: _,
+        } => try_accept_swap_offer(deps, env, info, offer_id),
+        ExecuteMsg::CancelSwapOffer {
+            offer_id,
+            padding: _,
+        } => try_cancel_swap_offer(deps, env, info, offer_id),
+        ExecuteMsg::CreateEscrow {
+            token_id,
+            amount,
+            beneficiary,
+            release,
+            clawback,
+            memo,
+            padding: _,
+        } => try_create_escrow(
+            deps,
+            env,
+            info,
+            token_id,
+            amount,
+            beneficiary,
+            release,
+            clawback.unwrap_or(false),
+            memo,
+        ),
+        ExecuteMsg::ClaimEscrow {
+            escrow_id,
+            padding: _,
+        } => try_claim_escrow(deps, env, info, escrow_id),
+        ExecuteMsg::ClawbackEscrow {
+            escrow_id,
+            padding: _,
+        } => try_clawback_escrow(deps, env, info, escrow_id),
+        ExecuteMsg::CreateVestingSchedule {
+            token_id,
+            beneficiary,
+            total,
+            start,
+            cliff,
+            end,
+            padding: _,
+        } => try_create_vesting_schedule(
+            deps,
+            env,
+            info,
+            token_id,
+            beneficiary,
+            total,
+            start,
+            cliff,
+            end,
+        ),
+        ExecuteMsg::ClaimVested {
+            token_id,
+            padding: _,
+        } => try_claim_vested(deps, env, info, token_id),
+        ExecuteMsg::RegisterAirdrop {
+            token_id,
+            round,
+            merkle_root,
+            padding: _,
+        } => try_register_airdrop(deps, env, info, token_id, round, merkle_root),
+        ExecuteMsg::ClaimAirdrop {
+            token_id,
+            round,
+            amount,
+            proof,
+            padding: _,
+        } => try_claim_airdrop(deps, env, info, token_id, round, amount, proof),
+        ExecuteMsg::SetSale {
+            token_id,
+            price,
+            per_address_limit,
+            start,
+            end,
+            max_sold,
+            snip20_prices,
+            padding: _,
+        } => try_set_sale(
+            deps,
+            env,
+            info,
+            token_id,
+            price,
+            per_address_limit,
+            start,
+            end,
+            max_sold,
+            snip20_prices.unwrap_or_default(),
+        ),
+        ExecuteMsg::Purchase {
+            token_id,
+            quantity,
+            padding: _,
+        } => try_purchase(deps, env, info, token_id, quantity),
+        ExecuteMsg::WithdrawSaleProceeds {
+            token_id,
+            padding: _,
+        } => try_withdraw_sale_proceeds(deps, env, info, token_id),
+        ExecuteMsg::Receive {
+            sender: _,
+            from,
+            amount,
+            memo: _,
+            msg,
+        } => try_receive(deps, env, info, from, amount, msg),
+        ExecuteMsg::SetUser {
+            token_id,
+            user,
+            expires,
+            padding: _,
+        } => try_set_user(deps, env, info, token_id, user, expires),
+        ExecuteMsg::Freeze {
+            token_id,
+            address,
+            padding: _,
+        } => try_freeze(deps, env, info, token_id, address, true),
+        ExecuteMsg::Unfreeze {
+            token_id,
+            address,
+            padding: _,
+        } => try_freeze(deps, env, info, token_id, address, false),
+        ExecuteMsg::FreezeMetadata {
+            token_id,
+            padding: _,
+        } => try_freeze_metadata(deps, env, info, token_id),
+        ExecuteMsg::SetTransferPolicy {
+            token_id,
+            policy,
+            padding: _,
+        } => try_set_transfer_policy(deps, env, info, token_id, policy),
+        ExecuteMsg::AddToAllowlist {
+            token_id,
+            addresses,
+            padding: _,
+        } => try_update_allowlist(deps, env, info, token_id, addresses, true),
+        ExecuteMsg::RemoveFromAllowlist {
+            token_id,
+            addresses,
+            padding: _,
+        } => try_update_allowlist(deps, env, info, token_id, addresses, false),
+        ExecuteMsg::ApproveTransfer {
+            token_id,
+            from,
+            recipient,
+            amount,
+            padding: _,
+        } => try_approve_transfer(deps, env, info, token_id, from, recipient, amount),
+        ExecuteMsg::CreateEditionSeries {
+            series,
+            max_editions,
+            padding: _,
+        } => try_create_edition_series(deps, env, info, series, max_editions),
+        ExecuteMsg::MintEdition {
+            parent_token_id,
+            recipient,
+            public_metadata,
+            private_metadata,
+            memo,
+            padding: _,
+        } => try_mint_edition(
+            deps,
+            env,
+            info,
+            parent_token_id,
+            recipient,
+            public_metadata,
+            private_metadata,
+            memo,
+        ),
+        ExecuteMsg::Fractionalize {
+            nft_token_id,
+            new_token_id,
+            shares,
+            padding: _,
+        } => try_fractionalize(deps, env, info, nft_token_id, new_token_id, shares),
+        ExecuteMsg::Reconstitute {
+            nft_token_id,
+            padding: _,
+        } => try_reconstitute(deps, env, info, nft_token_id),
+        ExecuteMsg::SetCuratorQuota {
+            curator,
+            quota,
+            padding: _,
+        } => try_set_curator_quota(deps, env, info, curator, quota),
+        ExecuteMsg::RegisterNamespace {
+            prefix,
+            owner,
             padding: _,
-        } => try_batch_send(deps, env, info, actions),
+        } => try_register_namespace(deps, env, info, prefix, owner),
+        ExecuteMsg::RemoveNamespace { prefix, padding: _ } => {
+            try_remove_namespace(deps, env, info, prefix)
+        }
+        ExecuteMsg::AddPaymentTokens {
+            payment_tokens,
+            treasury,
+            padding: _,
+        } => try_add_payment_tokens(deps, env, info, payment_tokens, treasury),
         ExecuteMsg::GivePermission {
             allowed_address,
             token_id,
@@ -190,6 +509,8 @@ pub fn execute(deps: DepsMut, env: Env, info: MessageInfo, msg: ExecuteMsg) -> S
             view_private_metadata_expiry,
             transfer,
             transfer_expiry,
+            burn,
+            burn_expiry,
             padding: _,
         } => try_give_permission(
             deps,
@@ -203,6 +524,8 @@ pub fn execute(deps: DepsMut, env: Env, info: MessageInfo, msg: ExecuteMsg) -> S
             view_private_metadata_expiry,
             transfer,
             transfer_expiry,
+            burn,
+            burn_expiry,
         ),
         ExecuteMsg::RevokePermission {
             token_id,
@@ -246,10 +569,53 @@ pub fn execute(deps: DepsMut, env: Env, info: MessageInfo, msg: ExecuteMsg) -> S
             contract_address,
             padding: _,
         } => try_remove_admin(deps, env, info, current_admin, contract_address),
+        ExecuteMsg::ProposeAdmin {
+            new_admin,
+            expiration,
+            padding: _,
+        } => try_propose_admin(deps, env, info, new_admin, expiration),
+        ExecuteMsg::AcceptAdmin { padding: _ } => try_accept_admin(deps, env, info),
+        ExecuteMsg::CancelAdminProposal { padding: _ } => {
+            try_cancel_admin_proposal(deps, env, info)
+        }
+        ExecuteMsg::GrantRole {
+            role,
+            address,
+            padding: _,
+        } => try_grant_role(deps, env, info, role, address),
+        ExecuteMsg::RevokeRole {
+            role,
+            address,
+            padding: _,
+        } => try_revoke_role(deps, env, info, role, address),
+        ExecuteMsg::SetContractStatus { level, padding: _ } => {
+            try_set_contract_status(deps, env, info, level)
+        }
+        ExecuteMsg::QueueAdminAction { action, padding: _ } => {
+            try_queue_admin_action(deps, env, info, action)
+        }
+        ExecuteMsg::ExecuteAdminAction {
+            action_id,
+            padding: _,
+        } => try_execute_admin_action(deps, env, info, action_id),
+        ExecuteMsg::CancelAdminAction {
+            action_id,
+            padding: _,
+        } => try_cancel_admin_action(deps, env, info, action_id),
         ExecuteMsg::RegisterReceive {
             code_hash,
+            token_ids,
+            reject_unlisted_token_ids,
             padding: _,
-        } => try_register_receive(deps, env, info, code_hash),
+        } => try_register_receive(
+            deps,
+            env,
+            info,
+            code_hash,
+            token_ids,
+            reject_unlisted_token_ids.unwrap_or(false),
+        ),
+        ExecuteMsg::UnregisterReceive { padding: _ } => try_unregister_receive(deps, env, info),
     };
     pad_response(response)
 }
@@ -260,11 +626,16 @@ fn try_curate_token_ids(
     info: MessageInfo,
     initial_tokens: Vec<CurateTokenId>,
     memo: Option<String>,
+    notify_receivers: bool,
 ) -> StdResult<Response> {
     let mut config = contr_conf_r(deps.storage).load()?;
     // check if sender is a curator
     verify_curator(&config, &info)?;
 
+    // set up cosmos messages
+    let mut messages = vec![];
+    let mut notifications = vec![];
+
     // curate new token_ids
     for initial_token in initial_tokens {
         exec_curate_token_id(
@@ -274,16 +645,20 @@ fn try_curate_token_ids(
             &mut config,
             initial_token,
             memo.clone(),
+            notify_receivers,
+            &mut messages,
+            &mut notifications,
         )?;
     }
 
     contr_conf_w(deps.storage).save(&config)?;
 
-    Ok(
-        Response::new().set_data(to_binary(&ExecuteAnswer::CurateTokenIds {
-            status: Success,
-        })?),
-    )
+    let attributes = render_notifications(deps.storage, deps.api, notifications)?;
+    let data = to_binary(&ExecuteAnswer::CurateTokenIds { status: Success })?;
+    Ok(Response::new()
+        .add_messages(messages)
+        .add_attributes(attributes)
+        .set_data(data))
 }
 
 fn try_mint_tokens(
@@ -292,11 +667,16 @@ fn try_mint_tokens(
     info: MessageInfo,
     mint_tokens: Vec<TokenAmount>,
     memo: Option<String>,
+    notify_receivers: bool,
 ) -> StdResult<Response> {
     let mut config = contr_conf_r(deps.storage).load()?;
 
     verify_curator(&config, &info)?;
 
+    // set up cosmos messages
+    let mut messages = vec![];
+    let mut notifications = vec![];
+
     // mint tokens
     for mint_token in mint_tokens {
         let token_info_op = tkn_info_r(deps.storage).may_load(mint_token.token_id.as_bytes())?;
@@ -326,35 +706,34 @@ fn try_mint_tokens(
 
         // add balances
         for add_balance in mint_token.balances {
-            exec_change_balance(
-                deps.storage,
-                &mint_token.token_id,
-                None,
-                Some(&add_balance.address),
-                &add_balance.amount,
-                &token_info_op.clone().unwrap(),
-            )?;
-
-            // store mint_token
-            store_mint(
-                deps.storage,
+            impl_mint(
+                &mut deps,
+                &env,
                 &mut config,
-                &env.block,
-                &mint_token.token_id,
-                deps.api.addr_canonicalize(info.sender.as_str())?,
-                deps.api.addr_canonicalize(add_balance.address.as_str())?,
+                &mut messages,
+                &mut notifications,
+                &info.sender,
+                token_info_op.as_ref().unwrap(),
+                add_balance.address,
                 add_balance.amount,
                 memo.clone(),
+                notify_receivers,
             )?;
         }
     }
 
     contr_conf_w(deps.storage).save(&config)?;
 
-    Ok(Response::new().set_data(to_binary(&ExecuteAnswer::MintTokens { status: Success })?))
+    let attributes = render_notifications(deps.storage, deps.api, notifications)?;
+    let data = to_binary(&ExecuteAnswer::MintTokens { status: Success })?;
+    Ok(Response::new()
+        .add_messages(messages)
+        .add_attributes(attributes)
+        .set_data(data))
 }
 
-// in the base specifications, this function can be performed by token owner only
+// in the base specifications, this function can be performed by token owner only. This implementation
+// also allows addresses with a burn allowance, and minters if `minter_may_burn` == true
 fn try_burn_tokens(
     deps: DepsMut,
     env: Env,
@@ -363,6 +742,7 @@ fn try_burn_tokens(
     memo: Option<String>,
 ) -> StdResult<Response> {
     let mut config = contr_conf_r(deps.storage).load()?;
+    let mut notifications = vec![];
 
     // burn tokens
     for burn_token in burn_tokens {
@@ -384,13 +764,29 @@ fn try_burn_tokens(
 
         // remove balances
         for rem_balance in burn_token.balances {
-            // in base specification, burner MUST be the owner
-            if rem_balance.address != info.sender {
-                return Err(StdError::generic_err(format!(
-                    "you do not have permission to burn {} tokens from address {}",
-                    rem_balance.amount, rem_balance.address
-                )));
-            }
+            // burner is None if owner burns its own tokens
+            let burner = if rem_balance.address == info.sender {
+                None
+            } else {
+                verify_burner(
+                    deps.storage,
+                    &env,
+                    &info,
+                    &token_info,
+                    &rem_balance.address,
+                    rem_balance.amount,
+                )?;
+                notifications.push(Notification::new(
+                    rem_balance.address.clone(),
+                    NotificationData::Burn {
+                        token_id: burn_token.token_id.clone(),
+                        amount: rem_balance.amount,
+                        burner: info.sender.clone(),
+                    },
+                ));
+                Some(deps.api.addr_canonicalize(info.sender.as_str())?)
+            };
+            verify_not_frozen(deps.storage, &token_info, &rem_balance.address)?;
 
             exec_change_balance(
                 deps.storage,
@@ -399,6 +795,8 @@ fn try_burn_tokens(
                 None,
                 &rem_balance.amount,
                 &token_info,
+                None,
+                None,
             )?;
 
             // store burn_token
@@ -407,8 +805,7 @@ fn try_burn_tokens(
                 &mut config,
                 &env.block,
                 &burn_token.token_id,
-                // in base specification, burner MUST be the owner
-                None,
+                burner,
                 deps.api.addr_canonicalize(rem_balance.address.as_str())?,
                 rem_balance.amount,
                 memo.clone(),
@@ -418,7 +815,10 @@ fn try_burn_tokens(
 
     contr_conf_w(deps.storage).save(&config)?;
 
-    Ok(Response::new().set_data(to_binary(&ExecuteAnswer::BurnTokens { status: Success })?))
+    let attributes = render_notifications(deps.storage, deps.api, notifications)?;
+    Ok(Response::new()
+        .add_attributes(attributes)
+        .set_data(to_binary(&ExecuteAnswer::BurnTokens { status: Success })?))
 }
 
 fn try_change_metadata(
@@ -437,6 +837,12 @@ fn try_change_metadata(
                 token_id
             )))
         }
+        Some(i) if i.metadata_frozen => {
+            return Err(StdError::generic_err(format!(
+                "the metadata of token_id {} is frozen",
+                token_id
+            )))
+        }
         Some(i) => i.token_config.flatten(),
     };
 
@@ -448,10 +854,13 @@ fn try_change_metadata(
     };
 
     let is_minter = verify_minter(tkn_info_op.as_ref().unwrap(), &info).is_ok();
+    let config = contr_conf_r(deps.storage).load()?;
+    let is_metadata_manager = has_role(&config, &info.sender, Role::MetadataManager);
 
-    // can sender change metadata? based on i) sender is minter or owner, ii) token_id config allows it or not
+    // can sender change metadata? based on i) sender is minter, owner or metadata manager, ii) token_id
+    // config allows it or not. Metadata managers are bound by the same config as minters
     let allow_update = is_owner && tkn_conf.owner_may_update_metadata
-        || is_minter && tkn_conf.minter_may_update_metadata;
+        || (is_minter || is_metadata_manager) && tkn_conf.minter_may_update_metadata;
 
     // control flow based on `allow_update`
     match allow_update {
@@ -480,22 +889,35 @@ fn try_change_metadata(
     )
 }
 
-#[allow(clippy::too_many_arguments)]
 fn try_transfer(
     mut deps: DepsMut,
     env: Env,
     info: MessageInfo,
-    token_id: String,
-    from: Addr,
-    recipient: Addr,
-    amount: Uint256,
-    memo: Option<String>,
+    action: TransferAction,
+    entropy: Option<Binary>,
 ) -> StdResult<Response> {
+    let mut prng = new_decoy_prng(deps.storage, &env, entropy.as_ref())?;
+    let account_random_pos = get_account_random_pos(&mut prng, action.decoys.as_ref());
+    let mut notifications = vec![];
+
     impl_transfer(
-        &mut deps, &env, &info, &token_id, &from, &recipient, amount, memo,
+        &mut deps,
+        &env,
+        &info,
+        &action.token_id,
+        &action.from,
+        &action.recipient,
+        action.amount,
+        action.memo,
+        action.decoys,
+        account_random_pos,
+        &mut notifications,
     )?;
 
-    Ok(Response::new().set_data(to_binary(&ExecuteAnswer::Transfer { status: Success })?))
+    let attributes = render_notifications(deps.storage, deps.api, notifications)?;
+    Ok(Response::new()
+        .add_attributes(attributes)
+        .set_data(to_binary(&ExecuteAnswer::Transfer { status: Success })?))
 }
 
 fn try_batch_transfer(
@@ -503,10 +925,15 @@ fn try_batch_transfer(
     env: Env,
     info: MessageInfo,
     actions: Vec<TransferAction>,
+    entropy: Option<Binary>,
 ) -> StdResult<Response> {
+    let mut prng = new_decoy_prng(deps.storage, &env, entropy.as_ref())?;
+    let mut notifications = vec![];
+
     for action in actions {
         let from = deps.api.addr_validate(action.from.as_str())?;
         let recipient = deps.api.addr_validate(action.recipient.as_str())?;
+        let account_random_pos = get_account_random_pos(&mut prng, action.decoys.as_ref());
         impl_transfer(
             &mut deps,
             &env,
@@ -516,14 +943,18 @@ fn try_batch_transfer(
             &recipient,
             action.amount,
             action.memo,
+            action.decoys,
+            account_random_pos,
+            &mut notifications,
         )?;
     }
 
-    Ok(
-        Response::new().set_data(to_binary(&ExecuteAnswer::BatchTransfer {
+    let attributes = render_notifications(deps.storage, deps.api, notifications)?;
+    Ok(Response::new()
+        .add_attributes(attributes)
+        .set_data(to_binary(&ExecuteAnswer::BatchTransfer {
             status: Success,
-        })?),
-    )
+        })?))
 }
 
 fn try_send(
@@ -531,14 +962,30 @@ fn try_send(
     env: Env,
     info: MessageInfo,
     action: SendAction,
+    entropy: Option<Binary>,
 ) -> StdResult<Response> {
     // set up cosmos messages
     let mut messages = vec![];
 
-    impl_send(&mut deps, &env, &info, &mut messages, action)?;
+    let mut notifications = vec![];
+
+    let mut prng = new_decoy_prng(deps.storage, &env, entropy.as_ref())?;
+    impl_send(
+        &mut deps,
+        &env,
+        &info,
+        &mut messages,
+        &mut notifications,
+        &mut prng,
+        action,
+    )?;
 
+    let attributes = render_notifications(deps.storage, deps.api, notifications)?;
     let data = to_binary(&ExecuteAnswer::Send { status: Success })?;
-    let res = Response::new().add_messages(messages).set_data(data);
+    let res = Response::new()
+        .add_messages(messages)
+        .add_attributes(attributes)
+        .set_data(data);
     Ok(res)
 }
 
@@ -547,46 +994,1676 @@ fn try_batch_send(
     env: Env,
     info: MessageInfo,
     actions: Vec<SendAction>,
+    entropy: Option<Binary>,
 ) -> StdResult<Response> {
     // declare vector for cosmos messages
     let mut messages = vec![];
+    let mut notifications = vec![];
 
+    let mut prng = new_decoy_prng(deps.storage, &env, entropy.as_ref())?;
     for action in actions {
-        impl_send(&mut deps, &env, &info, &mut messages, action)?;
+        impl_send(
+            &mut deps,
+            &env,
+            &info,
+            &mut messages,
+            &mut notifications,
+            &mut prng,
+            action,
+        )?;
     }
 
+    let attributes = render_notifications(deps.storage, deps.api, notifications)?;
     let data = to_binary(&ExecuteAnswer::BatchSend { status: Success })?;
-    let res = Response::new().add_messages(messages).set_data(data);
+    let res = Response::new()
+        .add_messages(messages)
+        .add_attributes(attributes)
+        .set_data(data);
     Ok(res)
 }
 
-/// does not check if `token_id` exists so attacker cannot easily figure out if
-/// a `token_id` has been created
+/// performs the transfers signed by an owner in a transfer permit. The message sender does not
+/// need any permission, as the transfers are performed as if they were sent by the owner
+fn try_transfer_with_permit(
+    mut deps: DepsMut,
+    env: Env,
+    _info: MessageInfo,
+    permit: Permit<PermitTransfer>,
+) -> StdResult<Response> {
+    if permit.params.chain_id != env.block.chain_id {
+        return Err(StdError::generic_err(format!(
+            "Permit is for chain_id {}, expected {}",
+            permit.params.chain_id, env.block.chain_id
+        )));
+    }
+    if permit.params.permissions.is_empty() {
+        return Err(StdError::generic_err("Transfer permit has no transfers"));
+    }
+
+    // verifies the owner's signature, and that the permit is for this contract and has not been revoked
+    let contract_address = contr_conf_r(deps.storage).load()?.contract_address;
+    let owner_str = validate(
+        deps.as_ref(),
+        PREFIX_REVOKED_PERMITS,
+        &permit,
+        contract_address.to_string(),
+        None,
+    )?;
+    let owner = deps.api.addr_validate(&owner_str)?;
+    let owner_info = MessageInfo {
+        sender: owner.clone(),
+        funds: vec![],
+    };
+
+    let mut notifications = vec![];
+    for transfer in permit.params.permissions {
+        if transfer.expiration.is_expired(&env.block) {
+            return Err(StdError::generic_err(format!(
+                "Transfer permit has expired: {}",
+                transfer.expiration
+            )));
+        }
+
+        // replay protection
+        let nonce = transfer.nonce.u64().to_be_bytes();
+        if transfer_nonces_r(deps.storage, &owner)
+            .may_load(&nonce)?
+            .is_some()
+        {
+            return Err(StdError::generic_err(format!(
+                "Transfer permit nonce {} has already been used",
+                transfer.nonce
+            )));
+        }
+        transfer_nonces_w(deps.storage, &owner).save(&nonce, &true)?;
+
+        let recipient = deps.api.addr_validate(transfer.recipient.as_str())?;
+        impl_transfer(
+            &mut deps,
+            &env,
+            &owner_info,
+            &transfer.token_id,
+            &owner,
+            &recipient,
+            transfer.amount,
+            transfer.memo,
+            None,
+            None,
+            &mut notifications,
+        )?;
+    }
+
+    let attributes = render_notifications(deps.storage, deps.api, notifications)?;
+    Ok(Response::new()
+        .add_attributes(attributes)
+        .set_data(to_binary(&ExecuteAnswer::TransferWithPermit {
+            status: Success,
+        })?))
+}
+
+/// transfers the offered tokens from the maker to the contract, which holds them until the
+/// offer is accepted or cancelled
 #[allow(clippy::too_many_arguments)]
-fn try_give_permission(
-    deps: DepsMut,
-    _env: Env,
+fn try_create_swap_offer(
+    mut deps: DepsMut,
+    env: Env,
     info: MessageInfo,
-    allowed_address: Addr,
-    token_id: String,
-    view_balance: Option<bool>,
-    view_balance_expiry: Option<Expiration>,
-    view_private_metadata: Option<bool>,
-    view_private_metadata_expiry: Option<Expiration>,
-    transfer: Option<Uint256>,
-    transfer_expiry: Option<Expiration>,
+    offer_token_id: String,
+    offer_amount: Uint256,
+    ask_token_id: String,
+    ask_amount: Uint256,
+    counterparty: Option<Addr>,
+    expiration: Option<Expiration>,
 ) -> StdResult<Response> {
-    // may_load current permission
-    let permission_op =
-        may_load_any_permission(deps.storage, &info.sender, &token_id, &allowed_address)?;
-
-    let action = |old_perm: Permission,
-                  view_balance: Option<bool>,
-                  view_balance_expiry: Option<Expiration>,
+    if offer_amount.is_zero() || ask_amount.is_zero() {
+        return Err(StdError::generic_err(
+            "swap offer amounts must be greater than zero",
+        ));
+    }
+    if tkn_info_r(deps.storage)
+        .may_load(ask_token_id.as_bytes())?
+        .is_none()
+    {
+        return Err(StdError::generic_err(format!(
+            "token_id {} does not exist",
+            ask_token_id
+        )));
+    }
+    let expiration = expiration.unwrap_or(Expiration::Never);
+    if expiration.is_expired(&env.block) {
+        return Err(StdError::generic_err(format!(
+            "swap offer expiration has already passed: {}",
+            expiration
+        )));
+    }
+    let counterparty = match counterparty {
+        Some(addr) => Some(deps.api.addr_validate(addr.as_str())?),
+        None => None,
+    };
+    exec_lock_transfer_policy(
+        deps.storage,
+        &offer_token_id,
+        &info.sender,
+        counterparty.as_ref(),
+        offer_amount,
+    )?;
+
+    let mut config = contr_conf_r(deps.storage).load()?;
+    let offer_id = config.swap_offer_cnt;
+    config.swap_offer_cnt += 1;
+    contr_conf_w(deps.storage).save(&config)?;
+
+    // lock offered tokens
+    let mut notifications = vec![];
+    impl_transfer(
+        &mut deps,
+        &env,
+        &info,
+        &offer_token_id,
+        &info.sender,
+        &env.contract.address,
+        offer_amount,
+        Some(format!("swap offer {}", offer_id)),
+        None,
+        None,
+        &mut notifications,
+    )?;
+
+    let offer = SwapOffer {
+        offer_id,
+        maker: info.sender,
+        offer_token_id,
+        offer_amount,
+        ask_token_id,
+        ask_amount,
+        counterparty,
+        expiration,
+    };
+    swap_offers_w(deps.storage).save(&offer_id.to_be_bytes(), &offer)?;
+
+    let attributes = render_notifications(deps.storage, deps.api, notifications)?;
+    Ok(Response::new()
+        .add_attributes(attributes)
+        .set_data(to_binary(&ExecuteAnswer::CreateSwapOffer { offer_id })?))
+}
+
+fn try_accept_swap_offer(
+    mut deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    offer_id: u64,
+) -> StdResult<Response> {
+    let offer = load_swap_offer(deps.storage, offer_id)?;
+    if let Some(counterparty) = &offer.counterparty {
+        if counterparty != &info.sender {
+            return Err(StdError::generic_err(format!(
+                "swap offer {} can only be accepted by {}",
+                offer_id, counterparty
+            )));
+        }
+    }
+    if offer.expiration.is_expired(&env.block) {
+        return Err(StdError::generic_err(format!(
+            "swap offer {} has expired",
+            offer_id
+        )));
+    }
+
+    let memo = Some(format!("swap offer {}", offer_id));
+    let mut notifications = vec![];
+
+    // taker pays the maker
+    impl_transfer(
+        &mut deps,
+        &env,
+        &info,
+        &offer.ask_token_id,
+        &info.sender,
+        &offer.maker,
+        offer.ask_amount,
+        memo.clone(),
+        None,
+        None,
+        &mut notifications,
+    )?;
+
+    // contract releases the offered tokens to the taker
+    exec_release_swap_offer(&mut deps, &env, &offer, &info.sender, &mut notifications)?;
+
+    let attributes = render_notifications(deps.storage, deps.api, notifications)?;
+    Ok(Response::new()
+        .add_attributes(attributes)
+        .set_data(to_binary(&ExecuteAnswer::AcceptSwapOffer {
+            status: Success,
+        })?))
+}
+
+fn try_cancel_swap_offer(
+    mut deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    offer_id: u64,
+) -> StdResult<Response> {
+    let offer = load_swap_offer(deps.storage, offer_id)?;
+    if offer.maker != info.sender {
+        return Err(StdError::generic_err(format!(
+            "only the maker can cancel swap offer {}",
+            offer_id
+        )));
+    }
+
+    // refund the maker
+    let mut notifications = vec![];
+    exec_release_swap_offer(&mut deps, &env, &offer, &offer.maker, &mut notifications)?;
+
+    let attributes = render_notifications(deps.storage, deps.api, notifications)?;
+    Ok(Response::new()
+        .add_attributes(attributes)
+        .set_data(to_binary(&ExecuteAnswer::CancelSwapOffer {
+            status: Success,
+        })?))
+}
+
+/// locks the sender's tokens in the contract for the beneficiary
+#[allow(clippy::too_many_arguments)]
+fn try_create_escrow(
+    mut deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    token_id: String,
+    amount: Uint256,
+    beneficiary: Addr,
+    release: Expiration,
+    clawback: bool,
+    memo: Option<String>,
+) -> StdResult<Response> {
+    let beneficiary = deps.api.addr_validate(beneficiary.as_str())?;
+    if amount.is_zero() {
+        return Err(StdError::generic_err(
+            "escrow amount must be greater than zero",
+        ));
+    }
+    if release.is_expired(&env.block) {
+        return Err(StdError::generic_err(format!(
+            "escrow release has already passed: {}",
+            release
+        )));
+    }
+
+    exec_lock_transfer_policy(
+        deps.storage,
+        &token_id,
+        &info.sender,
+        Some(&beneficiary),
+        amount,
+    )?;
+
+    let mut config = contr_conf_r(deps.storage).load()?;
+    let escrow_id = config.escrow_cnt;
+    config.escrow_cnt += 1;
+    contr_conf_w(deps.storage).save(&config)?;
+
+    // lock escrowed tokens
+    let mut notifications = vec![];
+    impl_transfer(
+        &mut deps,
+        &env,
+        &info,
+        &token_id,
+        &info.sender,
+        &env.contract.address,
+        amount,
+        Some(format!("escrow {}", escrow_id)),
+        None,
+        None,
+        &mut notifications,
+    )?;
+
+    let mut config = contr_conf_r(deps.storage).load()?;
+    store_escrow(
+        deps.storage,
+        &mut config,
+        &env.block,
+        &token_id,
+        StoredTxAction::EscrowDeposit {
+            escrow_id,
+            depositor: deps.api.addr_canonicalize(info.sender.as_str())?,
+            beneficiary: deps.api.addr_canonicalize(beneficiary.as_str())?,
+            amount,
+            release,
+        },
+        memo,
+    )?;
+    contr_conf_w(deps.storage).save(&config)?;
+
+    let escrow = Escrow {
+        escrow_id,
+        token_id,
+        depositor: info.sender,
+        beneficiary,
+        amount,
+        release,
+        clawback,
+    };
+    escrows_w(deps.storage).save(&escrow_id.to_be_bytes(), &escrow)?;
+
+    let attributes = render_notifications(deps.storage, deps.api, notifications)?;
+    Ok(Response::new()
+        .add_attributes(attributes)
+        .set_data(to_binary(&ExecuteAnswer::CreateEscrow { escrow_id })?))
+}
+
+fn try_claim_escrow(
+    mut deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    escrow_id: u64,
+) -> StdResult<Response> {
+    let escrow = load_escrow(deps.storage, escrow_id)?;
+    if escrow.beneficiary != info.sender {
+        return Err(StdError::generic_err(format!(
+            "only the beneficiary can claim escrow {}",
+            escrow_id
+        )));
+    }
+    if !escrow.release.is_expired(&env.block) {
+        return Err(StdError::generic_err(format!(
+            "escrow {} cannot be claimed before {}",
+            escrow_id, escrow.release
+        )));
+    }
+
+    let mut notifications = vec![];
+    exec_release_escrow(&mut deps, &env, escrow, false, &mut notifications)?;
+
+    let attributes = render_notifications(deps.storage, deps.api, notifications)?;
+    Ok(Response::new()
+        .add_attributes(attributes)
+        .set_data(to_binary(&ExecuteAnswer::ClaimEscrow { status: Success })?))
+}
+
+fn try_clawback_escrow(
+    mut deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    escrow_id: u64,
+) -> StdResult<Response> {
+    let escrow = load_escrow(deps.storage, escrow_id)?;
+    if escrow.depositor != info.sender {
+        return Err(StdError::generic_err(format!(
+            "only the depositor can claw back escrow {}",
+            escrow_id
+        )));
+    }
+    if !escrow.clawback {
+        return Err(StdError::generic_err(format!(
+            "escrow {} does not allow clawback",
+            escrow_id
+        )));
+    }
+    if escrow.release.is_expired(&env.block) {
+        return Err(StdError::generic_err(format!(
+            "escrow {} has been released and can no longer be clawed back",
+            escrow_id
+        )));
+    }
+
+    let mut notifications = vec![];
+    exec_release_escrow(&mut deps, &env, escrow, true, &mut notifications)?;
+
+    let attributes = render_notifications(deps.storage, deps.api, notifications)?;
+    Ok(Response::new()
+        .add_attributes(attributes)
+        .set_data(to_binary(&ExecuteAnswer::ClawbackEscrow {
+            status: Success,
+        })?))
+}
+
+#[allow(clippy::too_many_arguments)]
+fn try_create_vesting_schedule(
+    mut deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    token_id: String,
+    beneficiary: Addr,
+    total: Uint256,
+    start: u64,
+    cliff: u64,
+    end: u64,
+) -> StdResult<Response> {
+    verify_curator(&contr_conf_r(deps.storage).load()?, &info)?;
+    let beneficiary = deps.api.addr_validate(beneficiary.as_str())?;
+    if total.is_zero() {
+        return Err(StdError::generic_err(
+            "vesting total must be greater than zero",
+        ));
+    }
+    if start >= end || cliff < start || cliff > end {
+        return Err(StdError::generic_err(
+            "vesting schedule must satisfy start <= cliff <= end and start < end",
+        ));
+    }
+    if vesting_r(deps.storage, &beneficiary)
+        .may_load(token_id.as_bytes())?
+        .is_some()
+    {
+        return Err(StdError::generic_err(format!(
+            "{} already has a vesting schedule for token_id {}",
+            beneficiary, token_id
+        )));
+    }
+
+    // lock the tokens in the contract until they are claimed
+    let mut notifications = vec![];
+    exec_lock_transfer_policy(
+        deps.storage,
+        &token_id,
+        &info.sender,
+        Some(&beneficiary),
+        total,
+    )?;
+    impl_transfer(
+        &mut deps,
+        &env,
+        &info,
+        &token_id,
+        &info.sender,
+        &env.contract.address,
+        total,
+        Some(format!("vesting schedule for {}", beneficiary)),
+        None,
+        None,
+        &mut notifications,
+    )?;
+
+    let schedule = VestingSchedule {
+        token_id,
+        beneficiary,
+        funder: info.sender,
+        total,
+        claimed: Uint256::zero(),
+        start,
+        cliff,
+        end,
+    };
+    vesting_w(deps.storage, &schedule.beneficiary).save(schedule.token_id.as_bytes(), &schedule)?;
+
+    let attributes = render_notifications(deps.storage, deps.api, notifications)?;
+    Ok(Response::new()
+        .add_attributes(attributes)
+        .set_data(to_binary(&ExecuteAnswer::CreateVestingSchedule {
+            status: Success,
+        })?))
+}
+
+fn try_claim_vested(
+    mut deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    token_id: String,
+) -> StdResult<Response> {
+    let mut schedule = vesting_r(deps.storage, &info.sender)
+        .may_load(token_id.as_bytes())?
+        .ok_or_else(|| {
+            StdError::generic_err(format!(
+                "no vesting schedule found for token_id {}",
+                token_id
+            ))
+        })?;
+    let amount = schedule.vested(env.block.time.seconds()) - schedule.claimed;
+    if amount.is_zero() {
+        return Err(StdError::generic_err(format!(
+            "no vested tokens to claim for token_id {}",
+            token_id
+        )));
+    }
+
+    let contract_info = MessageInfo {
+        sender: env.contract.address.clone(),
+        funds: vec![],
+    };
+    let mut notifications = vec![];
+    impl_transfer(
+        &mut deps,
+        &env,
+        &contract_info,
+        &token_id,
+        &env.contract.address,
+        &info.sender,
+        amount,
+        Some("vesting claim".to_string()),
+        None,
+        None,
+        &mut notifications,
+    )?;
+
+    schedule.claimed += amount;
+    if schedule.claimed == schedule.total {
+        vesting_w(deps.storage, &info.sender).remove(token_id.as_bytes());
+    } else {
+        vesting_w(deps.storage, &info.sender).save(token_id.as_bytes(), &schedule)?;
+    }
+
+    let attributes = render_notifications(deps.storage, deps.api, notifications)?;
+    Ok(Response::new()
+        .add_attributes(attributes)
+        .set_data(to_binary(&ExecuteAnswer::ClaimVested { amount })?))
+}
+
+fn try_register_airdrop(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    token_id: String,
+    round: u64,
+    merkle_root: Binary,
+) -> StdResult<Response> {
+    verify_curator(&contr_conf_r(deps.storage).load()?, &info)?;
+    let token_info = match tkn_info_r(deps.storage).may_load(token_id.as_bytes())? {
+        Some(i) => i,
+        None => {
+            return Err(StdError::generic_err(format!(
+                "token_id {} does not exist",
+                token_id
+            )))
+        }
+    };
+    if token_info.curator != info.sender {
+        return Err(StdError::generic_err(format!(
+            "only the curator of token_id {} can register its airdrops",
+            token_id
+        )));
+    }
+    if !token_info.token_config.flatten().enable_mint {
+        return Err(StdError::generic_err(
+            "minting is not enabled for this token_id",
+        ));
+    }
+    if merkle_root.len() != 32 {
+        return Err(StdError::generic_err("merkle root must be 32 bytes"));
+    }
+    if airdrops_r(deps.storage, &token_id)
+        .may_load(&round.to_be_bytes())?
+        .is_some()
+    {
+        return Err(StdError::generic_err(format!(
+            "airdrop round {} of token_id {} already exists",
+            round, token_id
+        )));
+    }
+
+    let airdrop = AirdropRound {
+        token_id,
+        round,
+        merkle_root,
+        curator: info.sender,
+    };
+    airdrops_w(deps.storage, &airdrop.token_id).save(&round.to_be_bytes(), &airdrop)?;
+
+    Ok(
+        Response::new().set_data(to_binary(&ExecuteAnswer::RegisterAirdrop {
+            status: Success,
+        })?),
+    )
+}
+
+fn try_claim_airdrop(
+    mut deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    token_id: String,
+    round: u64,
+    amount: Uint256,
+    proof: Vec<Binary>,
+) -> StdResult<Response> {
+    let airdrop = airdrops_r(deps.storage, &token_id)
+        .may_load(&round.to_be_bytes())?
+        .ok_or_else(|| {
+            StdError::generic_err(format!(
+                "airdrop round {} of token_id {} does not exist",
+                round, token_id
+            ))
+        })?;
+    let sender_key = to_binary(&info.sender)?;
+    if airdrop_claims_r(deps.storage, &token_id, round)
+        .may_load(sender_key.as_slice())?
+        .is_some()
+    {
+        return Err(StdError::generic_err(format!(
+            "airdrop round {} of token_id {} has already been claimed",
+            round, token_id
+        )));
+    }
+
+    let mut leaf = info.sender.as_bytes().to_vec();
+    leaf.extend_from_slice(&amount.to_be_bytes());
+    if !verify_merkle_proof(sha_256(&leaf), &proof, airdrop.merkle_root.as_slice()) {
+        return Err(StdError::generic_err("invalid airdrop proof"));
+    }
+
+    let token_info = tkn_info_r(deps.storage).load(token_id.as_bytes())?;
+    if !token_info.token_config.flatten().enable_mint {
+        return Err(StdError::generic_err(
+            "minting is not enabled for this token_id",
+        ));
+    }
+
+    let mut config = contr_conf_r(deps.storage).load()?;
+    let mut messages = vec![];
+    let mut notifications = vec![];
+    impl_mint(
+        &mut deps,
+        &env,
+        &mut config,
+        &mut messages,
+        &mut notifications,
+        &airdrop.curator,
+        &token_info,
+        info.sender,
+        amount,
+        Some(format!("airdrop round {}", round)),
+        true,
+    )?;
+    contr_conf_w(deps.storage).save(&config)?;
+    airdrop_claims_w(deps.storage, &token_id, round).save(sender_key.as_slice(), &true)?;
+
+    let attributes = render_notifications(deps.storage, deps.api, notifications)?;
+    let data = to_binary(&ExecuteAnswer::ClaimAirdrop { status: Success })?;
+    Ok(Response::new()
+        .add_messages(messages)
+        .add_attributes(attributes)
+        .set_data(data))
+}
+
+#[allow(clippy::too_many_arguments)]
+fn try_set_sale(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    token_id: String,
+    price: Coin,
+    per_address_limit: Option<Uint256>,
+    start: Option<Expiration>,
+    end: Option<Expiration>,
+    max_sold: Option<Uint256>,
+    snip20_prices: Vec<Snip20Price>,
+) -> StdResult<Response> {
+    let token_info = match tkn_info_r(deps.storage).may_load(token_id.as_bytes())? {
+        Some(i) => i,
+        None => {
+            return Err(StdError::generic_err(format!(
+                "token_id {} does not exist",
+                token_id
+            )))
+        }
+    };
+    if token_info.curator != info.sender {
+        return Err(StdError::generic_err(format!(
+            "only the curator of token_id {} can set its sale",
+            token_id
+        )));
+    }
+    if !token_info.token_config.flatten().enable_mint {
+        return Err(StdError::generic_err(
+            "minting is not enabled for this token_id",
+        ));
+    }
+    if price.amount.is_zero() || snip20_prices.iter().any(|p| p.amount.is_zero()) {
+        return Err(StdError::generic_err(
+            "sale price must be greater than zero",
+        ));
+    }
+    let payment_tokens = contr_conf_r(deps.storage).load()?.payment_tokens;
+    for snip20_price in &snip20_prices {
+        if !payment_tokens
+            .iter()
+            .any(|t| t.address == snip20_price.token)
+        {
+            return Err(StdError::generic_err(format!(
+                "{} is not an accepted payment token",
+                snip20_price.token
+            )));
+        }
+    }
+
+    let (sold, proceeds) = match sales_r(deps.storage).may_load(token_id.as_bytes())? {
+        Some(sale) => {
+            if !sale.proceeds.is_zero() && sale.price.denom != price.denom {
+                return Err(StdError::generic_err(format!(
+                    "proceeds in {} must be withdrawn before changing the sale denom",
+                    sale.price.denom
+                )));
+            }
+            (sale.sold, sale.proceeds)
+        }
+        None => (Uint256::zero(), Uint128::zero()),
+    };
+    if let Some(max_sold) = max_sold {
+        if max_sold < sold {
+            return Err(StdError::generic_err(format!(
+                "max_sold cannot be less than the {} tokens of token_id {} already sold",
+                sold, token_id
+            )));
+        }
+    }
+
+    let sale = Sale {
+        token_id,
+        curator: info.sender,
+        price,
+        per_address_limit,
+        start,
+        end,
+        max_sold,
+        sold,
+        proceeds,
+        snip20_prices,
+    };
+    sales_w(deps.storage).save(sale.token_id.as_bytes(), &sale)?;
+
+    Ok(Response::new().set_data(to_binary(&ExecuteAnswer::SetSale { status: Success })?))
+}
+
+fn try_purchase(
+    mut deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    token_id: String,
+    quantity: Uint256,
+) -> StdResult<Response> {
+    let mut sale = load_sale(deps.storage, &token_id)?;
+
+    // payment
+    let mut paid = Uint128::zero();
+    for coin in &info.funds {
+        if coin.denom != sale.price.denom {
+            return Err(StdError::generic_err(format!(
+                "sale of token_id {} only accepts {}",
+                token_id, sale.price.denom
+            )));
+        }
+        paid = paid.checked_add(coin.amount)?;
+    }
+    let cost = Uint128::try_from(Uint256::from(sale.price.amount).checked_mul(quantity)?)?;
+    if paid < cost {
+        return Err(StdError::generic_err(format!(
+            "insufficient payment: {} tokens cost {}{}",
+            quantity, cost, sale.price.denom
+        )));
+    }
+
+    let mut messages = vec![];
+    let mut notifications = vec![];
+    exec_sale_mint(
+        &mut deps,
+        &env,
+        &mut messages,
+        &mut notifications,
+        &mut sale,
+        &info.sender,
+        quantity,
+    )?;
+    sale.proceeds = sale.proceeds.checked_add(cost)?;
+    sales_w(deps.storage).save(token_id.as_bytes(), &sale)?;
+
+    let attributes = render_notifications(deps.storage, deps.api, notifications)?;
+    let mut response = Response::new()
+        .add_messages(messages)
+        .add_attributes(attributes);
+    if paid > cost {
+        response = response.add_message(BankMsg::Send {
+            to_address: info.sender.to_string(),
+            amount: vec![Coin {
+                denom: sale.price.denom,
+                amount: paid - cost,
+            }],
+        });
+    }
+
+    Ok(response.set_data(to_binary(&ExecuteAnswer::Purchase { status: Success })?))
+}
+
+fn try_withdraw_sale_proceeds(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    token_id: String,
+) -> StdResult<Response> {
+    let mut sale = load_sale(deps.storage, &token_id)?;
+    if sale.curator != info.sender {
+        return Err(StdError::generic_err(format!(
+            "only the curator of token_id {} can withdraw its sale proceeds",
+            token_id
+        )));
+    }
+
+    let amount = Coin {
+        denom: sale.price.denom.clone(),
+        amount: sale.proceeds,
+    };
+    sale.proceeds = Uint128::zero();
+    sales_w(deps.storage).save(token_id.as_bytes(), &sale)?;
+
+    let mut response = Response::new();
+    if !amount.amount.is_zero() {
+        response = response.add_message(BankMsg::Send {
+            to_address: info.sender.to_string(),
+            amount: vec![amount.clone()],
+        });
+    }
+
+    Ok(response.set_data(to_binary(&ExecuteAnswer::WithdrawSaleProceeds { amount })?))
+}
+
+/// handles SNIP-20 `Receive` callbacks, which purchase tokens from a sale
+fn try_receive(
+    mut deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    from: Addr,
+    amount: Uint128,
+    msg: Option<Binary>,
+) -> StdResult<Response> {
+    let config = contr_conf_r(deps.storage).load()?;
+    let payment_token = match config
+        .payment_tokens
+        .iter()
+        .find(|t| t.address == info.sender)
+    {
+        Some(t) => t.clone(),
+        None => {
+            return Err(StdError::generic_err(format!(
+                "{} is not an accepted payment token",
+                info.sender
+            )))
+        }
+    };
+    let (token_id, quantity) = match msg {
+        Some(msg) => match from_binary(&msg)? {
+            Snip20ReceiveMsg::Purchase { token_id, quantity } => (token_id, quantity),
+        },
+        None => {
+            return Err(StdError::generic_err(
+                "SNIP-20 payments must include a `purchase` msg",
+            ))
+        }
+    };
+    let treasury = match config.treasury {
+        Some(t) => t,
+        None => {
+            return Err(StdError::generic_err(
+                "no treasury is configured for SNIP-20 payments",
+            ))
+        }
+    };
+
+    let mut sale = load_sale(deps.storage, &token_id)?;
+    let price = match sale.snip20_prices.iter().find(|p| p.token == info.sender) {
+        Some(p) => p.amount,
+        None => {
+            return Err(StdError::generic_err(format!(
+                "sale of token_id {} does not accept {}",
+                token_id, info.sender
+            )))
+        }
+    };
+    let cost = Uint128::try_from(Uint256::from(price).checked_mul(quantity)?)?;
+    if amount < cost {
+        return Err(StdError::generic_err(format!(
+            "insufficient payment: {} tokens cost {} of {}",
+            quantity, cost, info.sender
+        )));
+    }
+
+    let mut messages = vec![];
+    let mut notifications = vec![];
+    exec_sale_mint(
+        &mut deps,
+        &env,
+        &mut messages,
+        &mut notifications,
+        &mut sale,
+        &from,
+        quantity,
+    )?;
+    sales_w(deps.storage).save(token_id.as_bytes(), &sale)?;
+
+    messages.push(
+        Snip20HandleMsg::Transfer {
+            recipient: treasury,
+            amount: cost,
+            memo: Some(format!("sale of token_id {}", token_id)),
+            padding: None,
+        }
+        .into_cosmos_msg(
+            payment_token.code_hash.clone(),
+            payment_token.address.clone(),
+        )?,
+    );
+    if amount > cost {
+        messages.push(
+            Snip20HandleMsg::Transfer {
+                recipient: from,
+                amount: amount - cost,
+                memo: Some("refund".to_string()),
+                padding: None,
+            }
+            .into_cosmos_msg(payment_token.code_hash, payment_token.address)?,
+        );
+    }
+
+    let attributes = render_notifications(deps.storage, deps.api, notifications)?;
+    Ok(Response::new()
+        .add_messages(messages)
+        .add_attributes(attributes)
+        .set_data(to_binary(&ExecuteAnswer::Receive { status: Success })?))
+}
+
+fn try_set_user(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    token_id: String,
+    user: Option<Addr>,
+    expires: Option<Expiration>,
+) -> StdResult<Response> {
+    let token_info = match tkn_info_r(deps.storage).may_load(token_id.as_bytes())? {
+        Some(i) => i,
+        None => {
+            return Err(StdError::generic_err(format!(
+                "token_id {} does not exist",
+                token_id
+            )))
+        }
+    };
+    if !token_info.token_config.flatten().is_nft {
+        return Err(StdError::generic_err(format!(
+            "token_id {} is not an NFT",
+            token_id
+        )));
+    }
+    if may_get_current_owner(deps.storage, &token_id)?.as_ref() != Some(&info.sender) {
+        return Err(StdError::generic_err(format!(
+            "only the owner of token_id {} can set its user",
+            token_id
+        )));
+    }
+
+    match user {
+        Some(user) => {
+            let expires = expires.unwrap_or_default();
+            if expires.is_expired(&env.block) {
+                return Err(StdError::generic_err(format!(
+                    "user expiration has already passed: {}",
+                    expires
+                )));
+            }
+            let nft_user = NftUser {
+                user: deps.api.addr_validate(user.as_str())?,
+                expires,
+            };
+            nft_users_w(deps.storage).save(token_id.as_bytes(), &nft_user)?;
+        }
+        None => nft_users_w(deps.storage).remove(token_id.as_bytes()),
+    }
+
+    Ok(Response::new().set_data(to_binary(&ExecuteAnswer::SetUser { status: Success })?))
+}
+
+fn try_create_edition_series(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    series: TokenInfoMsg,
+    max_editions: u64,
+) -> StdResult<Response> {
+    let config = contr_conf_r(deps.storage).load()?;
+    verify_curator(&config, &info)?;
+    if !series.token_config.flatten().is_nft {
+        return Err(StdError::generic_err(
+            "edition series must have an NFT token_config",
+        ));
+    }
+    if max_editions == 0 {
+        return Err(StdError::generic_err(
+            "max_editions must be greater than zero",
+        ));
+    }
+    if tkn_info_r(deps.storage)
+        .may_load(series.token_id.as_bytes())?
+        .is_some()
+        || edition_series_r(deps.storage)
+            .may_load(series.token_id.as_bytes())?
+            .is_some()
+    {
+        return Err(StdError::generic_err(
+            "token_id already exists. Try a different id String",
+        ));
+    }
+    // the namespace, name and symbol are validated here, so that minting editions cannot fail on
+    // the inherited token info
+    verify_namespace(&config, &info, &series.token_id)?;
+    if !is_valid_name(&series.name) {
+        return Err(StdError::generic_err(
+            "Name is not in the expected format (3-30 UTF-8 bytes)",
+        ));
+    }
+    if !is_valid_symbol(&series.symbol) {
+        return Err(StdError::generic_err(format!(
+            "Ticker symbol is not in expected format [A-Z]{{3,6}}: {}",
+            &series.symbol
+        )));
+    }
+
+    let series = EditionSeries {
+        token_info: series,
+        max_editions,
+        minted: 0,
+        curator: info.sender,
+    };
+    edition_series_w(deps.storage).save(series.token_info.token_id.as_bytes(), &series)?;
+
+    Ok(
+        Response::new().set_data(to_binary(&ExecuteAnswer::CreateEditionSeries {
+            status: Success,
+        })?),
+    )
+}
+
+#[allow(clippy::too_many_arguments)]
+fn try_mint_edition(
+    mut deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    parent_token_id: String,
+    recipient: Addr,
+    public_metadata: Option<Metadata>,
+    private_metadata: Option<Metadata>,
+    memo: Option<String>,
+) -> StdResult<Response> {
+    let mut series = edition_series_r(deps.storage)
+        .may_load(parent_token_id.as_bytes())?
+        .ok_or_else(|| {
+            StdError::generic_err(format!("edition series {} does not exist", parent_token_id))
+        })?;
+    let mut config = contr_conf_r(deps.storage).load()?;
+    verify_curator(&config, &info)?;
+    if series.curator != info.sender {
+        return Err(StdError::generic_err(format!(
+            "only the curator of edition series {} can mint editions",
+            parent_token_id
+        )));
+    }
+    if series.minted >= series.max_editions {
+        return Err(StdError::generic_err(format!(
+            "all {} editions of series {} have been minted",
+            series.max_editions, parent_token_id
+        )));
+    }
+    let recipient = deps.api.addr_validate(recipient.as_str())?;
+
+    // skips serials taken by token_ids curated before the series was created
+    let mut serial = series.minted;
+    let token_id = loop {
+        serial += 1;
+        let token_id = format!("{}/{}", parent_token_id, serial);
+        if tkn_info_r(deps.storage)
+            .may_load(token_id.as_bytes())?
+            .is_none()
+        {
+            break token_id;
+        }
+    };
+    series.minted += 1;
+    let mut token_info = series.token_info.clone();
+    token_info.token_id = token_id.clone();
+    if public_metadata.is_some() {
+        token_info.public_metadata = public_metadata;
+    }
+    if private_metadata.is_some() {
+        token_info.private_metadata = private_metadata;
+    }
+
+    let mut notifications = vec![];
+    exec_curate_token_id(
+        &mut deps,
+        &env,
+        &info,
+        &mut config,
+        CurateTokenId {
+            token_info,
+            balances: vec![TokenIdBalance {
+                address: recipient,
+                amount: Uint256::from(1_u64),
+            }],
+        },
+        memo,
+        false,
+        &mut vec![],
+        &mut notifications,
+    )?;
+    contr_conf_w(deps.storage).save(&config)?;
+    edition_series_w(deps.storage).save(parent_token_id.as_bytes(), &series)?;
+
+    let attributes = render_notifications(deps.storage, deps.api, notifications)?;
+    Ok(Response::new()
+        .add_attributes(attributes)
+        .set_data(to_binary(&ExecuteAnswer::MintEdition { token_id })?))
+}
+
+fn try_fractionalize(
+    mut deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    nft_token_id: String,
+    new_token_id: String,
+    shares: Uint256,
+) -> StdResult<Response> {
+    let mut nft_info = match tkn_info_r(deps.storage).may_load(nft_token_id.as_bytes())? {
+        Some(i) => i,
+        None => {
+            return Err(StdError::generic_err(format!(
+                "token_id {} does not exist",
+                nft_token_id
+            )))
+        }
+    };
+    if !nft_info.token_config.flatten().is_nft {
+        return Err(StdError::generic_err(format!(
+            "token_id {} is not an NFT",
+            nft_token_id
+        )));
+    }
+    if may_get_current_owner(deps.storage, &nft_token_id)?.as_ref() != Some(&info.sender) {
+        return Err(StdError::generic_err(format!(
+            "only the owner of token_id {} can fractionalize it",
+            nft_token_id
+        )));
+    }
+    if shares.is_zero() {
+        return Err(StdError::generic_err(
+            "number of shares must be greater than zero",
+        ));
+    }
+    // whoever reconstitutes the NFT is not known yet
+    exec_lock_transfer_policy(
+        deps.storage,
+        &nft_token_id,
+        &info.sender,
+        None,
+        Uint256::from(1_u64),
+    )?;
+
+    let mut config = contr_conf_r(deps.storage).load()?;
+    let mut notifications = vec![];
+    // shares cannot be minted or burnt, so holding the whole supply always means holding every share
+    let shares_token = CurateTokenId {
+        token_info: TokenInfoMsg {
+            token_id: new_token_id.clone(),
+            name: nft_info.name.clone(),
+            symbol: nft_info.symbol.clone(),
+            token_config: TknConfig::Fungible {
+                minters: vec![],
+                decimals: 0,
+                public_total_supply: true,
+                enable_mint: false,
+                enable_burn: false,
+                minter_may_update_metadata: false,
+                minter_may_burn: false,
+                freezable: false,
+                transfers_locked_until: None,
+            },
+            public_metadata: nft_info.public_metadata.clone(),
+            private_metadata: None,
+        },
+        balances: vec![TokenIdBalance {
+            address: info.sender.clone(),
+            amount: shares,
+        }],
+    };
+    // the sender curates the shares, subject to the same namespace and quota checks as any
+    // other curated token_id
+    exec_curate_token_id(
+        &mut deps,
+        &env,
+        &info,
+        &mut config,
+        shares_token,
+        Some(format!("shares of token_id {}", nft_token_id)),
+        false,
+        &mut vec![],
+        &mut notifications,
+    )?;
+    contr_conf_w(deps.storage).save(&config)?;
+
+    // lock the NFT in the contract
+    impl_transfer(
+        &mut deps,
+        &env,
+        &info,
+        &nft_token_id,
+        &info.sender,
+        &env.contract.address,
+        Uint256::from(1_u64),
+        Some(format!("fractionalized as token_id {}", new_token_id)),
+        None,
+        None,
+        &mut notifications,
+    )?;
+
+    let mut shares_info = tkn_info_r(deps.storage).load(new_token_id.as_bytes())?;
+    shares_info.fraction_of = Some(nft_token_id.clone());
+    tkn_info_w(deps.storage).save(new_token_id.as_bytes(), &shares_info)?;
+    nft_info.fractionalized_as = Some(new_token_id);
+    tkn_info_w(deps.storage).save(nft_token_id.as_bytes(), &nft_info)?;
+
+    let attributes = render_notifications(deps.storage, deps.api, notifications)?;
+    Ok(Response::new()
+        .add_attributes(attributes)
+        .set_data(to_binary(&ExecuteAnswer::Fractionalize {
+            status: Success,
+        })?))
+}
+
+fn try_reconstitute(
+    mut deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    nft_token_id: String,
+) -> StdResult<Response> {
+    let mut nft_info = match tkn_info_r(deps.storage).may_load(nft_token_id.as_bytes())? {
+        Some(i) => i,
+        None => {
+            return Err(StdError::generic_err(format!(
+                "token_id {} does not exist",
+                nft_token_id
+            )))
+        }
+    };
+    let shares_token_id = match nft_info.fractionalized_as.clone() {
+        Some(i) => i,
+        None => {
+            return Err(StdError::generic_err(format!(
+                "token_id {} is not fractionalized",
+                nft_token_id
+            )))
+        }
+    };
+    let mut shares_info = tkn_info_r(deps.storage).load(shares_token_id.as_bytes())?;
+    let shares = tkn_tot_supply_r(deps.storage).load(shares_token_id.as_bytes())?;
+    let balance = balances_r(deps.storage, &shares_token_id)
+        .may_load(to_binary(&info.sender)?.as_slice())?
+        .unwrap_or_default();
+    if balance != shares {
+        return Err(StdError::generic_err(format!(
+            "all {} shares of token_id {} are required to reconstitute token_id {}",
+            shares, shares_token_id, nft_token_id
+        )));
+    }
+
+    // burn all shares
+    exec_change_balance(
+        deps.storage,
+        &shares_token_id,
+        Some(&info.sender),
+        None,
+        &shares,
+        &shares_info,
+        None,
+        None,
+    )?;
+    let mut config = contr_conf_r(deps.storage).load()?;
+    store_burn(
+        deps.storage,
+        &mut config,
+        &env.block,
+        &shares_token_id,
+        None,
+        deps.api.addr_canonicalize(info.sender.as_str())?,
+        shares,
+        Some(format!("reconstituted token_id {}", nft_token_id)),
+    )?;
+    contr_conf_w(deps.storage).save(&config)?;
+
+    // release the NFT
+    let contract_info = MessageInfo {
+        sender: env.contract.address.clone(),
+        funds: vec![],
+    };
+    let mut notifications = vec![];
+    impl_transfer(
+        &mut deps,
+        &env,
+        &contract_info,
+        &nft_token_id,
+        &env.contract.address,
+        &info.sender,
+        Uint256::from(1_u64),
+        Some(format!("reconstituted from token_id {}", shares_token_id)),
+        None,
+        None,
+        &mut notifications,
+    )?;
+
+    shares_info.fraction_of = None;
+    tkn_info_w(deps.storage).save(shares_token_id.as_bytes(), &shares_info)?;
+    nft_info.fractionalized_as = None;
+    tkn_info_w(deps.storage).save(nft_token_id.as_bytes(), &nft_info)?;
+
+    let attributes = render_notifications(deps.storage, deps.api, notifications)?;
+    Ok(Response::new()
+        .add_attributes(attributes)
+        .set_data(to_binary(&ExecuteAnswer::Reconstitute { status: Success })?))
+}
+
+/// freezes (or unfreezes if `frozen` == false) an address's balance of a token_id
+fn try_freeze(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    token_id: String,
+    address: Addr,
+    frozen: bool,
+) -> StdResult<Response> {
+    let token_info = tkn_info_r(deps.storage)
+        .may_load(token_id.as_bytes())?
+        .ok_or_else(|| StdError::generic_err(format!("token_id {} does not exist", token_id)))?;
+    if verify_minter(&token_info, &info).is_err() && token_info.curator != info.sender {
+        return Err(StdError::generic_err(format!(
+            "Only minters and the curator of token_id {} can freeze balances",
+            token_id
+        )));
+    }
+    if !token_info.token_config.flatten().freezable {
+        return Err(StdError::generic_err(format!(
+            "token_id {} is not freezable",
+            token_id
+        )));
+    }
+
+    let address = deps.api.addr_validate(address.as_str())?;
+    // tokens held by the contract (escrows, vesting schedules, locked NFTs) must always be releasable
+    if address == env.contract.address {
+        return Err(StdError::generic_err(
+            "the contract's own balances cannot be frozen",
+        ));
+    }
+    let address_key = to_binary(&address)?;
+    let is_frozen = frozen_accounts_r(deps.storage, &token_id)
+        .may_load(address_key.as_slice())?
+        .is_some();
+    if is_frozen == frozen {
+        return Err(StdError::generic_err(format!(
+            "{}'s balance of token_id {} is already {}",
+            address,
+            token_id,
+            if frozen { "frozen" } else { "unfrozen" }
+        )));
+    }
+    match frozen {
+        true => frozen_accounts_w(deps.storage, &token_id).save(address_key.as_slice(), &true)?,
+        false => frozen_accounts_w(deps.storage, &token_id).remove(address_key.as_slice()),
+    }
+
+    let mut config = contr_conf_r(deps.storage).load()?;
+    store_freeze(
+        deps.storage,
+        &mut config,
+        &env.block,
+        &token_id,
+        deps.api.addr_canonicalize(address.as_str())?,
+        deps.api.addr_canonicalize(info.sender.as_str())?,
+        frozen,
+    )?;
+    contr_conf_w(deps.storage).save(&config)?;
+
+    let answer = match frozen {
+        true => ExecuteAnswer::Freeze { status: Success },
+        false => ExecuteAnswer::Unfreeze { status: Success },
+    };
+    Ok(Response::new().set_data(to_binary(&answer)?))
+}
+
+fn try_freeze_metadata(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    token_id: String,
+) -> StdResult<Response> {
+    let mut token_info = tkn_info_r(deps.storage)
+        .may_load(token_id.as_bytes())?
+        .ok_or_else(|| StdError::generic_err(format!("token_id {} does not exist", token_id)))?;
+    if verify_minter(&token_info, &info).is_err() && token_info.curator != info.sender {
+        return Err(StdError::generic_err(format!(
+            "Only minters and the curator of token_id {} can freeze its metadata",
+            token_id
+        )));
+    }
+    if token_info.metadata_frozen {
+        return Err(StdError::generic_err(format!(
+            "the metadata of token_id {} is already frozen",
+            token_id
+        )));
+    }
+    token_info.metadata_frozen = true;
+    tkn_info_w(deps.storage).save(token_id.as_bytes(), &token_info)?;
+
+    Ok(
+        Response::new().set_data(to_binary(&ExecuteAnswer::FreezeMetadata {
+            status: Success,
+        })?),
+    )
+}
+
+fn try_set_transfer_policy(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    token_id: String,
+    policy: TransferPolicy,
+) -> StdResult<Response> {
+    verify_token_id_curator(deps.storage, &info, &token_id)?;
+    transfer_policies_w(deps.storage).save(token_id.as_bytes(), &policy)?;
+
+    Ok(
+        Response::new().set_data(to_binary(&ExecuteAnswer::SetTransferPolicy {
+            status: Success,
+        })?),
+    )
+}
+
+/// adds addresses to (or removes them from if `add` == false) a token_id's allowlist
+fn try_update_allowlist(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    token_id: String,
+    addresses: Vec<Addr>,
+    add: bool,
+) -> StdResult<Response> {
+    verify_token_id_curator(deps.storage, &info, &token_id)?;
+
+    for address in addresses {
+        let address_key = to_binary(&deps.api.addr_validate(address.as_str())?)?;
+        match add {
+            true => transfer_allowlists_w(deps.storage, &token_id)
+                .save(address_key.as_slice(), &true)?,
+            false => transfer_allowlists_w(deps.storage, &token_id).remove(address_key.as_slice()),
+        }
+    }
+
+    let answer = match add {
+        true => ExecuteAnswer::AddToAllowlist { status: Success },
+        false => ExecuteAnswer::RemoveFromAllowlist { status: Success },
+    };
+    Ok(Response::new().set_data(to_binary(&answer)?))
+}
+
+fn try_approve_transfer(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    token_id: String,
+    from: Addr,
+    recipient: Addr,
+    amount: Uint256,
+) -> StdResult<Response> {
+    verify_token_id_curator(deps.storage, &info, &token_id)?;
+
+    let key = to_binary(&(
+        deps.api.addr_validate(from.as_str())?,
+        deps.api.addr_validate(recipient.as_str())?,
+    ))?;
+    match amount.is_zero() {
+        true => transfer_approvals_w(deps.storage, &token_id).remove(key.as_slice()),
+        false => transfer_approvals_w(deps.storage, &token_id).save(key.as_slice(), &amount)?,
+    }
+
+    Ok(
+        Response::new().set_data(to_binary(&ExecuteAnswer::ApproveTransfer {
+            status: Success,
+        })?),
+    )
+}
+
+fn try_set_curator_quota(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    curator: Addr,
+    quota: Option<u64>,
+) -> StdResult<Response> {
+    let config = contr_conf_r(deps.storage).load()?;
+    verify_role(&config, &info, Role::CuratorManager)?;
+
+    let curator = deps.api.addr_validate(curator.as_str())?;
+    let curator_key = to_binary(&curator)?;
+    match quota {
+        Some(quota) => curator_quotas_w(deps.storage).save(curator_key.as_slice(), &quota)?,
+        None => curator_quotas_w(deps.storage).remove(curator_key.as_slice()),
+    }
+
+    Ok(
+        Response::new().set_data(to_binary(&ExecuteAnswer::SetCuratorQuota {
+            status: Success,
+        })?),
+    )
+}
+
+fn try_register_namespace(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    prefix: String,
+    owner: Addr,
+) -> StdResult<Response> {
+    let mut config = contr_conf_r(deps.storage).load()?;
+    verify_role(&config, &info, Role::CuratorManager)?;
+
+    if prefix.is_empty() {
+        return Err(StdError::generic_err("namespace prefix cannot be empty"));
+    }
+    if let Some(existing) = config
+        .namespaces
+        .iter()
+        .find(|n| n.prefix.starts_with(&prefix) || prefix.starts_with(&n.prefix))
+    {
+        return Err(StdError::generic_err(format!(
+            "namespace {} overlaps with existing namespace {}",
+            prefix, existing.prefix
+        )));
+    }
+    config.namespaces.push(Namespace {
+        prefix,
+        owner: deps.api.addr_validate(owner.as_str())?,
+    });
+    contr_conf_w(deps.storage).save(&config)?;
+
+    Ok(
+        Response::new().set_data(to_binary(&ExecuteAnswer::RegisterNamespace {
+            status: Success,
+        })?),
+    )
+}
+
+fn try_remove_namespace(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    prefix: String,
+) -> StdResult<Response> {
+    let mut config = contr_conf_r(deps.storage).load()?;
+    verify_role(&config, &info, Role::CuratorManager)?;
+
+    let old_len = config.namespaces.len();
+    config.namespaces.retain(|n| n.prefix != prefix);
+    if config.namespaces.len() == old_len {
+        return Err(StdError::generic_err(format!(
+            "namespace {} does not exist",
+            prefix
+        )));
+    }
+    contr_conf_w(deps.storage).save(&config)?;
+
+    Ok(
+        Response::new().set_data(to_binary(&ExecuteAnswer::RemoveNamespace {
+            status: Success,
+        })?),
+    )
+}
+
+fn try_add_payment_tokens(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    payment_tokens: Vec<PaymentToken>,
+    treasury: Option<Addr>,
+) -> StdResult<Response> {
+    let mut config = contr_conf_r(deps.storage).load()?;
+    verify_role(&config, &info, Role::Admin)?;
+
+    let messages = exec_add_payment_tokens(&env, &mut config, payment_tokens)?;
+    if treasury.is_some() {
+        config.treasury = treasury;
+    }
+    contr_conf_w(deps.storage).save(&config)?;
+
+    Ok(Response::new().add_messages(messages).set_data(to_binary(
+        &ExecuteAnswer::AddPaymentTokens { status: Success },
+    )?))
+}
+
+/// does not check if `token_id` exists so attacker cannot easily figure out if
+/// a `token_id` has been created
+#[allow(clippy::too_many_arguments)]
+fn try_give_permission(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    allowed_address: Addr,
+    token_id: String,
+    view_balance: Option<bool>,
+    view_balance_expiry: Option<Expiration>,
+    view_private_metadata: Option<bool>,
+    view_private_metadata_expiry: Option<Expiration>,
+    transfer: Option<Uint256>,
+    transfer_expiry: Option<Expiration>,
+    burn: Option<Uint256>,
+    burn_expiry: Option<Expiration>,
+) -> StdResult<Response> {
+    // may_load current permission
+    let permission_op =
+        may_load_any_permission(deps.storage, &info.sender, &token_id, &allowed_address)?;
+
+    let action = |old_perm: Permission,
+                  view_balance: Option<bool>,
+                  view_balance_expiry: Option<Expiration>,
                   view_private_metadata: Option<bool>,
                   view_private_metadata_expiry: Option<Expiration>,
                   transfer: Option<Uint256>,
-                  transfer_expiry: Option<Expiration>|
+                  transfer_expiry: Option<Expiration>,
+                  burn: Option<Uint256>,
+                  burn_expiry: Option<Expiration>|
      -> Permission {
         Permission {
             view_balance_perm: match view_balance {
@@ -613,11 +2690,22 @@ fn try_give_permission(
                 Some(i) => i,
                 None => old_perm.trfer_allowance_exp,
             },
+            burn_allowance_perm: match burn {
+                Some(i) => i,
+                None => old_perm.burn_allowance_perm,
+            },
+            burn_allowance_exp: match burn_expiry {
+                Some(i) => i,
+                None => old_perm.burn_allowance_exp,
+            },
         }
     };
 
+    // notify the allowed address if its transfer or burn allowance changes
+    let allowance_changed = transfer.is_some() || burn.is_some();
+
     // create new permission if not created yet, otherwise update existing permission
-    match permission_op {
+    let updated_permission = match permission_op {
         Some(old_perm) => {
             let updated_permission = action(
                 old_perm,
@@ -627,6 +2715,8 @@ fn try_give_permission(
                 view_private_metadata_expiry,
                 transfer,
                 transfer_expiry,
+                burn,
+                burn_expiry,
             );
             update_permission(
                 deps.storage,
@@ -635,6 +2725,7 @@ fn try_give_permission(
                 &allowed_address,
                 &updated_permission,
             )?;
+            updated_permission
         }
         None => {
             let default_permission = Permission::default();
@@ -646,6 +2737,8 @@ fn try_give_permission(
                 view_private_metadata_expiry,
                 transfer,
                 transfer_expiry,
+                burn,
+                burn_expiry,
             );
             new_permission(
                 deps.storage,
@@ -654,14 +2747,29 @@ fn try_give_permission(
                 &allowed_address,
                 &updated_permission,
             )?;
+            updated_permission
         }
     };
 
-    Ok(
-        Response::new().set_data(to_binary(&ExecuteAnswer::GivePermission {
+    let mut notifications = vec![];
+    if allowance_changed && allowed_address != info.sender {
+        notifications.push(Notification::new(
+            allowed_address,
+            NotificationData::Allowance {
+                token_id,
+                owner: info.sender,
+                transfer_allowance: updated_permission.trfer_allowance_perm,
+                burn_allowance: updated_permission.burn_allowance_perm,
+            },
+        ));
+    }
+
+    let attributes = render_notifications(deps.storage, deps.api, notifications)?;
+    Ok(Response::new()
+        .add_attributes(attributes)
+        .set_data(to_binary(&ExecuteAnswer::GivePermission {
             status: Success,
-        })?),
-    )
+        })?))
 }
 
 /// changes an existing permission entry to default (ie: revoke all permissions granted). Does not remove
@@ -683,155 +2791,396 @@ fn try_revoke_permission(
         ));
     }
 
-    update_permission(
-        deps.storage,
-        &owner,
-        &token_id,
-        &allowed_addr,
-        &Permission::default(),
-    )?;
+    update_permission(
+        deps.storage,
+        &owner,
+        &token_id,
+        &allowed_addr,
+        &Permission::default(),
+    )?;
+
+    // notify the allowed address if the owner revoked its allowances
+    let mut notifications = vec![];
+    if allowed_addr != info.sender {
+        notifications.push(Notification::new(
+            allowed_addr,
+            NotificationData::Allowance {
+                token_id,
+                owner,
+                transfer_allowance: Uint256::zero(),
+                burn_allowance: Uint256::zero(),
+            },
+        ));
+    }
+
+    let attributes = render_notifications(deps.storage, deps.api, notifications)?;
+    Ok(Response::new()
+        .add_attributes(attributes)
+        .set_data(to_binary(&ExecuteAnswer::RevokePermission {
+            status: Success,
+        })?))
+}
+
+fn try_create_viewing_key(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    entropy: String,
+) -> StdResult<Response> {
+    let key = ViewingKey::create(
+        deps.storage,
+        &info,
+        &env,
+        info.sender.as_str(),
+        entropy.as_ref(),
+    );
+
+    Ok(Response::new().set_data(to_binary(&ExecuteAnswer::CreateViewingKey { key })?))
+}
+
+fn try_set_viewing_key(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    key: String,
+) -> StdResult<Response> {
+    ViewingKey::set(deps.storage, info.sender.as_str(), key.as_str());
+    Ok(
+        Response::new().set_data(to_binary(&ExecuteAnswer::SetViewingKey {
+            status: Success,
+        })?),
+    )
+}
+
+fn try_revoke_permit(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    permit_name: String,
+) -> StdResult<Response> {
+    RevokedPermits::revoke_permit(
+        deps.storage,
+        PREFIX_REVOKED_PERMITS,
+        info.sender.as_ref(),
+        &permit_name,
+    );
+
+    Ok(Response::new().set_data(to_binary(&ExecuteAnswer::RevokePermit { status: Success })?))
+}
+
+fn try_add_curators(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    add_curators: Vec<Addr>,
+) -> StdResult<Response> {
+    let mut config = contr_conf_r(deps.storage).load()?;
+    let action = AdminAction::AddCurators { add_curators };
+
+    verify_role(&config, &info, action.role())?;
+    verify_not_time_locked(&config)?;
+
+    exec_admin_action(&mut config, &env.block, action)?;
+    contr_conf_w(deps.storage).save(&config)?;
+
+    Ok(Response::new().set_data(to_binary(&ExecuteAnswer::AddCurators { status: Success })?))
+}
+
+fn try_remove_curators(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    remove_curators: Vec<Addr>,
+) -> StdResult<Response> {
+    let mut config = contr_conf_r(deps.storage).load()?;
+    let action = AdminAction::RemoveCurators { remove_curators };
+
+    verify_role(&config, &info, action.role())?;
+    verify_not_time_locked(&config)?;
+
+    exec_admin_action(&mut config, &env.block, action)?;
+    contr_conf_w(deps.storage).save(&config)?;
+
+    Ok(
+        Response::new().set_data(to_binary(&ExecuteAnswer::RemoveCurators {
+            status: Success,
+        })?),
+    )
+}
+
+fn try_change_admin(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    new_admin: Addr,
+) -> StdResult<Response> {
+    let mut config = contr_conf_r(deps.storage).load()?;
+    let action = AdminAction::ChangeAdmin {
+        new_admin: deps.api.addr_validate(new_admin.as_str())?,
+    };
+
+    verify_role(&config, &info, action.role())?;
+    verify_not_time_locked(&config)?;
+
+    exec_admin_action(&mut config, &env.block, action)?;
+    contr_conf_w(deps.storage).save(&config)?;
+
+    Ok(Response::new().set_data(to_binary(&ExecuteAnswer::ChangeAdmin { status: Success })?))
+}
+
+fn try_propose_admin(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    new_admin: Addr,
+    expiration: Option<Expiration>,
+) -> StdResult<Response> {
+    let mut config = contr_conf_r(deps.storage).load()?;
+    let action = AdminAction::ProposeAdmin {
+        new_admin: deps.api.addr_validate(new_admin.as_str())?,
+        expiration,
+    };
+
+    verify_role(&config, &info, action.role())?;
+    verify_not_time_locked(&config)?;
+
+    exec_admin_action(&mut config, &env.block, action)?;
+    contr_conf_w(deps.storage).save(&config)?;
+
+    Ok(Response::new().set_data(to_binary(&ExecuteAnswer::ProposeAdmin { status: Success })?))
+}
+
+fn try_accept_admin(deps: DepsMut, env: Env, info: MessageInfo) -> StdResult<Response> {
+    let mut config = contr_conf_r(deps.storage).load()?;
+
+    let proposal = match config.pending_admin.take() {
+        Some(proposal) if proposal.address == info.sender => proposal,
+        _ => {
+            return Err(StdError::generic_err(
+                "you have not been proposed as the admin",
+            ))
+        }
+    };
+    if let Some(expiration) = proposal.expiration {
+        if expiration.is_expired(&env.block) {
+            return Err(StdError::generic_err("the admin proposal has expired"));
+        }
+    }
+    config.admin = Some(proposal.address);
+    contr_conf_w(deps.storage).save(&config)?;
+
+    Ok(Response::new().set_data(to_binary(&ExecuteAnswer::AcceptAdmin { status: Success })?))
+}
+
+fn try_cancel_admin_proposal(deps: DepsMut, _env: Env, info: MessageInfo) -> StdResult<Response> {
+    let mut config = contr_conf_r(deps.storage).load()?;
+    verify_role(&config, &info, Role::Admin)?;
+
+    if config.pending_admin.take().is_none() {
+        return Err(StdError::generic_err("there is no pending admin proposal"));
+    }
+    contr_conf_w(deps.storage).save(&config)?;
 
     Ok(
-        Response::new().set_data(to_binary(&ExecuteAnswer::RevokePermission {
+        Response::new().set_data(to_binary(&ExecuteAnswer::CancelAdminProposal {
             status: Success,
         })?),
     )
 }
 
-fn try_create_viewing_key(
+fn try_remove_admin(
     deps: DepsMut,
-    env: Env,
+    _env: Env,
     info: MessageInfo,
-    entropy: String,
+    current_admin: Addr,
+    contract_address: Addr,
 ) -> StdResult<Response> {
-    let key = ViewingKey::create(
-        deps.storage,
-        &info,
-        &env,
-        info.sender.as_str(),
-        entropy.as_ref(),
-    );
+    let mut config = contr_conf_r(deps.storage).load()?;
 
-    Ok(Response::new().set_data(to_binary(&ExecuteAnswer::CreateViewingKey { key })?))
-}
+    verify_role(&config, &info, Role::Admin)?;
 
-fn try_set_viewing_key(
-    deps: DepsMut,
-    _env: Env,
-    info: MessageInfo,
-    key: String,
-) -> StdResult<Response> {
-    ViewingKey::set(deps.storage, info.sender.as_str(), key.as_str());
-    Ok(
-        Response::new().set_data(to_binary(&ExecuteAnswer::SetViewingKey {
-            status: Success,
-        })?),
-    )
+    // checks on redundancy inputs, designed to reduce chances of accidentally
+    // calling this function
+    if config.admin.as_ref() != Some(&current_admin) || contract_address != config.contract_address
+    {
+        return Err(StdError::generic_err(
+            "your inputs are incorrect to perform this function",
+        ));
+    }
+
+    // remove admin, and every role it has granted
+    config.admin = None;
+    config.roles.clear();
+    config.pending_admin = None;
+    contr_conf_w(deps.storage).save(&config)?;
+
+    Ok(Response::new().set_data(to_binary(&ExecuteAnswer::RemoveAdmin { status: Success })?))
 }
 
-fn try_revoke_permit(
+fn try_grant_role(
     deps: DepsMut,
-    _env: Env,
+    env: Env,
     info: MessageInfo,
-    permit_name: String,
+    role: Role,
+    address: Addr,
 ) -> StdResult<Response> {
-    RevokedPermits::revoke_permit(
-        deps.storage,
-        PREFIX_REVOKED_PERMITS,
-        info.sender.as_ref(),
-        &permit_name,
-    );
+    let mut config = contr_conf_r(deps.storage).load()?;
+    let action = AdminAction::GrantRole {
+        role,
+        address: deps.api.addr_validate(address.as_str())?,
+    };
 
-    Ok(Response::new().set_data(to_binary(&ExecuteAnswer::RevokePermit { status: Success })?))
+    verify_role(&config, &info, action.role())?;
+    verify_not_time_locked(&config)?;
+
+    exec_admin_action(&mut config, &env.block, action)?;
+    contr_conf_w(deps.storage).save(&config)?;
+
+    Ok(Response::new().set_data(to_binary(&ExecuteAnswer::GrantRole { status: Success })?))
 }
 
-fn try_add_curators(
+fn try_revoke_role(
     deps: DepsMut,
-    _env: Env,
+    env: Env,
     info: MessageInfo,
-    add_curators: Vec<Addr>,
+    role: Role,
+    address: Addr,
 ) -> StdResult<Response> {
     let mut config = contr_conf_r(deps.storage).load()?;
+    let action = AdminAction::RevokeRole { role, address };
 
-    // verify admin
-    verify_admin(&config, &info)?;
+    verify_role(&config, &info, action.role())?;
+    verify_not_time_locked(&config)?;
 
-    // add curators
-    for curator in add_curators {
-        config.curators.push(curator);
-    }
+    exec_admin_action(&mut config, &env.block, action)?;
     contr_conf_w(deps.storage).save(&config)?;
 
-    Ok(Response::new().set_data(to_binary(&ExecuteAnswer::AddCurators { status: Success })?))
+    Ok(Response::new().set_data(to_binary(&ExecuteAnswer::RevokeRole { status: Success })?))
 }
 
-fn try_remove_curators(
+fn try_set_contract_status(
     deps: DepsMut,
-    _env: Env,
+    env: Env,
     info: MessageInfo,
-    remove_curators: Vec<Addr>,
+    level: ContractStatus,
 ) -> StdResult<Response> {
     let mut config = contr_conf_r(deps.storage).load()?;
+    let action = AdminAction::SetContractStatus { level };
 
-    // verify admin
-    verify_admin(&config, &info)?;
+    verify_role(&config, &info, action.role())?;
+    verify_not_time_locked(&config)?;
 
-    // remove curators
-    for curator in remove_curators {
-        config.curators.retain(|x| x != &curator);
-    }
+    exec_admin_action(&mut config, &env.block, action)?;
     contr_conf_w(deps.storage).save(&config)?;
 
     Ok(
-        Response::new().set_data(to_binary(&ExecuteAnswer::RemoveCurators {
+        Response::new().set_data(to_binary(&ExecuteAnswer::SetContractStatus {
             status: Success,
         })?),
     )
 }
 
-fn try_change_admin(
+fn try_queue_admin_action(
     deps: DepsMut,
-    _env: Env,
+    env: Env,
     info: MessageInfo,
-    new_admin: Addr,
+    action: AdminAction,
 ) -> StdResult<Response> {
     let mut config = contr_conf_r(deps.storage).load()?;
+    verify_role(&config, &info, action.role())?;
 
-    // verify admin
-    verify_admin(&config, &info)?;
-
-    // change admin
-    config.admin = Some(new_admin);
+    let action = match action {
+        AdminAction::ChangeAdmin { new_admin } => AdminAction::ChangeAdmin {
+            new_admin: deps.api.addr_validate(new_admin.as_str())?,
+        },
+        AdminAction::ProposeAdmin {
+            new_admin,
+            expiration,
+        } => AdminAction::ProposeAdmin {
+            new_admin: deps.api.addr_validate(new_admin.as_str())?,
+            expiration,
+        },
+        AdminAction::GrantRole { role, address } => AdminAction::GrantRole {
+            role,
+            address: deps.api.addr_validate(address.as_str())?,
+        },
+        action => action,
+    };
+    let executable_at = env
+        .block
+        .time
+        .seconds()
+        .checked_add(config.admin_action_delay)
+        .ok_or_else(|| StdError::generic_err("admin_action_delay is too long"))?;
+    let action_id = config.admin_action_cnt;
+    let mut queue = admin_actions_r(deps.storage)
+        .may_load()?
+        .unwrap_or_default();
+    queue.push(QueuedAdminAction {
+        id: action_id,
+        action,
+        queued_by: info.sender,
+        executable_at,
+    });
+    admin_actions_w(deps.storage).save(&queue)?;
+    config.admin_action_cnt += 1;
     contr_conf_w(deps.storage).save(&config)?;
 
-    Ok(Response::new().set_data(to_binary(&ExecuteAnswer::ChangeAdmin { status: Success })?))
+    Ok(Response::new().set_data(to_binary(&ExecuteAnswer::QueueAdminAction { action_id })?))
 }
 
-fn try_remove_admin(
+fn try_execute_admin_action(
     deps: DepsMut,
-    _env: Env,
+    env: Env,
     info: MessageInfo,
-    current_admin: Addr,
-    contract_address: Addr,
+    action_id: u64,
 ) -> StdResult<Response> {
     let mut config = contr_conf_r(deps.storage).load()?;
+    let mut queue = admin_actions_r(deps.storage)
+        .may_load()?
+        .unwrap_or_default();
+    let pos = find_admin_action(&queue, action_id)?;
+    verify_role(&config, &info, queue[pos].action.role())?;
 
-    // verify admin
-    verify_admin(&config, &info)?;
-
-    // checks on redundancy inputs, designed to reduce chances of accidentally
-    // calling this function
-    if current_admin != config.admin.unwrap() || contract_address != config.contract_address {
-        return Err(StdError::generic_err(
-            "your inputs are incorrect to perform this function",
-        ));
+    if env.block.time.seconds() < queue[pos].executable_at {
+        return Err(StdError::generic_err(format!(
+            "admin action {} cannot be executed until {}",
+            action_id, queue[pos].executable_at
+        )));
     }
-
-    // remove admin
-    config.admin = None;
+    let queued = queue.remove(pos);
+    exec_admin_action(&mut config, &env.block, queued.action)?;
+    admin_actions_w(deps.storage).save(&queue)?;
     contr_conf_w(deps.storage).save(&config)?;
 
-    Ok(Response::new().set_data(to_binary(&ExecuteAnswer::RemoveAdmin { status: Success })?))
+    Ok(
+        Response::new().set_data(to_binary(&ExecuteAnswer::ExecuteAdminAction {
+            status: Success,
+        })?),
+    )
+}
+
+fn try_cancel_admin_action(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    action_id: u64,
+) -> StdResult<Response> {
+    let config = contr_conf_r(deps.storage).load()?;
+    let mut queue = admin_actions_r(deps.storage)
+        .may_load()?
+        .unwrap_or_default();
+    let pos = find_admin_action(&queue, action_id)?;
+    verify_role(&config, &info, queue[pos].action.role())?;
+
+    queue.remove(pos);
+    admin_actions_w(deps.storage).save(&queue)?;
+
+    Ok(
+        Response::new().set_data(to_binary(&ExecuteAnswer::CancelAdminAction {
+            status: Success,
+        })?),
+    )
 }
 
 fn try_register_receive(
@@ -839,8 +3188,18 @@ fn try_register_receive(
     _env: Env,
     info: MessageInfo,
     code_hash: String,
+    token_ids: Option<Vec<String>>,
+    reject_unlisted_token_ids: bool,
 ) -> StdResult<Response> {
-    set_receiver_hash(deps.storage, &info.sender, code_hash);
+    set_receiver(
+        deps.storage,
+        &info.sender,
+        &ReceiverRegistration {
+            code_hash,
+            token_ids,
+            reject_unlisted_token_ids,
+        },
+    )?;
 
     let data = to_binary(&ExecuteAnswer::RegisterReceive { status: Success })?;
     Ok(Response::new()
@@ -848,6 +3207,15 @@ fn try_register_receive(
         .set_data(data))
 }
 
+fn try_unregister_receive(deps: DepsMut, _env: Env, info: MessageInfo) -> StdResult<Response> {
+    remove_receiver(deps.storage, &info.sender);
+
+    let data = to_binary(&ExecuteAnswer::UnregisterReceive { status: Success })?;
+    Ok(Response::new()
+        .add_attribute("unregister_status", "success")
+        .set_data(data))
+}
+
 /////////////////////////////////////////////////////////////////////////////////
 // Private functions
 /////////////////////////////////////////////////////////////////////////////////
@@ -875,20 +3243,183 @@ fn is_valid_symbol(symbol: &str) -> bool {
     len_is_valid && symbol.bytes().all(|byte| byte.is_ascii_uppercase())
 }
 
-fn verify_admin(contract_config: &ContractConfig, info: &MessageInfo) -> StdResult<()> {
-    let admin_op = &contract_config.admin;
-    match admin_op {
-        Some(admin) => {
-            if admin != &info.sender {
-                return Err(StdError::generic_err("This is an admin function"));
+/// enforces a token_id's transfer policy on both the sender and recipient, using up the curator
+/// approval of transfers that need one. Transfers to and from the contract do not need approval
+fn exec_transfer_policy(
+    storage: &mut dyn Storage,
+    token_id: &str,
+    remove_from: Option<&Addr>,
+    add_to: Option<&Addr>,
+    amount: &Uint256,
+) -> StdResult<()> {
+    if let Some(reason) =
+        transfer_policy_violation(storage, token_id, remove_from, add_to, *amount)?
+    {
+        return Err(StdError::generic_err(reason));
+    }
+
+    let policy = transfer_policies_r(storage).may_load(token_id.as_bytes())?;
+    if let (Some(TransferPolicy::CuratorApproval), Some(from), Some(to)) =
+        (policy, remove_from, add_to)
+    {
+        let contract_address = contr_conf_r(storage).load()?.contract_address;
+        if from == &contract_address || to == &contract_address {
+            return Ok(());
+        }
+        let key = to_binary(&(from, to))?;
+        let remaining = transfer_approvals_r(storage, token_id).load(key.as_slice())? - *amount;
+        match remaining.is_zero() {
+            true => transfer_approvals_w(storage, token_id).remove(key.as_slice()),
+            false => transfer_approvals_w(storage, token_id).save(key.as_slice(), &remaining)?,
+        }
+    }
+    Ok(())
+}
+
+/// transfers to and from the contract do not need curator approval, so when tokens are locked in
+/// the contract, this enforces the token_id's transfer policy on the address they will be released
+/// to, using up the approval. `recipient` == `None` if it is not known yet, which is not allowed
+/// for token_ids that need curator approval
+fn exec_lock_transfer_policy(
+    storage: &mut dyn Storage,
+    token_id: &str,
+    from: &Addr,
+    recipient: Option<&Addr>,
+    amount: Uint256,
+) -> StdResult<()> {
+    match recipient {
+        Some(recipient) => {
+            exec_transfer_policy(storage, token_id, Some(from), Some(recipient), &amount)
+        }
+        None => match transfer_policies_r(storage).may_load(token_id.as_bytes())? {
+            Some(TransferPolicy::CuratorApproval) => Err(StdError::generic_err(format!(
+                "transfers of token_id {} need curator approval, so they must have a known recipient",
+                token_id
+            ))),
+            _ => Ok(()),
+        },
+    }
+}
+
+/// returns the position of a queued admin action
+fn find_admin_action(queue: &[QueuedAdminAction], action_id: u64) -> StdResult<usize> {
+    queue
+        .iter()
+        .position(|a| a.id == action_id)
+        .ok_or_else(|| StdError::generic_err(format!("admin action {} does not exist", action_id)))
+}
+
+/// performs an admin action on the contract config, which the caller must save
+fn exec_admin_action(
+    config: &mut ContractConfig,
+    block: &BlockInfo,
+    action: AdminAction,
+) -> StdResult<()> {
+    match action {
+        AdminAction::AddCurators { add_curators } => config.curators.extend(add_curators),
+        AdminAction::RemoveCurators { remove_curators } => {
+            for curator in remove_curators {
+                config.curators.retain(|x| x != &curator);
+            }
+        }
+        // replaces any pending proposal
+        AdminAction::ChangeAdmin { new_admin } => {
+            config.admin = Some(new_admin);
+            config.pending_admin = None;
+        }
+        // replaces any pending proposal
+        AdminAction::ProposeAdmin {
+            new_admin,
+            expiration,
+        } => {
+            if let Some(expiration) = &expiration {
+                if expiration.is_expired(block) {
+                    return Err(StdError::generic_err(
+                        "the admin proposal expiration has already passed",
+                    ));
+                }
+            }
+            config.pending_admin = Some(AdminProposal {
+                address: new_admin,
+                expiration,
+            });
+        }
+        AdminAction::GrantRole { role, address } => {
+            if config
+                .roles
+                .iter()
+                .any(|r| r.role == role && r.address == address)
+            {
+                return Err(StdError::generic_err(format!(
+                    "{} already has the {} role",
+                    address,
+                    role.as_str()
+                )));
+            }
+            config.roles.push(RoleHolder { role, address });
+        }
+        AdminAction::RevokeRole { role, address } => {
+            let old_len = config.roles.len();
+            config
+                .roles
+                .retain(|r| !(r.role == role && r.address == address));
+            if config.roles.len() == old_len {
+                return Err(StdError::generic_err(format!(
+                    "{} does not have the {} role",
+                    address,
+                    role.as_str()
+                )));
             }
         }
-        None => return Err(StdError::generic_err("This contract has no admin")),
+        AdminAction::SetContractStatus { level } => config.status = level,
     }
+    Ok(())
+}
 
+/// time-locked admin actions can only be performed directly if the contract has no
+/// `admin_action_delay`
+fn verify_not_time_locked(contract_config: &ContractConfig) -> StdResult<()> {
+    if contract_config.admin_action_delay > 0 {
+        return Err(StdError::generic_err(
+            "This action is time-locked and must be queued with QueueAdminAction",
+        ));
+    }
     Ok(())
 }
 
+/// verifies if sender holds `role`, either directly or as an admin
+fn verify_role(contract_config: &ContractConfig, info: &MessageInfo, role: Role) -> StdResult<()> {
+    if has_role(contract_config, &info.sender, role) {
+        return Ok(());
+    }
+    if contract_config.admin.is_none() && contract_config.roles.is_empty() {
+        return Err(StdError::generic_err("This contract has no admin"));
+    }
+    match role {
+        Role::Admin => Err(StdError::generic_err("This is an admin function")),
+        _ => Err(StdError::generic_err(format!(
+            "This is an admin function, which can also be called with the {} role",
+            role.as_str()
+        ))),
+    }
+}
+
+/// the admin, and addresses granted `Role::Admin`, hold every role except `MetadataManager`,
+/// which must be granted explicitly because admins cannot otherwise change token_ids
+fn has_role(contract_config: &ContractConfig, address: &Addr, role: Role) -> bool {
+    let admin_implies_role = role != Role::MetadataManager;
+    let is_admin = contract_config.admin.as_ref() == Some(address)
+        || contract_config
+            .roles
+            .iter()
+            .any(|r| &r.address == address && r.role == Role::Admin);
+    (admin_implies_role && is_admin)
+        || contract_config
+            .roles
+            .iter()
+            .any(|r| &r.address == address && r.role == role)
+}
+
 /// verifies if sender is a curator
 fn verify_curator(contract_config: &ContractConfig, info: &MessageInfo) -> StdResult<()> {
     let curators = &contract_config.curators;
@@ -900,6 +3431,65 @@ fn verify_curator(contract_config: &ContractConfig, info: &MessageInfo) -> StdRe
     Ok(())
 }
 
+/// verifies that `token_id` is not in another address's namespace, and that it is in one of the
+/// sender's namespaces if the sender owns any
+fn verify_namespace(
+    contract_config: &ContractConfig,
+    info: &MessageInfo,
+    token_id: &str,
+) -> StdResult<()> {
+    if let Some(namespace) = contract_config
+        .namespaces
+        .iter()
+        .find(|n| token_id.starts_with(&n.prefix))
+    {
+        if namespace.owner != info.sender {
+            return Err(StdError::generic_err(format!(
+                "token_id {} is in namespace {}, which belongs to {}",
+                token_id, namespace.prefix, namespace.owner
+            )));
+        }
+    } else if contract_config
+        .namespaces
+        .iter()
+        .any(|n| n.owner == info.sender)
+    {
+        return Err(StdError::generic_err(format!(
+            "token_id {} must be in one of your namespaces",
+            token_id
+        )));
+    }
+    Ok(())
+}
+
+/// verifies that the sender may curate `token_id` if it is derived from another token_id, ie:
+/// `{parent}/{suffix}`. Token_ids derived from an existing token_id or edition series can only be
+/// curated by its curator
+fn verify_derived_token_id(
+    storage: &dyn Storage,
+    info: &MessageInfo,
+    token_id: &str,
+) -> StdResult<()> {
+    for (pos, _) in token_id.match_indices('/') {
+        let parent = &token_id[..pos];
+        let parent_curator = match tkn_info_r(storage).may_load(parent.as_bytes())? {
+            Some(parent_info) => Some(parent_info.curator),
+            None => edition_series_r(storage)
+                .may_load(parent.as_bytes())?
+                .map(|series| series.curator),
+        };
+        if let Some(curator) = parent_curator {
+            if curator != info.sender {
+                return Err(StdError::generic_err(format!(
+                    "token_id {} is reserved for the curator of token_id {}",
+                    token_id, parent
+                )));
+            }
+        }
+    }
+    Ok(())
+}
+
 /// verifies if sender is a minter of the specific token_id
 fn verify_minter(token_info: &StoredTokenInfo, info: &MessageInfo) -> StdResult<()> {
     let minters = &token_info.token_config.flatten().minters;
@@ -909,10 +3499,109 @@ fn verify_minter(token_info: &StoredTokenInfo, info: &MessageInfo) -> StdResult<
             token_info.token_id
         )));
     }
-    Ok(())
+    Ok(())
+}
+
+/// verifies if sender is the curator of an existing token_id
+fn verify_token_id_curator(
+    storage: &dyn Storage,
+    info: &MessageInfo,
+    token_id: &str,
+) -> StdResult<()> {
+    let token_info_op = tkn_info_r(storage).may_load(token_id.as_bytes())?;
+    match token_info_op {
+        Some(token_info) if token_info.curator == info.sender => Ok(()),
+        _ => Err(StdError::generic_err(format!(
+            "only the curator of token_id {} can change its transfer policy",
+            token_id
+        ))),
+    }
+}
+
+/// verifies that `address`'s balance of a token_id has not been frozen
+fn verify_not_frozen(
+    storage: &dyn Storage,
+    token_info: &StoredTokenInfo,
+    address: &Addr,
+) -> StdResult<()> {
+    if token_info.token_config.flatten().freezable
+        && frozen_accounts_r(storage, &token_info.token_id)
+            .may_load(to_binary(address)?.as_slice())?
+            .is_some()
+    {
+        return Err(StdError::generic_err(format!(
+            "{}'s balance of token_id {} is frozen",
+            address, token_info.token_id
+        )));
+    }
+    Ok(())
+}
+
+/// verifies if sender can burn `amount` tokens on behalf of `owner`, ie: sender is a minter and
+/// `minter_may_burn` == true, or sender has enough burn allowance. Reduces the burn allowance if used
+fn verify_burner(
+    storage: &mut dyn Storage,
+    env: &Env,
+    info: &MessageInfo,
+    token_info: &StoredTokenInfo,
+    owner: &Addr,
+    amount: Uint256,
+) -> StdResult<()> {
+    // tokens held by the contract are locked for escrows, swap offers, vesting schedules and
+    // fractionalized NFTs
+    if owner == &env.contract.address {
+        return Err(StdError::generic_err(format!(
+            "tokens of token_id {} held by the contract cannot be burnt",
+            token_info.token_id
+        )));
+    }
+
+    if token_info.token_config.flatten().minter_may_burn && verify_minter(token_info, info).is_ok()
+    {
+        return Ok(());
+    }
+
+    let permission_op =
+        may_load_any_permission(storage, owner, &token_info.token_id, &info.sender)?;
+    match permission_op {
+        // no burn allowance given
+        None => (),
+        Some(perm) if perm.burn_allowance_perm.is_zero() => (),
+        // allowance has expired
+        Some(perm) if perm.burn_allowance_exp.is_expired(&env.block) => {
+            return Err(StdError::generic_err(format!(
+                "Burn allowance has expired: {}",
+                perm.burn_allowance_exp
+            )))
+        }
+        // not enough allowance to burn amount
+        Some(perm) if perm.burn_allowance_perm < amount => {
+            return Err(StdError::generic_err(format!(
+                "Insufficient burn allowance: {}",
+                perm.burn_allowance_perm
+            )))
+        }
+        // success, so need to reduce allowance
+        Some(mut perm) => {
+            perm.burn_allowance_perm = perm
+                .burn_allowance_perm
+                .checked_sub(amount)
+                .expect("something strange happened");
+            update_permission(storage, owner, &token_info.token_id, &info.sender, &perm)?;
+            return Ok(());
+        }
+    }
+
+    Err(StdError::generic_err(format!(
+        "you do not have permission to burn {} tokens from address {}",
+        amount, owner
+    )))
 }
 
-/// checks if `token_id` is available (ie: not yet created), then creates new `token_id` and initial balances
+/// checks if `token_id` is available (ie: not yet created), then creates new `token_id` and initial balances.
+/// If `notify_receivers` == true, pushes a `Snip1155Receive` callback to `messages` for each registered recipient.
+/// Pushes a mint notification to `notifications` for each recipient other than the curator
+#[allow(clippy::too_many_arguments)]
 fn exec_curate_token_id(
     deps: &mut DepsMut,
     env: &Env,
@@ -920,6 +3609,9 @@ fn exec_curate_token_id(
     config: &mut ContractConfig,
     initial_token: CurateTokenId,
     memo: Option<String>,
+    notify_receivers: bool,
+    messages: &mut Vec<CosmosMsg>,
+    notifications: &mut Vec<Notification>,
 ) -> StdResult<()> {
     // check: token_id has not been created yet
     if tkn_info_r(deps.storage)
@@ -931,6 +3623,24 @@ fn exec_curate_token_id(
         ));
     }
 
+    // check: token_id is in a namespace the sender may curate in, and is not reserved for a token_id
+    // derived from another
+    verify_namespace(config, info, &initial_token.token_info.token_id)?;
+    verify_derived_token_id(deps.storage, info, &initial_token.token_info.token_id)?;
+
+    // check: curator has not reached its quota
+    let curated = curator_token_id_count(deps.storage, &info.sender)?;
+    if let Some(quota) =
+        curator_quotas_r(deps.storage).may_load(to_binary(&info.sender)?.as_slice())?
+    {
+        if curated >= quota {
+            return Err(StdError::generic_err(format!(
+                "curator {} has reached its quota of {} token_ids",
+                info.sender, quota
+            )));
+        }
+    }
+
     // check: token_id is an NFT => cannot create more than one
     if initial_token.token_info.token_config.flatten().is_nft {
         if initial_token.balances.len() > 1 {
@@ -967,6 +3677,11 @@ fn exec_curate_token_id(
         initial_token.token_info.token_id.as_bytes(),
         &initial_token.token_info.to_store(&info.sender),
     )?;
+    append_curator_token_id(
+        deps.storage,
+        &info.sender,
+        &initial_token.token_info.token_id,
+    )?;
 
     // set initial balances and store mint history
     for balance in initial_token.balances {
@@ -998,6 +3713,29 @@ fn exec_curate_token_id(
             balance.amount,
             memo.clone(),
         )?;
+
+        if balance.address != info.sender {
+            notifications.push(Notification::new(
+                balance.address.clone(),
+                NotificationData::Mint {
+                    token_id: initial_token.token_info.token_id.clone(),
+                    amount: balance.amount,
+                    minter: info.sender.clone(),
+                },
+            ));
+        }
+
+        if notify_receivers {
+            try_add_mint_receiver_callback(
+                deps.storage,
+                messages,
+                &info.sender,
+                &initial_token.token_info.token_id,
+                balance.address,
+                balance.amount,
+                memo.clone(),
+            )?;
+        }
     }
 
     // push token_id to token_id_list
@@ -1006,6 +3744,71 @@ fn exec_curate_token_id(
     Ok(())
 }
 
+/// Implements a single mint of a `token_id` by `minter`. Saves mint history, notifies the
+/// recipient, and creates a callback message if `notify_receiver` == true
+#[allow(clippy::too_many_arguments)]
+fn impl_mint(
+    deps: &mut DepsMut,
+    env: &Env,
+    config: &mut ContractConfig,
+    messages: &mut Vec<CosmosMsg>,
+    notifications: &mut Vec<Notification>,
+    minter: &Addr,
+    token_info: &StoredTokenInfo,
+    recipient: Addr,
+    amount: Uint256,
+    memo: Option<String>,
+    notify_receiver: bool,
+) -> StdResult<()> {
+    exec_change_balance(
+        deps.storage,
+        &token_info.token_id,
+        None,
+        Some(&recipient),
+        &amount,
+        token_info,
+        None,
+        None,
+    )?;
+
+    // store mint_token
+    store_mint(
+        deps.storage,
+        config,
+        &env.block,
+        &token_info.token_id,
+        deps.api.addr_canonicalize(minter.as_str())?,
+        deps.api.addr_canonicalize(recipient.as_str())?,
+        amount,
+        memo.clone(),
+    )?;
+
+    if &recipient != minter {
+        notifications.push(Notification::new(
+            recipient.clone(),
+            NotificationData::Mint {
+                token_id: token_info.token_id.clone(),
+                amount,
+                minter: minter.clone(),
+            },
+        ));
+    }
+
+    if notify_receiver {
+        try_add_mint_receiver_callback(
+            deps.storage,
+            messages,
+            minter,
+            &token_info.token_id,
+            recipient,
+            amount,
+            memo,
+        )?;
+    }
+
+    Ok(())
+}
+
 /// Implements a single `Send` function. Transfers Uint256 amount of a single `token_id`,
 /// saves transfer history, may register-receive, and creates callback message.
 fn impl_send(
@@ -1013,6 +3816,8 @@ fn impl_send(
     env: &Env,
     info: &MessageInfo,
     messages: &mut Vec<CosmosMsg>,
+    notifications: &mut Vec<Notification>,
+    prng: &mut ContractPrng,
     action: SendAction,
 ) -> StdResult<()> {
     // action variables from SendAction
@@ -1023,6 +3828,22 @@ fn impl_send(
     let recipient_code_hash = action.recipient_code_hash;
     let msg = action.msg;
     let memo = action.memo;
+    let decoys = action.decoys;
+    let account_random_pos = get_account_random_pos(prng, decoys.as_ref());
+
+    // create cosmos message. Done before the transfer, as the recipient may reject this token_id
+    try_add_receiver_api_callback(
+        deps.storage,
+        messages,
+        recipient.clone(),
+        recipient_code_hash,
+        msg,
+        info.sender.clone(),
+        token_id.clone(),
+        from.to_owned(),
+        amount,
+        memo.clone(),
+    )?;
 
     // implements transfer of tokens
     impl_transfer(
@@ -1033,30 +3854,24 @@ fn impl_send(
         &from,
         &recipient,
         amount,
-        memo.clone(),
-    )?;
-
-    // create cosmos message
-    try_add_receiver_api_callback(
-        deps.storage,
-        messages,
-        recipient,
-        recipient_code_hash,
-        msg,
-        info.sender.clone(),
-        token_id,
-        from.to_owned(),
-        amount,
         memo,
+        decoys,
+        account_random_pos,
+        notifications,
     )?;
 
     Ok(())
 }
 
 /// Implements a single `Transfer` function. Transfers a Uint256 amount of a
-/// single `token_id` and saves the transfer history. Used by `Transfer` and
-/// `Send` (via `impl_send`) messages
-#[allow(clippy::too_many_arguments)]
+/// single `token_id`, saves the transfer history and pushes a notification for
+/// the recipient, unless it is the sender or the contract itself. Used by
+/// `Transfer` and `Send` (via `impl_send`) messages
+#[allow(
+    clippy::too_many_arguments,
+    clippy::single_match,
+    clippy::useless_conversion
+)]
 fn impl_transfer(
     deps: &mut DepsMut,
     env: &Env,
@@ -1066,7 +3881,18 @@ fn impl_transfer(
     recipient: &Addr,
     amount: Uint256,
     memo: Option<String>,
+    decoys: Option<Vec<Addr>>,
+    account_random_pos: Option<usize>,
+    notifications: &mut Vec<Notification>,
 ) -> StdResult<()> {
+    let decoys = decoys
+        .map(|d| {
+            d.iter()
+                .map(|decoy| deps.api.addr_validate(decoy.as_str()))
+                .collect::<StdResult<Vec<Addr>>>()
+        })
+        .transpose()?;
+
     // check if `from` == message sender || has enough allowance to send tokens
     // perform allowance check, and may reduce allowance
     let mut throw_err = false;
@@ -1122,6 +3948,17 @@ fn impl_transfer(
         false => (),
     }
 
+    let token_info = token_info_op.unwrap();
+    verify_not_frozen(deps.storage, &token_info, from)?;
+    if let Some(locked_until) = token_info.token_config.flatten().transfers_locked_until {
+        if !locked_until.is_expired(&env.block) {
+            return Err(StdError::generic_err(format!(
+                "transfers of token_id {} are locked until {}",
+                token_id, locked_until
+            )));
+        }
+    }
+
     // transfer tokens
     exec_change_balance(
         deps.storage,
@@ -1129,7 +3966,9 @@ fn impl_transfer(
         Some(from),
         Some(recipient),
         &amount,
-        &token_info_op.unwrap(),
+        &token_info,
+        decoys.as_ref(),
+        account_random_pos,
     )?;
 
     // store transaction
@@ -1147,6 +3986,263 @@ fn impl_transfer(
     )?;
     contr_conf_w(deps.storage).save(&config)?;
 
+    if recipient != &info.sender && recipient != &env.contract.address {
+        notifications.push(Notification::new(
+            recipient.clone(),
+            NotificationData::Transfer {
+                token_id: token_id.to_string(),
+                amount,
+                sender: from.clone(),
+            },
+        ));
+    }
+
+    Ok(())
+}
+
+/// adds SNIP-20 payment tokens to the contract config, and returns the messages registering this
+/// contract's code hash with each of them
+fn exec_add_payment_tokens(
+    env: &Env,
+    config: &mut ContractConfig,
+    payment_tokens: Vec<PaymentToken>,
+) -> StdResult<Vec<CosmosMsg>> {
+    let mut messages = vec![];
+    for payment_token in payment_tokens {
+        if config
+            .payment_tokens
+            .iter()
+            .any(|t| t.address == payment_token.address)
+        {
+            continue;
+        }
+        messages.push(
+            Snip20HandleMsg::RegisterReceive {
+                code_hash: env.contract.code_hash.clone(),
+                padding: None,
+            }
+            .into_cosmos_msg(
+                payment_token.code_hash.clone(),
+                payment_token.address.clone(),
+            )?,
+        );
+        config.payment_tokens.push(payment_token);
+    }
+
+    Ok(messages)
+}
+
+fn load_sale(storage: &dyn Storage, token_id: &str) -> StdResult<Sale> {
+    sales_r(storage)
+        .may_load(token_id.as_bytes())?
+        .ok_or_else(|| StdError::generic_err(format!("token_id {} does not have a sale", token_id)))
+}
+
+/// checks that a sale is open and that `quantity` is within its limits, then mints `quantity`
+/// tokens to `buyer`, pushing its mint notification and receiver callback. Does not save the
+/// updated `sale`
+fn exec_sale_mint(
+    deps: &mut DepsMut,
+    env: &Env,
+    messages: &mut Vec<CosmosMsg>,
+    notifications: &mut Vec<Notification>,
+    sale: &mut Sale,
+    buyer: &Addr,
+    quantity: Uint256,
+) -> StdResult<()> {
+    if quantity.is_zero() {
+        return Err(StdError::generic_err(
+            "purchase quantity must be greater than zero",
+        ));
+    }
+    if let Some(start) = sale.start {
+        if !start.is_expired(&env.block) {
+            return Err(StdError::generic_err(format!(
+                "sale of token_id {} has not started",
+                sale.token_id
+            )));
+        }
+    }
+    if let Some(end) = sale.end {
+        if end.is_expired(&env.block) {
+            return Err(StdError::generic_err(format!(
+                "sale of token_id {} has ended",
+                sale.token_id
+            )));
+        }
+    }
+
+    sale.sold = sale.sold.checked_add(quantity)?;
+    if let Some(max_sold) = sale.max_sold {
+        if sale.sold > max_sold {
+            return Err(StdError::generic_err(format!(
+                "only {} tokens of token_id {} remain for sale",
+                max_sold.saturating_sub(sale.sold - quantity),
+                sale.token_id
+            )));
+        }
+    }
+    let buyer_key = to_binary(buyer)?;
+    let purchased = sale_purchases_r(deps.storage, &sale.token_id)
+        .may_load(buyer_key.as_slice())?
+        .unwrap_or_default()
+        .checked_add(quantity)?;
+    if let Some(limit) = sale.per_address_limit {
+        if purchased > limit {
+            return Err(StdError::generic_err(format!(
+                "purchase exceeds the limit of {} tokens of token_id {} per address",
+                limit, sale.token_id
+            )));
+        }
+    }
+
+    let token_info = tkn_info_r(deps.storage).load(sale.token_id.as_bytes())?;
+    if !token_info.token_config.flatten().enable_mint {
+        return Err(StdError::generic_err(
+            "minting is not enabled for this token_id",
+        ));
+    }
+    let mut config = contr_conf_r(deps.storage).load()?;
+    impl_mint(
+        deps,
+        env,
+        &mut config,
+        messages,
+        notifications,
+        &sale.curator,
+        &token_info,
+        buyer.clone(),
+        quantity,
+        Some("purchase".to_string()),
+        true,
+    )?;
+    contr_conf_w(deps.storage).save(&config)?;
+    sale_purchases_w(deps.storage, &sale.token_id).save(buyer_key.as_slice(), &purchased)?;
+
+    Ok(())
+}
+
+/// verifies a Merkle proof, hashing each pair of nodes in ascending byte order
+fn verify_merkle_proof(leaf: [u8; 32], proof: &[Binary], root: &[u8]) -> bool {
+    let mut hash = leaf;
+    for node in proof {
+        let mut pair = Vec::with_capacity(hash.len() + node.len());
+        if hash.as_slice() <= node.as_slice() {
+            pair.extend_from_slice(&hash);
+            pair.extend_from_slice(node.as_slice());
+        } else {
+            pair.extend_from_slice(node.as_slice());
+            pair.extend_from_slice(&hash);
+        }
+        hash = sha_256(&pair);
+    }
+    hash.as_slice() == root
+}
+
+fn load_swap_offer(storage: &dyn Storage, offer_id: u64) -> StdResult<SwapOffer> {
+    swap_offers_r(storage)
+        .may_load(&offer_id.to_be_bytes())?
+        .ok_or_else(|| StdError::generic_err(format!("swap offer {} does not exist", offer_id)))
+}
+
+/// transfers the tokens held for a swap offer from the contract to `recipient`, and removes the offer
+fn exec_release_swap_offer(
+    deps: &mut DepsMut,
+    env: &Env,
+    offer: &SwapOffer,
+    recipient: &Addr,
+    notifications: &mut Vec<Notification>,
+) -> StdResult<()> {
+    let contract_info = MessageInfo {
+        sender: env.contract.address.clone(),
+        funds: vec![],
+    };
+    impl_transfer(
+        deps,
+        env,
+        &contract_info,
+        &offer.offer_token_id,
+        &env.contract.address,
+        recipient,
+        offer.offer_amount,
+        Some(format!("swap offer {}", offer.offer_id)),
+        None,
+        None,
+        notifications,
+    )?;
+    swap_offers_w(deps.storage).remove(&offer.offer_id.to_be_bytes());
+
+    Ok(())
+}
+
+fn load_escrow(storage: &dyn Storage, escrow_id: u64) -> StdResult<Escrow> {
+    escrows_r(storage)
+        .may_load(&escrow_id.to_be_bytes())?
+        .ok_or_else(|| StdError::generic_err(format!("escrow {} does not exist", escrow_id)))
+}
+
+/// transfers escrowed tokens from the contract to the beneficiary, or back to the depositor if
+/// `clawback` == true, records the tx and removes the escrow
+fn exec_release_escrow(
+    deps: &mut DepsMut,
+    env: &Env,
+    escrow: Escrow,
+    clawback: bool,
+    notifications: &mut Vec<Notification>,
+) -> StdResult<()> {
+    let recipient = if clawback {
+        &escrow.depositor
+    } else {
+        &escrow.beneficiary
+    };
+    let contract_info = MessageInfo {
+        sender: env.contract.address.clone(),
+        funds: vec![],
+    };
+    impl_transfer(
+        deps,
+        env,
+        &contract_info,
+        &escrow.token_id,
+        &env.contract.address,
+        recipient,
+        escrow.amount,
+        Some(format!("escrow {}", escrow.escrow_id)),
+        None,
+        None,
+        notifications,
+    )?;
+
+    let depositor = deps.api.addr_canonicalize(escrow.depositor.as_str())?;
+    let beneficiary = deps.api.addr_canonicalize(escrow.beneficiary.as_str())?;
+    let action = if clawback {
+        StoredTxAction::EscrowClawback {
+            escrow_id: escrow.escrow_id,
+            depositor,
+            beneficiary,
+            amount: escrow.amount,
+        }
+    } else {
+        StoredTxAction::EscrowClaim {
+            escrow_id: escrow.escrow_id,
+            depositor,
+            beneficiary,
+            amount: escrow.amount,
+        }
+    };
+    let mut config = contr_conf_r(deps.storage).load()?;
+    store_escrow(
+        deps.storage,
+        &mut config,
+        &env.block,
+        &escrow.token_id,
+        action,
+        None,
+    )?;
+    contr_conf_w(deps.storage).save(&config)?;
+
+    escrows_w(deps.storage).remove(&escrow.escrow_id.to_be_bytes());
+
     Ok(())
 }
 
@@ -1159,6 +4255,10 @@ fn impl_transfer(
 /// * If `add_to` == None: burn tokens.
 /// * If is_nft == true, then `remove_from` MUST be Some(_).
 /// * If is_nft == true, stores new owner of NFT
+/// * If `decoys` == Some(_), the balance of each decoy is read and written back unchanged around
+///   both the `remove_from` and the `add_to` balance writes, with the real balance written at
+///   `account_random_pos`
+#[allow(clippy::too_many_arguments, clippy::useless_conversion)]
 fn exec_change_balance(
     storage: &mut dyn Storage,
     token_id: &str,
@@ -1166,6 +4266,8 @@ fn exec_change_balance(
     add_to: Option<&Addr>,
     amount: &Uint256,
     token_info: &StoredTokenInfo,
+    decoys: Option<&Vec<Addr>>,
+    account_random_pos: Option<usize>,
 ) -> StdResult<()> {
     // check whether token_id is an NFT => cannot mint. This should not be reachable in standard implementation,
     // as the calling function would have checked that enable_mint == false, which needs to be true for NFTs.
@@ -1181,8 +4283,19 @@ fn exec_change_balance(
         return Err(StdError::generic_err("NFT amount must == 1"));
     }
 
+    exec_transfer_policy(storage, token_id, remove_from, add_to, amount)?;
+
+    // read and write decoy balances before and after each real balance, so that the accounts
+    // cannot be identified from storage access patterns
+    let decoys: &[Addr] = decoys.map(|d| d.as_slice()).unwrap_or_default();
+    let real_pos = account_random_pos.unwrap_or(0).min(decoys.len());
+
     // remove balance
     if let Some(from) = remove_from {
+        for decoy in &decoys[..real_pos] {
+            exec_touch_balance(storage, token_id, decoy)?;
+        }
+
         let from_existing_bal = balances_r(storage, token_id).load(to_binary(&from)?.as_slice())?;
         let from_new_amount_op = from_existing_bal.checked_sub(*amount);
         if from_new_amount_op.is_err() {
@@ -1193,12 +4306,25 @@ fn exec_change_balance(
             &Uint256::from(from_new_amount_op.unwrap()),
         )?;
 
+        for decoy in &decoys[real_pos..] {
+            exec_touch_balance(storage, token_id, decoy)?;
+        }
+
         // NOTE: if nft, the ownership history remains in storage. Any existing viewing permissions of last owner
         // will remain too
+
+        // the NFT's user is cleared when it changes hands
+        if token_info.token_config.flatten().is_nft {
+            nft_users_w(storage).remove(token_id.as_bytes());
+        }
     }
 
     // add balance
     if let Some(to) = add_to {
+        for decoy in &decoys[..real_pos] {
+            exec_touch_balance(storage, token_id, decoy)?;
+        }
+
         let to_existing_bal_op =
             balances_r(storage, token_id).may_load(to_binary(&to)?.as_slice())?;
         let to_existing_bal = match to_existing_bal_op {
@@ -1219,6 +4345,10 @@ fn exec_change_balance(
             &Uint256::from(to_new_amount_op.unwrap()),
         )?;
 
+        for decoy in &decoys[real_pos..] {
+            exec_touch_balance(storage, token_id, decoy)?;
+        }
+
         // if is_nft == true, store new owner of NFT
         if token_info.token_config.flatten().is_nft {
             append_new_owner(storage, &token_info.token_id, to)?;
@@ -1256,6 +4386,43 @@ fn exec_change_balance(
     Ok(())
 }
 
+/// reads the balance of a decoy address and writes it back unchanged
+fn exec_touch_balance(storage: &mut dyn Storage, token_id: &str, decoy: &Addr) -> StdResult<()> {
+    let key = to_binary(decoy)?;
+    let balance = balances_r(storage, token_id)
+        .may_load(key.as_slice())?
+        .unwrap_or_default();
+    balances_w(storage, token_id).save(key.as_slice(), &balance)
+}
+
+/// creates a prng used to position recipients among decoys, from the contract's prng seed,
+/// the block's random value (if any) and optional user entropy
+fn new_decoy_prng(
+    storage: &dyn Storage,
+    env: &Env,
+    entropy: Option<&Binary>,
+) -> StdResult<ContractPrng> {
+    let prng_seed = contr_conf_r(storage).load()?.prng_seed;
+    let mut rng_entropy = entropy.map(|e| e.to_vec()).unwrap_or_default();
+    if let Some(random) = env.block.random.as_ref() {
+        rng_entropy.extend_from_slice(random.as_slice());
+    }
+    rng_entropy.extend_from_slice(&env.block.height.to_be_bytes());
+    Ok(ContractPrng::new(&prng_seed, &rng_entropy))
+}
+
+/// returns a random position for the real recipient among `decoys`, or None if there are no decoys
+fn get_account_random_pos(prng: &mut ContractPrng, decoys: Option<&Vec<Addr>>) -> Option<usize> {
+    decoys.map(|d| {
+        let mut rand_bytes = [0u8; 8];
+        rand_bytes.copy_from_slice(&prng.rand_bytes()[..8]);
+        (u64::from_be_bytes(rand_bytes) % (d.len() as u64 + 1)) as usize
+    })
+}
+
+/// adds a `Snip1155Receive` callback to `messages` if `recipient_code_hash` is provided or the
+/// recipient has registered its code hash. If the recipient has registered token_id filters, no
+/// callback is sent for unlisted token_ids, or an error is returned if the recipient rejects them
 #[allow(clippy::too_many_arguments)]
 fn try_add_receiver_api_callback(
     storage: &dyn Storage,
@@ -1269,22 +4436,54 @@ fn try_add_receiver_api_callback(
     amount: Uint256,
     memo: Option<String>,
 ) -> StdResult<()> {
-    if let Some(receiver_hash) = recipient_code_hash {
-        let receiver_msg = Snip1155ReceiveMsg::new(sender, token_id, from, amount, memo, msg);
-        let callback_msg = receiver_msg.into_cosmos_msg(receiver_hash, recipient)?;
+    let registration = may_load_receiver(storage, &recipient)?;
 
-        messages.push(callback_msg);
-        return Ok(());
+    // apply token_id filters of the recipient, if any
+    if let Some(reg) = registration.as_ref() {
+        if !reg.is_listed(&token_id) {
+            if reg.reject_unlisted_token_ids {
+                return Err(StdError::generic_err(format!(
+                    "recipient {} does not accept token_id {}",
+                    recipient, token_id
+                )));
+            }
+            return Ok(());
+        }
     }
 
-    let receiver_hash = get_receiver_hash(storage, &recipient);
-    if let Some(receiver_hash) = receiver_hash {
-        let receiver_hash = receiver_hash?;
-        let receiver_msg = Snip1155ReceiveMsg::new(sender, token_id, from, amount, memo, msg);
-        let callback_msg = receiver_msg.into_cosmos_msg(receiver_hash, recipient)?;
-
-        messages.push(callback_msg);
-    }
+    let receiver_hash = match (recipient_code_hash, registration) {
+        (Some(hash), _) => hash,
+        (None, Some(reg)) => reg.code_hash,
+        (None, None) => return Ok(()),
+    };
+    let receiver_msg = Snip1155ReceiveMsg::new(sender, token_id, from, amount, memo, msg);
+    let callback_msg = receiver_msg.into_cosmos_msg(receiver_hash, recipient)?;
+    messages.push(callback_msg);
 
     Ok(())
 }
+
+/// creates a `Snip1155Receive` callback for minted tokens, only if the recipient has registered
+/// its code hash using `RegisterReceive`. As there is no previous owner, `from` is the minter
+fn try_add_mint_receiver_callback(
+    storage: &dyn Storage,
+    messages: &mut Vec<CosmosMsg>,
+    minter: &Addr,
+    token_id: &str,
+    recipient: Addr,
+    amount: Uint256,
+    memo: Option<String>,
+) -> StdResult<()> {
+    try_add_receiver_api_callback(
+        storage,
+        messages,
+        recipient,
+        None,
+        None,
+        minter.clone(),
+        token_id.to_string(),
+        minter.clone(),
+        amount,
+        memo,
+    )
+}
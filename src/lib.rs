@@ -1,7 +1,9 @@
 pub mod handles;
 pub mod queries;
 pub mod msg;
+pub mod notifications;
 pub mod state;
 pub mod receiver;
+pub mod snip20;
 #[cfg(test)]
 pub mod unittest;
\ No newline at end of file
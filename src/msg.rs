@@ -1,4 +1,4 @@
-use cosmwasm_std::{Addr, Binary, StdResult, Uint256};
+use cosmwasm_std::{Addr, Binary, Coin, StdResult, Uint128, Uint256, Uint64};
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
@@ -6,7 +6,11 @@ use crate::state::{
     expiration::Expiration,
     metadata::Metadata,
     permissions::{Permission, PermissionKey},
-    state_structs::{CurateTokenId, OwnerBalance, StoredTokenInfo, TokenAmount},
+    state_structs::{
+        AdminAction, AdminProposal, ContractStatus, CurateTokenId, EditionSeries, Namespace,
+        OwnerBalance, PaymentToken, QueuedAdminAction, Role, RoleHolder, Sale, Snip20Price,
+        StoredTokenInfo, SwapOffer, TokenAmount, TokenInfoMsg, TransferPolicy, VestingSchedule,
+    },
     txhistory::Tx,
 };
 
@@ -29,6 +33,18 @@ pub struct InstantiateMsg {
     pub initial_tokens: Vec<CurateTokenId>,
     /// for `create_viewing_key` function
     pub entropy: String,
+    /// SNIP-20 contracts that sales can accept as payment. The contract registers its code hash
+    /// with each of them
+    #[serde(default)]
+    pub payment_tokens: Vec<PaymentToken>,
+    /// receives the proceeds of sales paid with SNIP-20 tokens
+    #[serde(default)]
+    pub treasury: Option<Addr>,
+    /// if set, `AddCurators`, `RemoveCurators`, `ChangeAdmin`, `ProposeAdmin`, `GrantRole`,
+    /// `RevokeRole` and `SetContractStatus` must be queued with `QueueAdminAction`, and can only
+    /// be executed this many seconds later
+    #[serde(default)]
+    pub admin_action_delay: Option<u64>,
 }
 
 /////////////////////////////////////////////////////////////////////////////////
@@ -46,6 +62,9 @@ pub enum ExecuteMsg {
     CurateTokenIds {
         initial_tokens: Vec<CurateTokenId>,
         memo: Option<String>,
+        /// if `true`, sends a `Snip1155Receive` callback to each recipient of the initial balances
+        /// that has registered its code hash using `RegisterReceive`. Defaults to `false`
+        notify_receivers: Option<bool>,
         padding: Option<String>,
     },
     /// mints additional tokens of existing fungible token_ids, if configuration allows this, ie
@@ -54,18 +73,25 @@ pub enum ExecuteMsg {
     MintTokens {
         mint_tokens: Vec<TokenAmount>,
         memo: Option<String>,
+        /// if `true`, sends a `Snip1155Receive` callback to each recipient of the minted tokens
+        /// that has registered its code hash using `RegisterReceive`. Defaults to `false`
+        notify_receivers: Option<bool>,
         padding: Option<String>,
     },
     /// burns existing tokens, if configuration allows this, ie
     /// `enable_burn == true`.
-    /// Only owners can burn their own tokens in the base specifications. Flexibility is built
-    /// into the contract functions to allow other addresses to burn tokens, allowed in additional specifications.
+    /// Owners can burn their own tokens. Other addresses can burn an owner's tokens if the owner has
+    /// given them a burn allowance using `GivePermission`, and minters can burn an owner's tokens if the
+    /// token_id configuration has `minter_may_burn == true`.
     BurnTokens {
         burn_tokens: Vec<TokenAmount>,
         memo: Option<String>,
         padding: Option<String>,
     },
     /// allows owner or minter to change metadata if allowed by token_id configuration.
+    /// Holders of the `MetadataManager` role can change the metadata of any token_id whose
+    /// configuration has `minter_may_update_metadata == true`.
+    /// Metadata cannot be changed after `FreezeMetadata`.
     ChangeMetadata {
         token_id: String,
         /// does not attempt to change if left blank. Can effectively remove metadata by setting
@@ -89,11 +115,18 @@ pub enum ExecuteMsg {
         recipient: Addr,
         amount: Uint256,
         memo: Option<String>,
+        /// optional addresses whose balances are read and re-written along with the recipient's, so
+        /// the recipient cannot be identified from storage access patterns
+        decoys: Option<Vec<Addr>>,
+        /// optional entropy used to randomize the position of the recipient among the decoys
+        entropy: Option<Binary>,
         padding: Option<String>,
     },
     /// performs `transfer`s of multiple token_ids in a single transaction
     BatchTransfer {
         actions: Vec<TransferAction>,
+        /// optional entropy used to randomize the position of recipients among their decoys
+        entropy: Option<Binary>,
         padding: Option<String>,
     },
     /// similar to transfer, but also sends a cosmos message. The recipient needs to be a contract that
@@ -107,11 +140,278 @@ pub enum ExecuteMsg {
         amount: Uint256,
         msg: Option<Binary>,
         memo: Option<String>,
+        /// optional addresses whose balances are read and re-written along with the recipient's, so
+        /// the recipient cannot be identified from storage access patterns
+        decoys: Option<Vec<Addr>>,
+        /// optional entropy used to randomize the position of the recipient among the decoys
+        entropy: Option<Binary>,
         padding: Option<String>,
     },
     /// performs `send` of multiple token_ids in a single transaction
     BatchSend {
         actions: Vec<SendAction>,
+        /// optional entropy used to randomize the position of recipients among their decoys
+        entropy: Option<Binary>,
+        padding: Option<String>,
+    },
+    /// performs the transfers signed by a token owner in a permit. Any address can submit the permit
+    /// (and pay the gas fees), and tokens are transferred as if the owner had sent the transfers.
+    /// Each transfer's `nonce` can only be used once per owner, and an owner can cancel an unused
+    /// permit with `RevokePermit`.
+    TransferWithPermit {
+        permit: Permit<PermitTransfer>,
+        padding: Option<String>,
+    },
+    /// offers to swap `offer_amount` of the sender's `offer_token_id` tokens for `ask_amount` of
+    /// `ask_token_id` tokens. The offered tokens are transferred to the contract until the offer
+    /// is accepted or cancelled. Returns the `offer_id` of the new offer
+    CreateSwapOffer {
+        offer_token_id: String,
+        offer_amount: Uint256,
+        ask_token_id: String,
+        ask_amount: Uint256,
+        /// if `Some`, only this address can accept the offer
+        counterparty: Option<Addr>,
+        /// the offer cannot be accepted after this expiration. Defaults to `Never`
+        expiration: Option<Expiration>,
+        padding: Option<String>,
+    },
+    /// accepts a swap offer: the asked tokens are transferred from the sender to the maker, and
+    /// the offered tokens are transferred to the sender
+    AcceptSwapOffer {
+        offer_id: u64,
+        padding: Option<String>,
+    },
+    /// cancels a swap offer and returns the offered tokens to the maker. Only the maker can
+    /// cancel an offer
+    CancelSwapOffer {
+        offer_id: u64,
+        padding: Option<String>,
+    },
+    /// locks the sender's tokens in the contract for a beneficiary, who can claim them after
+    /// `release`. Returns the `escrow_id` of the new escrow
+    CreateEscrow {
+        token_id: String,
+        amount: Uint256,
+        beneficiary: Addr,
+        /// must not have passed yet. Escrows are subject to the token_id's
+        /// `transfers_locked_until`, both when created and when released
+        release: Expiration,
+        /// if `true`, the sender can take back the tokens before `release` using
+        /// `ClawbackEscrow`. Defaults to `false`
+        clawback: Option<bool>,
+        memo: Option<String>,
+        padding: Option<String>,
+    },
+    /// transfers escrowed tokens to the beneficiary. Only the beneficiary can claim, and only after
+    /// the escrow's `release`
+    ClaimEscrow {
+        escrow_id: u64,
+        padding: Option<String>,
+    },
+    /// returns escrowed tokens to the depositor. Only the depositor can claw back, only before the
+    /// escrow's `release`, and only if the escrow was created with `clawback == true`
+    ClawbackEscrow {
+        escrow_id: u64,
+        padding: Option<String>,
+    },
+    /// creates a linear vesting schedule of `total` tokens for a beneficiary, funded from the
+    /// sender's balance. Only curators can access this function. A beneficiary can only have one
+    /// vesting schedule per token_id at a time. Times are in seconds since 01/01/1970
+    CreateVestingSchedule {
+        token_id: String,
+        beneficiary: Addr,
+        total: Uint256,
+        start: u64,
+        cliff: u64,
+        end: u64,
+        padding: Option<String>,
+    },
+    /// transfers all vested and unclaimed tokens of a token_id to the sender
+    ClaimVested {
+        token_id: String,
+        padding: Option<String>,
+    },
+    /// registers the Merkle root of an airdrop round for a token_id. Only the curator of the
+    /// token_id can access this function, and minting must be enabled for the token_id.
+    ///
+    /// Each leaf is SHA-256(`address` || `amount` as a 32-byte big-endian integer), and each
+    /// parent is SHA-256 of its two children concatenated in ascending byte order
+    RegisterAirdrop {
+        token_id: String,
+        round: u64,
+        merkle_root: Binary,
+        padding: Option<String>,
+    },
+    /// mints `amount` tokens of a token_id to the sender, if `proof` shows that the sender and
+    /// `amount` are a leaf of the airdrop round's Merkle tree. Each address can only claim once
+    /// per round. The tokens are minted by the curator, and a `Snip1155Receive` callback is sent
+    /// if the sender has registered its code hash
+    ClaimAirdrop {
+        token_id: String,
+        round: u64,
+        amount: Uint256,
+        proof: Vec<Binary>,
+        padding: Option<String>,
+    },
+    /// creates or updates the public sale of a token_id. Only the curator of the token_id can
+    /// access this function, and minting must be enabled for the token_id. Updating a sale keeps
+    /// its sold amount and unwithdrawn proceeds, but they must be in the same denom
+    SetSale {
+        token_id: String,
+        /// price of one token
+        price: Coin,
+        per_address_limit: Option<Uint256>,
+        start: Option<Expiration>,
+        end: Option<Expiration>,
+        max_sold: Option<Uint256>,
+        /// prices in SNIP-20 tokens, which must be in the contract's `payment_tokens`
+        snip20_prices: Option<Vec<Snip20Price>>,
+        padding: Option<String>,
+    },
+    /// mints `quantity` tokens to the sender, paid with native coins sent with this message.
+    /// Any amount sent in excess of the price is refunded
+    Purchase {
+        token_id: String,
+        quantity: Uint256,
+        padding: Option<String>,
+    },
+    /// sends the unwithdrawn proceeds of a sale to its curator
+    WithdrawSaleProceeds {
+        token_id: String,
+        padding: Option<String>,
+    },
+    /// SNIP-20 `Receive` callback. `msg` must be a [Snip20ReceiveMsg](crate::msg::Snip20ReceiveMsg)
+    Receive {
+        sender: Addr,
+        from: Addr,
+        amount: Uint128,
+        memo: Option<String>,
+        msg: Option<Binary>,
+    },
+    /// sets the address that can use an NFT until `expires`, without transferring ownership. The
+    /// user can view the NFT's private metadata. Only the owner of the NFT can access this
+    /// function. `user` == `None` removes the current user. `expires` defaults to `Never`.
+    /// The user is removed when the NFT is transferred
+    SetUser {
+        token_id: String,
+        user: Option<Addr>,
+        expires: Option<Expiration>,
+        padding: Option<String>,
+    },
+    /// freezes an address's balance of a `freezable` token_id, so it cannot be transferred, sent
+    /// or burnt. The contract's own balances cannot be frozen. Only minters and the curator of the
+    /// token_id can access this function
+    Freeze {
+        token_id: String,
+        address: Addr,
+        padding: Option<String>,
+    },
+    /// unfreezes an address's balance of a token_id. Only minters and the curator of the
+    /// token_id can access this function
+    Unfreeze {
+        token_id: String,
+        address: Addr,
+        padding: Option<String>,
+    },
+    /// permanently prevents the metadata of a token_id from being changed. Only minters and the
+    /// curator of the token_id can access this function
+    FreezeMetadata {
+        token_id: String,
+        padding: Option<String>,
+    },
+    /// sets which addresses a token_id can move between. Only the curator of the token_id can
+    /// access this function
+    SetTransferPolicy {
+        token_id: String,
+        policy: TransferPolicy,
+        padding: Option<String>,
+    },
+    /// adds addresses to a token_id's allowlist, which applies when its transfer policy is
+    /// `Allowlist`. Only the curator of the token_id can access this function
+    AddToAllowlist {
+        token_id: String,
+        addresses: Vec<Addr>,
+        padding: Option<String>,
+    },
+    /// removes addresses from a token_id's allowlist. Only the curator of the token_id can access
+    /// this function
+    RemoveFromAllowlist {
+        token_id: String,
+        addresses: Vec<Addr>,
+        padding: Option<String>,
+    },
+    /// approves up to `amount` tokens of a token_id to move from `from` to `recipient`, which
+    /// applies when its transfer policy is `CuratorApproval`. Replaces any remaining approved
+    /// amount. Only the curator of the token_id can access this function
+    ApproveTransfer {
+        token_id: String,
+        from: Addr,
+        recipient: Addr,
+        amount: Uint256,
+        padding: Option<String>,
+    },
+    /// creates a series of up to `max_editions` numbered NFT editions. `series.token_id` is the
+    /// parent token_id, and its name, symbol, NFT `token_config` and metadata are inherited by
+    /// each edition. Only curators can access this function
+    CreateEditionSeries {
+        series: TokenInfoMsg,
+        max_editions: u64,
+        padding: Option<String>,
+    },
+    /// curates the next edition of a series as NFT token_id `{parent_token_id}/{serial}`, owned
+    /// by `recipient`, skipping serials taken by token_ids curated before the series. The edition
+    /// inherits the series' metadata, unless overridden. Only the curator of the series can access
+    /// this function, and only while it is still a curator
+    MintEdition {
+        parent_token_id: String,
+        recipient: Addr,
+        public_metadata: Option<Metadata>,
+        private_metadata: Option<Metadata>,
+        memo: Option<String>,
+        padding: Option<String>,
+    },
+    /// locks an NFT owned by the sender in the contract, and curates a new fungible token_id
+    /// with the same name, symbol and public metadata, whose `shares` tokens are minted to the
+    /// sender
+    Fractionalize {
+        nft_token_id: String,
+        new_token_id: String,
+        shares: Uint256,
+        padding: Option<String>,
+    },
+    /// burns all shares of a fractionalized NFT, which must all be owned by the sender, and
+    /// transfers the NFT to the sender
+    Reconstitute {
+        nft_token_id: String,
+        padding: Option<String>,
+    },
+    /// sets the maximum number of token_ids a curator can curate, including token_ids it has
+    /// already curated. `quota` == `None` removes the curator's quota. Requires the
+    /// `CuratorManager` role
+    SetCuratorQuota {
+        curator: Addr,
+        quota: Option<u64>,
+        padding: Option<String>,
+    },
+    /// reserves a token_id prefix for a curator. `prefix` cannot overlap with an existing
+    /// namespace. Requires the `CuratorManager` role
+    RegisterNamespace {
+        prefix: String,
+        owner: Addr,
+        padding: Option<String>,
+    },
+    /// removes a namespace, after which any curator can curate token_ids with its prefix.
+    /// Requires the `CuratorManager` role
+    RemoveNamespace {
+        prefix: String,
+        padding: Option<String>,
+    },
+    /// adds SNIP-20 contracts that sales can accept as payment, registering this contract's code
+    /// hash with each of them, and optionally changes the treasury. Admin-only
+    AddPaymentTokens {
+        payment_tokens: Vec<PaymentToken>,
+        treasury: Option<Addr>,
         padding: Option<String>,
     },
     /// allows an owner of token_ids to change transfer or viewership permissions to other addresses.
@@ -120,6 +420,10 @@ pub enum ExecuteMsg {
     /// * view balance permission: owner can allow another address to view owner's balance of specific token_ids
     /// * view private metadata: owner can allow another address to view private metadata of specific token_ids
     /// * transfer allowance: owner can give permission to another address to transfer tokens up to a certain limit (cumulatively)
+    ///
+    /// This implementation also has a burn allowance, which allows another address to burn the owner's tokens
+    /// up to a certain limit (cumulatively).
+    ///
     /// Owners can set an [expiry](crate::state::expiration) for each of these permissions.
     ///
     /// SNIP1155 gives flexibility for permissions to have any combination of
@@ -140,6 +444,9 @@ pub enum ExecuteMsg {
         /// set allowance by for transfer approvals. If ignored, leaves current permission settings
         transfer: Option<Uint256>,
         transfer_expiry: Option<Expiration>,
+        /// set allowance for burning the owner's tokens. If ignored, leaves current permission settings
+        burn: Option<Uint256>,
+        burn_expiry: Option<Expiration>,
         /// optional message length padding
         padding: Option<String>,
     },
@@ -167,10 +474,12 @@ pub enum ExecuteMsg {
         permit_name: String,
         padding: Option<String>,
     },
+    /// Requires the `CuratorManager` role
     AddCurators {
         add_curators: Vec<Addr>,
         padding: Option<String>,
     },
+    /// Requires the `CuratorManager` role
     RemoveCurators {
         remove_curators: Vec<Addr>,
         padding: Option<String>,
@@ -185,13 +494,25 @@ pub enum ExecuteMsg {
     //     remove_minters: Vec<Addr>,
     //     padding: Option<String>,
     // },
+    /// immediately replaces the admin. `ProposeAdmin` is safer, as the new admin must accept
     ChangeAdmin {
         new_admin: Addr,
         padding: Option<String>,
     },
+    /// proposes a new admin, which becomes admin once it calls `AcceptAdmin` before
+    /// `expiration`, if any. Replaces any pending proposal. Admin-only
+    ProposeAdmin {
+        new_admin: Addr,
+        expiration: Option<Expiration>,
+        padding: Option<String>,
+    },
+    /// makes the sender the admin, if it is the pending admin
+    AcceptAdmin { padding: Option<String> },
+    /// cancels the pending admin proposal. Admin-only
+    CancelAdminProposal { padding: Option<String> },
     /// Permanently breaks admin keys for this contract. No admin function can be called after this
     /// action. Any existing curators or minters will remain as curators or minters; no new curators can be
-    /// added and no current curator can be removed.
+    /// added and no current curator can be removed. All roles granted by admins are revoked.
     ///
     /// Requires caller to input current admin address and contract address. These inputs are not strictly
     /// necessary, but as a safety precaution to reduce the chances of accidentally calling this function.
@@ -200,10 +521,64 @@ pub enum ExecuteMsg {
         contract_address: Addr,
         padding: Option<String>,
     },
+    /// grants a role to an address. Admin-only
+    GrantRole {
+        role: Role,
+        address: Addr,
+        padding: Option<String>,
+    },
+    /// revokes a role from an address. Admin-only
+    RevokeRole {
+        role: Role,
+        address: Addr,
+        padding: Option<String>,
+    },
+    /// stops or resumes the contract. Requires the `Pauser` role
+    SetContractStatus {
+        level: ContractStatus,
+        padding: Option<String>,
+    },
+    /// queues a time-locked admin action, which can be executed once the contract's
+    /// `admin_action_delay` has passed. Requires the role needed to perform the action
+    QueueAdminAction {
+        action: AdminAction,
+        padding: Option<String>,
+    },
+    /// performs a queued admin action whose time lock has passed
+    ExecuteAdminAction {
+        action_id: u64,
+        padding: Option<String>,
+    },
+    /// removes a queued admin action without performing it
+    CancelAdminAction {
+        action_id: u64,
+        padding: Option<String>,
+    },
+    /// registers the code hash that the sender contract's `Snip1155Receive` callbacks are sent to.
+    /// Overwrites any existing registration, including its token_id filters
     RegisterReceive {
         code_hash: String,
+        /// optional list of token_ids this contract wants to receive callbacks for. If `None`,
+        /// callbacks are sent for every token_id
+        token_ids: Option<Vec<String>>,
+        /// if `true`, `Send`s of token_ids that are not in `token_ids` to this contract fail.
+        /// Otherwise the tokens are transferred without a callback. Defaults to `false`
+        reject_unlisted_token_ids: Option<bool>,
         padding: Option<String>,
     },
+    /// removes the sender contract's registered code hash and token_id filters. Callbacks are then
+    /// only sent if a `recipient_code_hash` is provided in `Send`
+    UnregisterReceive { padding: Option<String> },
+}
+
+/// messages that can be included in the `msg` field of a SNIP-20 `Send` to this contract
+#[derive(Serialize, Deserialize, Clone, Debug, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum Snip20ReceiveMsg {
+    /// purchases `quantity` tokens of a token_id that has a sale priced in the sent SNIP-20.
+    /// The tokens are minted to the owner of the sent SNIP-20 tokens, and any amount sent in
+    /// excess of the price is refunded
+    Purchase { token_id: String, quantity: Uint256 },
 }
 
 /// Handle answers in the `data` field of `HandleResponse`. See
@@ -219,6 +594,37 @@ pub enum ExecuteAnswer {
     BatchTransfer { status: ResponseStatus },
     Send { status: ResponseStatus },
     BatchSend { status: ResponseStatus },
+    TransferWithPermit { status: ResponseStatus },
+    CreateSwapOffer { offer_id: u64 },
+    AcceptSwapOffer { status: ResponseStatus },
+    CancelSwapOffer { status: ResponseStatus },
+    CreateEscrow { escrow_id: u64 },
+    ClaimEscrow { status: ResponseStatus },
+    ClawbackEscrow { status: ResponseStatus },
+    CreateVestingSchedule { status: ResponseStatus },
+    ClaimVested { amount: Uint256 },
+    RegisterAirdrop { status: ResponseStatus },
+    ClaimAirdrop { status: ResponseStatus },
+    SetSale { status: ResponseStatus },
+    Purchase { status: ResponseStatus },
+    WithdrawSaleProceeds { amount: Coin },
+    Receive { status: ResponseStatus },
+    AddPaymentTokens { status: ResponseStatus },
+    SetCuratorQuota { status: ResponseStatus },
+    RegisterNamespace { status: ResponseStatus },
+    RemoveNamespace { status: ResponseStatus },
+    SetUser { status: ResponseStatus },
+    Freeze { status: ResponseStatus },
+    Unfreeze { status: ResponseStatus },
+    FreezeMetadata { status: ResponseStatus },
+    SetTransferPolicy { status: ResponseStatus },
+    AddToAllowlist { status: ResponseStatus },
+    RemoveFromAllowlist { status: ResponseStatus },
+    ApproveTransfer { status: ResponseStatus },
+    CreateEditionSeries { status: ResponseStatus },
+    MintEdition { token_id: String },
+    Fractionalize { status: ResponseStatus },
+    Reconstitute { status: ResponseStatus },
     GivePermission { status: ResponseStatus },
     RevokePermission { status: ResponseStatus },
     CreateViewingKey { key: String },
@@ -230,7 +636,17 @@ pub enum ExecuteAnswer {
     RemoveMinters { status: ResponseStatus },
     ChangeAdmin { status: ResponseStatus },
     RemoveAdmin { status: ResponseStatus },
+    ProposeAdmin { status: ResponseStatus },
+    AcceptAdmin { status: ResponseStatus },
+    CancelAdminProposal { status: ResponseStatus },
+    GrantRole { status: ResponseStatus },
+    RevokeRole { status: ResponseStatus },
+    SetContractStatus { status: ResponseStatus },
+    QueueAdminAction { action_id: u64 },
+    ExecuteAdminAction { status: ResponseStatus },
+    CancelAdminAction { status: ResponseStatus },
     RegisterReceive { status: ResponseStatus },
+    UnregisterReceive { status: ResponseStatus },
 }
 
 /////////////////////////////////////////////////////////////////////////////////
@@ -287,8 +703,66 @@ pub enum QueryMsg {
     RegisteredCodeHash {
         contract: Addr,
     },
+    /// returns an open swap offer. Only the maker and the counterparty can view an offer, so
+    /// `viewer` must be one of them
+    SwapOffer {
+        viewer: Addr,
+        key: String,
+        offer_id: u64,
+    },
+    /// returns the sale of a token_id
+    Sale {
+        token_id: String,
+    },
+    /// lists the token_ids curated by a curator, in the order they were curated
+    TokenIdsByCurator {
+        curator: Addr,
+        page: Option<u32>,
+        page_size: u32,
+    },
+    /// lists the registered token_id namespaces and their owners
+    Namespaces {},
+    /// lists the admin and the addresses that have been granted roles
+    RoleHolders {},
+    /// lists the admin actions waiting to be executed
+    QueuedAdminActions {},
+    /// returns an edition series
+    EditionSeries {
+        parent_token_id: String,
+    },
+    /// returns the active user of an NFT, if any. Only the owner and the active user of the NFT
+    /// can view its user, so `viewer` must be one of them
+    NftUser {
+        viewer: Addr,
+        key: String,
+        token_id: String,
+    },
+    /// checks whether a token_id's transfer policy allows `amount` tokens to be transferred from
+    /// `from` to `recipient`. Does not check balances or permissions. Requires the viewing key
+    /// of `from`
+    TransferAllowed {
+        token_id: String,
+        from: Addr,
+        key: String,
+        recipient: Addr,
+        amount: Uint256,
+    },
+    /// lists the SNIP-52 notification channels supported by this contract
+    ListChannels {},
+    /// returns the information needed to receive SNIP-52 notifications on the given channels
+    ChannelInfo {
+        channels: Vec<String>,
+        viewer: Addr,
+        key: String,
+    },
+    /// returns the beneficiary's vesting schedule of a token_id
+    VestingSchedule {
+        address: Addr,
+        key: String,
+        token_id: String,
+    },
     WithPermit {
-        permit: Permit,
+        permit: Permit<Snip1155PermitPermission>,
         query: QueryWithPermit,
     },
 }
@@ -309,9 +783,21 @@ impl QueryMsg {
             } => Ok((vec![owner, allowed_address], key.clone())),
             Self::AllPermissions { address, key, .. } => Ok((vec![address], key.clone())),
             Self::TokenIdPrivateInfo { address, key, .. } => Ok((vec![address], key.clone())),
+            Self::ChannelInfo { viewer, key, .. } => Ok((vec![viewer], key.clone())),
+            Self::VestingSchedule { address, key, .. } => Ok((vec![address], key.clone())),
+            Self::TransferAllowed { from, key, .. } => Ok((vec![from], key.clone())),
+            Self::SwapOffer { viewer, key, .. } => Ok((vec![viewer], key.clone())),
+            Self::NftUser { viewer, key, .. } => Ok((vec![viewer], key.clone())),
             Self::ContractInfo {}
             | Self::TokenIdPublicInfo { .. }
             | Self::RegisteredCodeHash { .. }
+            | Self::Sale { .. }
+            | Self::EditionSeries { .. }
+            | Self::Namespaces {}
+            | Self::RoleHolders {}
+            | Self::QueuedAdminActions {}
+            | Self::TokenIdsByCurator { .. }
+            | Self::ListChannels {}
             | Self::WithPermit { .. } => {
                 unreachable!("This query type does not require viewing key authentication")
             }
@@ -319,6 +805,28 @@ impl QueryMsg {
     }
 }
 
+/// permissions that can be granted by a SNIP1155 query permit. `Owner` grants access to all
+/// permit queries; the other scopes each grant access to a subset of them:
+/// * `balance`: `balance`, `all_balances`, `vesting_schedule`, `swap_offer` and `nft_user`
+/// * `history`: `transaction_history` and `channel_info`
+/// * `allowance`: `permission`, `all_permissions` and `transfer_allowed`
+/// * `private_metadata`: `token_id_private_info`
+///
+/// If `token_ids` is included, queries on other token_ids are rejected, `all_balances` only
+/// returns the listed token_ids, and queries that are not about a single token_id
+/// (`transaction_history`, `all_permissions`, `channel_info` and `swap_offer`) are rejected.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum Snip1155PermitPermission {
+    Owner,
+    Balance,
+    History,
+    Allowance,
+    PrivateMetadata,
+    /// restricts the permit to the listed token_ids
+    TokenIds(Vec<String>),
+}
+
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 #[serde(rename_all = "snake_case")]
 pub enum QueryWithPermit {
@@ -346,6 +854,26 @@ pub enum QueryWithPermit {
     TokenIdPrivateInfo {
         token_id: String,
     },
+    ChannelInfo {
+        channels: Vec<String>,
+    },
+    VestingSchedule {
+        token_id: String,
+    },
+    /// returns a swap offer, if the permit's signer is its maker or counterparty
+    SwapOffer {
+        offer_id: u64,
+    },
+    /// returns the active user of an NFT, if the permit's signer is its owner or user
+    NftUser {
+        token_id: String,
+    },
+    /// checks whether transfers from the permit's signer are allowed
+    TransferAllowed {
+        token_id: String,
+        recipient: Addr,
+        amount: Uint256,
+    },
 }
 
 /// the query responses for each [QueryMsg](crate::msg::QueryMsg) variant
@@ -360,6 +888,9 @@ pub enum QueryAnswer {
         curators: Vec<Addr>,
         /// the list of all token_ids that have been curated
         all_token_ids: Vec<String>,
+        status: ContractStatus,
+        /// the admin proposed with `ProposeAdmin`, which has not accepted yet
+        pending_admin: Option<AdminProposal>,
     },
     /// returns balance of a specific token_id. Owners can give permission to other addresses to query their balance
     Balance {
@@ -392,6 +923,9 @@ pub enum QueryAnswer {
         total_supply: Option<Uint256>,
         /// if owner_is_public == false, total_supply = None
         owner: Option<Addr>,
+        /// whether transfers are locked by the token_id's `transfers_locked_until`, as of the
+        /// block of the latest executed transaction in the contract
+        transfers_locked: bool,
     },
     TokenIdPrivateInfo {
         token_id_info: StoredTokenInfo,
@@ -403,6 +937,57 @@ pub enum QueryAnswer {
     /// returns None if contract has not registered with SNIP1155 contract
     RegisteredCodeHash {
         code_hash: Option<String>,
+        /// token_ids that the contract receives callbacks for. `None` means all token_ids
+        token_ids: Option<Vec<String>>,
+        reject_unlisted_token_ids: bool,
+    },
+    SwapOffer(SwapOffer),
+    Sale(Sale),
+    EditionSeries(EditionSeries),
+    Namespaces {
+        namespaces: Vec<Namespace>,
+    },
+    /// `admin` holds every role, in addition to the addresses in `role_holders`
+    RoleHolders {
+        admin: Option<Addr>,
+        role_holders: Vec<RoleHolder>,
+    },
+    QueuedAdminActions {
+        /// seconds between queuing an admin action and when it can be executed
+        admin_action_delay: u64,
+        actions: Vec<QueuedAdminAction>,
+    },
+    TokenIdsByCurator {
+        token_ids: Vec<String>,
+        /// the total number of token_ids curated by the curator
+        total: u64,
+        quota: Option<u64>,
+    },
+    /// `user` and `expires` are `None` if the NFT has no user, or the user has expired
+    NftUser {
+        user: Option<Addr>,
+        expires: Option<Expiration>,
+    },
+    /// `reason` explains why the transfer is not allowed
+    TransferAllowed {
+        policy: TransferPolicy,
+        allowed: bool,
+        reason: Option<String>,
+    },
+    ListChannels {
+        channels: Vec<String>,
+    },
+    ChannelInfo {
+        channels: Vec<ChannelInfoData>,
+    },
+    /// amounts are as of the block of the latest executed transaction in the contract
+    VestingSchedule {
+        schedule: VestingSchedule,
+        /// total amount vested, including claimed tokens
+        vested: Uint256,
+        claimed: Uint256,
+        /// amount not yet vested
+        locked: Uint256,
     },
     /// returned when an viewing_key-specific errors occur during a user's attempt to
     /// perform an authenticated query
@@ -422,6 +1007,37 @@ pub enum ResponseStatus {
     Failure,
 }
 
+/// a transfer signed by the token owner, for use in `TransferWithPermit`. Transfers are signed as the
+/// `permissions` of a permit, so wallets can sign them the same way as query permits
+#[derive(Serialize, Deserialize, Clone, JsonSchema, Debug, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub struct PermitTransfer {
+    pub token_id: String,
+    pub recipient: Addr,
+    pub amount: Uint256,
+    pub memo: Option<String>,
+    /// must not have been used in another transfer permit by the same owner
+    pub nonce: Uint64,
+    /// the transfer cannot be performed after this expiration
+    pub expiration: Expiration,
+}
+
+/// information needed to receive and decrypt SNIP-52 notifications on a channel. See
+/// [notifications](crate::notifications) for how notification ids and payloads are derived
+#[derive(Serialize, Deserialize, Clone, JsonSchema, Debug, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub struct ChannelInfoData {
+    pub channel: String,
+    /// the address's notification seed for this contract
+    pub seed: Binary,
+    /// number of notifications emitted to the address on this channel so far
+    pub counter: Uint64,
+    /// the id of the next notification that will be emitted on this channel
+    pub next_id: Binary,
+    /// describes the CBOR-encoded notification payload
+    pub cddl: String,
+}
+
 #[derive(Serialize, Deserialize, Clone, JsonSchema, Debug)]
 #[serde(rename_all = "snake_case")]
 pub struct TransferAction {
@@ -431,6 +1047,8 @@ pub struct TransferAction {
     pub recipient: Addr,
     pub amount: Uint256,
     pub memo: Option<String>,
+    /// optional addresses whose balances are read and re-written along with the recipient's
+    pub decoys: Option<Vec<Addr>>,
 }
 
 #[derive(Serialize, Deserialize, Clone, JsonSchema, Debug)]
@@ -444,9 +1062,12 @@ pub struct SendAction {
     pub amount: Uint256,
     pub msg: Option<Binary>,
     pub memo: Option<String>,
+    /// optional addresses whose balances are read and re-written along with the recipient's
+    pub decoys: Option<Vec<Addr>>,
 }
 
 // Take a Vec<u8> and pad it up to a multiple of `block_size`, using spaces at the end.
+#[allow(clippy::manual_repeat_n)]
 pub fn space_pad(block_size: usize, message: &mut Vec<u8>) -> &mut Vec<u8> {
     let len = message.len();
     let surplus = len % block_size;
@@ -0,0 +1,320 @@
+//! SNIP-52 private push notifications.
+//!
+//! Each address has a secret notification seed per contract, derived from the contract's
+//! `prng_seed` and the address. For every notification sent to an address on a given channel,
+//! the contract emits a plaintext log attribute whose key is `snip52:{notification_id}` and
+//! whose value is the encrypted notification data.
+//!
+//! * `notification_id` = HMAC-SHA256(seed, "{channel}:{counter}"), where `counter` is the number
+//!   of notifications previously emitted to the address on this channel.
+//! * the data is the CBOR-encoded payload described by the channel's CDDL, zero-padded to a
+//!   multiple of [NOTIFICATION_BLOCK_SIZE](crate::notifications::NOTIFICATION_BLOCK_SIZE) bytes
+//!   and encrypted with ChaCha20-Poly1305, using `seed` as the key, the `notification_id` as the
+//!   additional authenticated data, and a nonce equal to the first 12 bytes of SHA-256(channel)
+//!   XOR-ed with `counter` as a big-endian integer in the last 8 bytes.
+//!
+//! Clients obtain `seed`, `counter` and the next expected `notification_id` through the
+//! `ChannelInfo` query.
+
+use chacha20poly1305::{
+    aead::{generic_array::GenericArray, Aead, KeyInit, Payload},
+    ChaCha20Poly1305,
+};
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+
+use cosmwasm_std::{
+    Addr, Api, Attribute, Binary, CanonicalAddr, StdError, StdResult, Storage, Uint256, Uint64,
+};
+use secret_toolkit::crypto::sha_256;
+
+use crate::{
+    msg::ChannelInfoData,
+    state::{contr_conf_r, notification_counter_r, notification_counter_w},
+};
+
+/// notifications when tokens are transferred or sent to an address
+pub const TRANSFER_CHANNEL: &str = "transfer";
+/// notifications when tokens are minted to an address
+pub const MINT_CHANNEL: &str = "mint";
+/// notifications when another address burns an address's tokens
+pub const BURN_CHANNEL: &str = "burn";
+/// notifications when an address's transfer or burn allowance is granted or revoked
+pub const ALLOWANCE_CHANNEL: &str = "allowance";
+
+/// all channels supported by this contract
+pub const CHANNELS: [&str; 4] = [
+    TRANSFER_CHANNEL,
+    MINT_CHANNEL,
+    BURN_CHANNEL,
+    ALLOWANCE_CHANNEL,
+];
+
+/// notification payloads are padded to a multiple of this size before encryption
+pub const NOTIFICATION_BLOCK_SIZE: usize = 128;
+
+const TRANSFER_CDDL: &str = "transfer = [token_id: tstr, amount: biguint, sender: bstr]";
+const MINT_CDDL: &str = "mint = [token_id: tstr, amount: biguint, minter: bstr]";
+const BURN_CDDL: &str = "burn = [token_id: tstr, amount: biguint, burner: bstr]";
+const ALLOWANCE_CDDL: &str =
+    "allowance = [token_id: tstr, owner: bstr, transfer_allowance: biguint, burn_allowance: biguint]";
+
+type HmacSha256 = Hmac<Sha256>;
+
+/// a notification to be emitted at the end of a transaction
+#[derive(Clone, Debug, PartialEq)]
+pub struct Notification {
+    /// the address that receives the notification
+    pub notification_for: Addr,
+    pub data: NotificationData,
+}
+
+/// notification payload for each channel
+#[derive(Clone, Debug, PartialEq)]
+pub enum NotificationData {
+    Transfer {
+        token_id: String,
+        amount: Uint256,
+        /// previous owner of the tokens
+        sender: Addr,
+    },
+    Mint {
+        token_id: String,
+        amount: Uint256,
+        minter: Addr,
+    },
+    Burn {
+        token_id: String,
+        amount: Uint256,
+        burner: Addr,
+    },
+    Allowance {
+        token_id: String,
+        owner: Addr,
+        transfer_allowance: Uint256,
+        burn_allowance: Uint256,
+    },
+}
+
+impl Notification {
+    pub fn new(notification_for: Addr, data: NotificationData) -> Self {
+        Notification {
+            notification_for,
+            data,
+        }
+    }
+}
+
+impl NotificationData {
+    pub fn channel(&self) -> &'static str {
+        match self {
+            NotificationData::Transfer { .. } => TRANSFER_CHANNEL,
+            NotificationData::Mint { .. } => MINT_CHANNEL,
+            NotificationData::Burn { .. } => BURN_CHANNEL,
+            NotificationData::Allowance { .. } => ALLOWANCE_CHANNEL,
+        }
+    }
+
+    /// CBOR encoding of the payload, as described by the channel's CDDL
+    fn to_cbor(&self, api: &dyn Api) -> StdResult<Vec<u8>> {
+        let mut buf = vec![];
+        match self {
+            NotificationData::Transfer {
+                token_id,
+                amount,
+                sender: address,
+            }
+            | NotificationData::Mint {
+                token_id,
+                amount,
+                minter: address,
+            }
+            | NotificationData::Burn {
+                token_id,
+                amount,
+                burner: address,
+            } => {
+                cbor_head(&mut buf, 4, 3);
+                cbor_tstr(&mut buf, token_id);
+                cbor_biguint(&mut buf, amount);
+                cbor_bstr(
+                    &mut buf,
+                    api.addr_canonicalize(address.as_str())?.as_slice(),
+                );
+            }
+            NotificationData::Allowance {
+                token_id,
+                owner,
+                transfer_allowance,
+                burn_allowance,
+            } => {
+                cbor_head(&mut buf, 4, 4);
+                cbor_tstr(&mut buf, token_id);
+                cbor_bstr(&mut buf, api.addr_canonicalize(owner.as_str())?.as_slice());
+                cbor_biguint(&mut buf, transfer_allowance);
+                cbor_biguint(&mut buf, burn_allowance);
+            }
+        }
+        Ok(buf)
+    }
+}
+
+/// encrypts and renders notifications as plaintext log attributes, incrementing the
+/// channel counter of each notified address
+pub fn render_notifications(
+    storage: &mut dyn Storage,
+    api: &dyn Api,
+    notifications: Vec<Notification>,
+) -> StdResult<Vec<Attribute>> {
+    if notifications.is_empty() {
+        return Ok(vec![]);
+    }
+    let secret = internal_secret(&contr_conf_r(storage).load()?.prng_seed);
+
+    let mut attributes = Vec::with_capacity(notifications.len());
+    for notification in notifications {
+        let channel = notification.data.channel();
+        let canonical = api.addr_canonicalize(notification.notification_for.as_str())?;
+        let seed = notification_seed(&secret, &canonical);
+
+        let counter = notification_counter_r(storage, channel)
+            .may_load(canonical.as_slice())?
+            .unwrap_or_default();
+        let id = notification_id(&seed, channel, counter);
+
+        let mut plaintext = notification.data.to_cbor(api)?;
+        let padded_len =
+            plaintext.len().div_ceil(NOTIFICATION_BLOCK_SIZE) * NOTIFICATION_BLOCK_SIZE;
+        plaintext.resize(padded_len, 0);
+
+        let cipher = ChaCha20Poly1305::new(GenericArray::from_slice(&seed));
+        let nonce = notification_nonce(channel, counter);
+        let data = cipher
+            .encrypt(
+                GenericArray::from_slice(&nonce),
+                Payload {
+                    msg: &plaintext,
+                    aad: &id,
+                },
+            )
+            .map_err(|_| StdError::generic_err("failed to encrypt notification"))?;
+
+        notification_counter_w(storage, channel).save(canonical.as_slice(), &(counter + 1))?;
+
+        attributes.push(Attribute::new_plaintext(
+            format!("snip52:{}", Binary::from(id.as_slice()).to_base64()),
+            Binary::from(data).to_base64(),
+        ));
+    }
+
+    Ok(attributes)
+}
+
+/// returns the information a client needs to decrypt the notifications of `address` on a channel
+pub fn channel_info(
+    storage: &dyn Storage,
+    api: &dyn Api,
+    address: &Addr,
+    channel: &str,
+) -> StdResult<ChannelInfoData> {
+    let cddl = match channel {
+        TRANSFER_CHANNEL => TRANSFER_CDDL,
+        MINT_CHANNEL => MINT_CDDL,
+        BURN_CHANNEL => BURN_CDDL,
+        ALLOWANCE_CHANNEL => ALLOWANCE_CDDL,
+        _ => {
+            return Err(StdError::generic_err(format!(
+                "channel {} does not exist",
+                channel
+            )))
+        }
+    };
+
+    let secret = internal_secret(&contr_conf_r(storage).load()?.prng_seed);
+    let canonical = api.addr_canonicalize(address.as_str())?;
+    let seed = notification_seed(&secret, &canonical);
+    let counter = notification_counter_r(storage, channel)
+        .may_load(canonical.as_slice())?
+        .unwrap_or_default();
+
+    Ok(ChannelInfoData {
+        channel: channel.to_string(),
+        seed: Binary::from(seed.as_slice()),
+        counter: Uint64::from(counter),
+        next_id: Binary::from(notification_id(&seed, channel, counter).as_slice()),
+        cddl: cddl.to_string(),
+    })
+}
+
+/////////////////////////////////////////////////////////////////////////////////
+// Private functions
+/////////////////////////////////////////////////////////////////////////////////
+
+fn hmac_sha256(key: &[u8], msg: &[u8]) -> [u8; 32] {
+    // HMAC accepts keys of any length
+    let mut mac = <HmacSha256 as Mac>::new_from_slice(key).unwrap();
+    mac.update(msg);
+    mac.finalize().into_bytes().into()
+}
+
+/// contract-wide secret from which all notification seeds are derived. Kept separate from
+/// `prng_seed` itself, which also seeds viewing keys
+fn internal_secret(prng_seed: &[u8]) -> [u8; 32] {
+    hmac_sha256(prng_seed, b"snip52:internal_secret")
+}
+
+fn notification_seed(secret: &[u8; 32], address: &CanonicalAddr) -> [u8; 32] {
+    hmac_sha256(secret, address.as_slice())
+}
+
+fn notification_id(seed: &[u8; 32], channel: &str, counter: u64) -> [u8; 32] {
+    hmac_sha256(seed, format!("{}:{}", channel, counter).as_bytes())
+}
+
+fn notification_nonce(channel: &str, counter: u64) -> [u8; 12] {
+    let mut nonce = [0u8; 12];
+    nonce.copy_from_slice(&sha_256(channel.as_bytes())[..12]);
+    for (n, c) in nonce[4..].iter_mut().zip(counter.to_be_bytes()) {
+        *n ^= c;
+    }
+    nonce
+}
+
+/// writes a CBOR data item head with major type `major` and argument `arg`
+fn cbor_head(buf: &mut Vec<u8>, major: u8, arg: u64) {
+    let major = major << 5;
+    match arg {
+        0..=23 => buf.push(major | arg as u8),
+        24..=0xff => buf.extend_from_slice(&[major | 24, arg as u8]),
+        0x100..=0xffff => {
+            buf.push(major | 25);
+            buf.extend_from_slice(&(arg as u16).to_be_bytes());
+        }
+        0x1_0000..=0xffff_ffff => {
+            buf.push(major | 26);
+            buf.extend_from_slice(&(arg as u32).to_be_bytes());
+        }
+        _ => {
+            buf.push(major | 27);
+            buf.extend_from_slice(&arg.to_be_bytes());
+        }
+    }
+}
+
+fn cbor_bstr(buf: &mut Vec<u8>, bytes: &[u8]) {
+    cbor_head(buf, 2, bytes.len() as u64);
+    buf.extend_from_slice(bytes);
+}
+
+fn cbor_tstr(buf: &mut Vec<u8>, text: &str) {
+    cbor_head(buf, 3, text.len() as u64);
+    buf.extend_from_slice(text.as_bytes());
+}
+
+/// unsigned bignum (tag 2) with leading zero bytes removed
+fn cbor_biguint(buf: &mut Vec<u8>, value: &Uint256) {
+    let bytes = value.to_be_bytes();
+    let start = bytes.iter().position(|b| *b != 0).unwrap_or(bytes.len());
+    cbor_head(buf, 6, 2);
+    cbor_bstr(buf, &bytes[start..]);
+}
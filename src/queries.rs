@@ -1,25 +1,29 @@
 use std::collections::BTreeSet;
 
 use cosmwasm_std::{
-    entry_point, to_binary, Addr, Binary, BlockInfo, Deps, Env, StdError, StdResult, Timestamp,
-    Uint256,
+    entry_point, to_binary, Addr, Binary, BlockInfo, Deps, Env, StdError, StdResult, Storage,
+    Timestamp, Uint256,
 };
 use secret_toolkit::{
-    permit::{validate, Permit, TokenPermissions},
+    permit::{validate, Permit},
     viewing_key::{ViewingKey, ViewingKeyStore},
 };
 
 use crate::{
-    msg::{QueryAnswer, QueryMsg, QueryWithPermit},
+    msg::{QueryAnswer, QueryMsg, QueryWithPermit, Snip1155PermitPermission},
+    notifications::{channel_info, CHANNELS},
     state::{
-        balances_r, blockinfo_r, contr_conf_r, get_receiver_hash,
+        admin_actions_r, balances_r, blockinfo_r, contr_conf_r, curator_quotas_r, edition_series_r,
+        list_curator_token_ids, may_load_receiver, nft_users_r,
         permissions::{
             list_owner_permission_keys, may_load_any_permission, Permission, PermissionKey,
         },
-        state_structs::OwnerBalance,
-        tkn_info_r, tkn_tot_supply_r,
+        sales_r,
+        state_structs::{NftUser, OwnerBalance},
+        swap_offers_r, tkn_info_r, tkn_tot_supply_r, transfer_policies_r,
+        transfer_policy_violation,
         txhistory::{get_txs, may_get_current_owner},
-        PREFIX_REVOKED_PERMITS,
+        vesting_r, PREFIX_REVOKED_PERMITS,
     },
 };
 
@@ -35,17 +39,37 @@ pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
         QueryMsg::ContractInfo {} => query_contract_info(deps),
         QueryMsg::TokenIdPublicInfo { token_id } => query_token_id_public_info(deps, token_id),
         QueryMsg::RegisteredCodeHash { contract } => query_registered_code_hash(deps, contract),
+        QueryMsg::Sale { token_id } => query_sale(deps, token_id),
+        QueryMsg::Namespaces {} => query_namespaces(deps),
+        QueryMsg::RoleHolders {} => query_role_holders(deps),
+        QueryMsg::QueuedAdminActions {} => query_queued_admin_actions(deps),
+        QueryMsg::TokenIdsByCurator {
+            curator,
+            page,
+            page_size,
+        } => query_token_ids_by_curator(deps, &curator, page.unwrap_or(0), page_size),
+        QueryMsg::EditionSeries { parent_token_id } => query_edition_series(deps, parent_token_id),
+        QueryMsg::ListChannels {} => query_list_channels(),
         QueryMsg::WithPermit { permit, query } => permit_queries(deps, permit, query),
         QueryMsg::Balance { .. }
         | QueryMsg::AllBalances { .. }
         | QueryMsg::TransactionHistory { .. }
         | QueryMsg::Permission { .. }
         | QueryMsg::AllPermissions { .. }
-        | QueryMsg::TokenIdPrivateInfo { .. } => viewing_keys_queries(deps, msg),
+        | QueryMsg::TokenIdPrivateInfo { .. }
+        | QueryMsg::ChannelInfo { .. }
+        | QueryMsg::VestingSchedule { .. }
+        | QueryMsg::TransferAllowed { .. }
+        | QueryMsg::SwapOffer { .. }
+        | QueryMsg::NftUser { .. } => viewing_keys_queries(deps, msg),
     }
 }
 
-fn permit_queries(deps: Deps, permit: Permit, query: QueryWithPermit) -> Result<Binary, StdError> {
+fn permit_queries(
+    deps: Deps,
+    permit: Permit<Snip1155PermitPermission>,
+    query: QueryWithPermit,
+) -> Result<Binary, StdError> {
     // Validate permit content
     let contract_address = contr_conf_r(deps.storage).load()?.contract_address;
 
@@ -58,23 +82,36 @@ fn permit_queries(deps: Deps, permit: Permit, query: QueryWithPermit) -> Result<
     )?;
     let account = deps.api.addr_validate(&account_str)?;
 
-    if !permit.check_permission(&TokenPermissions::Owner) {
-        return Err(StdError::generic_err(format!(
-            "`Owner` permit required for SNIP1155 permit queries, got permissions {:?}",
-            permit.params.permissions
-        )));
-    }
+    // token_ids this permit is restricted to, if any
+    let permitted_token_ids = permit_token_ids(&permit);
 
-    // Permit validated! We can now execute the query.
+    // Permit validated! We can now check the permit's scope and execute the query.
     match query {
         QueryWithPermit::Balance { owner, token_id } => {
+            check_permit_scope(&permit, Snip1155PermitPermission::Balance, "balance")?;
+            check_permit_token_id(permitted_token_ids.as_ref(), &token_id)?;
             query_balance(deps, &owner, &account, token_id)
         }
         QueryWithPermit::AllBalances {
             tx_history_page,
             tx_history_page_size,
-        } => query_all_balances(deps, &account, tx_history_page, tx_history_page_size),
+        } => {
+            check_permit_scope(&permit, Snip1155PermitPermission::Balance, "all_balances")?;
+            query_all_balances(
+                deps,
+                &account,
+                tx_history_page,
+                tx_history_page_size,
+                permitted_token_ids.as_ref(),
+            )
+        }
         QueryWithPermit::TransactionHistory { page, page_size } => {
+            check_permit_scope(
+                &permit,
+                Snip1155PermitPermission::History,
+                "transaction_history",
+            )?;
+            check_permit_all_token_ids(permitted_token_ids.as_ref(), "transaction_history")?;
             query_transactions(deps, &account, page.unwrap_or(0), page_size)
         }
         QueryWithPermit::Permission {
@@ -82,6 +119,8 @@ fn permit_queries(deps: Deps, permit: Permit, query: QueryWithPermit) -> Result<
             allowed_address,
             token_id,
         } => {
+            check_permit_scope(&permit, Snip1155PermitPermission::Allowance, "permission")?;
+            check_permit_token_id(permitted_token_ids.as_ref(), &token_id)?;
             if account != owner.as_str() && account != allowed_address.as_str() {
                 return Err(StdError::generic_err(format!(
                     "Cannot query permission. Requires permit for either owner {:?} or viewer||spender {:?}, got permit for {:?}",
@@ -92,11 +131,119 @@ fn permit_queries(deps: Deps, permit: Permit, query: QueryWithPermit) -> Result<
             query_permission(deps, token_id, owner, allowed_address)
         }
         QueryWithPermit::AllPermissions { page, page_size } => {
+            check_permit_scope(
+                &permit,
+                Snip1155PermitPermission::Allowance,
+                "all_permissions",
+            )?;
+            check_permit_all_token_ids(permitted_token_ids.as_ref(), "all_permissions")?;
             query_all_permissions(deps, &account, page.unwrap_or(0), page_size)
         }
         QueryWithPermit::TokenIdPrivateInfo { token_id } => {
+            check_permit_scope(
+                &permit,
+                Snip1155PermitPermission::PrivateMetadata,
+                "token_id_private_info",
+            )?;
+            check_permit_token_id(permitted_token_ids.as_ref(), &token_id)?;
             query_token_id_private_info(deps, &account, token_id)
         }
+        QueryWithPermit::VestingSchedule { token_id } => {
+            check_permit_scope(
+                &permit,
+                Snip1155PermitPermission::Balance,
+                "vesting_schedule",
+            )?;
+            check_permit_token_id(permitted_token_ids.as_ref(), &token_id)?;
+            query_vesting_schedule(deps, &account, token_id)
+        }
+        QueryWithPermit::ChannelInfo { channels } => {
+            check_permit_scope(&permit, Snip1155PermitPermission::History, "channel_info")?;
+            check_permit_all_token_ids(permitted_token_ids.as_ref(), "channel_info")?;
+            query_channel_info(deps, &account, channels)
+        }
+        QueryWithPermit::SwapOffer { offer_id } => {
+            check_permit_scope(&permit, Snip1155PermitPermission::Balance, "swap_offer")?;
+            check_permit_all_token_ids(permitted_token_ids.as_ref(), "swap_offer")?;
+            query_swap_offer(deps, &account, offer_id)
+        }
+        QueryWithPermit::NftUser { token_id } => {
+            check_permit_scope(&permit, Snip1155PermitPermission::Balance, "nft_user")?;
+            check_permit_token_id(permitted_token_ids.as_ref(), &token_id)?;
+            query_nft_user(deps, &account, token_id)
+        }
+        QueryWithPermit::TransferAllowed {
+            token_id,
+            recipient,
+            amount,
+        } => {
+            check_permit_scope(
+                &permit,
+                Snip1155PermitPermission::Allowance,
+                "transfer_allowed",
+            )?;
+            check_permit_token_id(permitted_token_ids.as_ref(), &token_id)?;
+            query_transfer_allowed(deps, token_id, account, recipient, amount)
+        }
+    }
+}
+
+/// returns error if the permit has neither `Owner` permission nor the permission
+/// required for this query
+fn check_permit_scope(
+    permit: &Permit<Snip1155PermitPermission>,
+    required: Snip1155PermitPermission,
+    query_name: &str,
+) -> StdResult<()> {
+    if permit.check_permission(&Snip1155PermitPermission::Owner)
+        || permit.check_permission(&required)
+    {
+        return Ok(());
+    }
+
+    Err(StdError::generic_err(format!(
+        "`{:?}` or `Owner` permit required for {} permit query, got permissions {:?}",
+        required, query_name, permit.params.permissions
+    )))
+}
+
+/// returns the union of all token_ids the permit is restricted to, or `None` if unrestricted
+fn permit_token_ids(permit: &Permit<Snip1155PermitPermission>) -> Option<BTreeSet<String>> {
+    let mut restricted = false;
+    let mut token_ids = BTreeSet::new();
+    for permission in &permit.params.permissions {
+        if let Snip1155PermitPermission::TokenIds(ids) = permission {
+            restricted = true;
+            token_ids.extend(ids.iter().cloned());
+        }
+    }
+
+    restricted.then_some(token_ids)
+}
+
+fn check_permit_token_id(
+    permitted_token_ids: Option<&BTreeSet<String>>,
+    token_id: &str,
+) -> StdResult<()> {
+    match permitted_token_ids {
+        Some(ids) if !ids.contains(token_id) => Err(StdError::generic_err(format!(
+            "permit does not apply to token_id {}",
+            token_id
+        ))),
+        _ => Ok(()),
+    }
+}
+
+fn check_permit_all_token_ids(
+    permitted_token_ids: Option<&BTreeSet<String>>,
+    query_name: &str,
+) -> StdResult<()> {
+    match permitted_token_ids {
+        Some(_) => Err(StdError::generic_err(format!(
+            "{} permit query is not available for permits restricted to specific token_ids",
+            query_name
+        ))),
+        None => Ok(()),
     }
 }
 
@@ -117,7 +264,7 @@ fn viewing_keys_queries(deps: Deps, msg: QueryMsg) -> StdResult<Binary> {
                     tx_history_page,
                     tx_history_page_size,
                     ..
-                } => query_all_balances(deps, address, tx_history_page, tx_history_page_size),
+                } => query_all_balances(deps, address, tx_history_page, tx_history_page_size, None),
                 QueryMsg::TransactionHistory {
                     page, page_size, ..
                 } => query_transactions(deps, address, page.unwrap_or(0), page_size),
@@ -133,9 +280,35 @@ fn viewing_keys_queries(deps: Deps, msg: QueryMsg) -> StdResult<Binary> {
                 QueryMsg::TokenIdPrivateInfo {
                     address, token_id, ..
                 } => query_token_id_private_info(deps, &address, token_id),
+                QueryMsg::ChannelInfo {
+                    viewer, channels, ..
+                } => query_channel_info(deps, &viewer, channels),
+                QueryMsg::VestingSchedule {
+                    address, token_id, ..
+                } => query_vesting_schedule(deps, &address, token_id),
+                QueryMsg::TransferAllowed {
+                    token_id,
+                    from,
+                    recipient,
+                    amount,
+                    ..
+                } => query_transfer_allowed(deps, token_id, from, recipient, amount),
+                QueryMsg::SwapOffer {
+                    viewer, offer_id, ..
+                } => query_swap_offer(deps, &viewer, offer_id),
+                QueryMsg::NftUser {
+                    viewer, token_id, ..
+                } => query_nft_user(deps, &viewer, token_id),
                 QueryMsg::ContractInfo {}
                 | QueryMsg::TokenIdPublicInfo { .. }
                 | QueryMsg::RegisteredCodeHash { .. }
+                | QueryMsg::Sale { .. }
+                | QueryMsg::EditionSeries { .. }
+                | QueryMsg::Namespaces {}
+                | QueryMsg::RoleHolders {}
+                | QueryMsg::QueuedAdminActions {}
+                | QueryMsg::TokenIdsByCurator { .. }
+                | QueryMsg::ListChannels {}
                 | QueryMsg::WithPermit { .. } => {
                     unreachable!("This query type does not require viewing key authentication")
                 }
@@ -154,6 +327,8 @@ fn query_contract_info(deps: Deps) -> StdResult<Binary> {
         admin: contr_conf.admin,
         curators: contr_conf.curators,
         all_token_ids: contr_conf.token_id_list,
+        status: contr_conf.status,
+        pending_admin: contr_conf.pending_admin,
     };
     to_binary(&response)
 }
@@ -181,12 +356,29 @@ fn query_token_id_public_info(deps: Deps, token_id: String) -> StdResult<Binary>
                     None
                 };
 
+            let transfers_locked = match tkn_info.token_config.flatten().transfers_locked_until {
+                Some(locked_until) => {
+                    let block =
+                        blockinfo_r(deps.storage)
+                            .may_load()?
+                            .unwrap_or_else(|| BlockInfo {
+                                height: 1,
+                                time: Timestamp::from_seconds(1),
+                                chain_id: "not used".to_string(),
+                                random: None,
+                            });
+                    !locked_until.is_expired(&block)
+                }
+                None => false,
+            };
+
             // private_metadata always == None for public info query
             tkn_info.private_metadata = None;
             let response = QueryAnswer::TokenIdPublicInfo {
                 token_id_info: tkn_info,
                 total_supply,
                 owner,
+                transfers_locked,
             };
             to_binary(&response)
         }
@@ -211,9 +403,12 @@ fn query_token_id_private_info(deps: Deps, viewer: &Addr, token_id: String) -> S
         None
     };
 
-    // private metadata is viewable if viewer owns at least 1 token
-    let viewer_owns_some_tokens =
-        match balances_r(deps.storage, &token_id).may_load(to_binary(&viewer)?.as_slice())? {
+    // private metadata is viewable if viewer owns at least 1 token, or is the active user of the NFT
+    let viewer_is_user = may_load_active_nft_user(deps.storage, &token_id)?
+        .map(|nft_user| &nft_user.user == viewer)
+        .unwrap_or(false);
+    let viewer_owns_some_tokens = viewer_is_user
+        || match balances_r(deps.storage, &token_id).may_load(to_binary(&viewer)?.as_slice())? {
             None => false,
             Some(i) if i == Uint256::from(0_u64) => false,
             Some(i) if i > Uint256::from(0_u64) => true,
@@ -269,17 +464,223 @@ fn query_token_id_private_info(deps: Deps, viewer: &Addr, token_id: String) -> S
 }
 
 fn query_registered_code_hash(deps: Deps, contract: Addr) -> StdResult<Binary> {
-    let may_hash_res = get_receiver_hash(deps.storage, &contract);
-    let response: QueryAnswer = match may_hash_res {
-        Some(hash_res) => QueryAnswer::RegisteredCodeHash {
-            code_hash: Some(hash_res?),
+    let response: QueryAnswer = match may_load_receiver(deps.storage, &contract)? {
+        Some(registration) => QueryAnswer::RegisteredCodeHash {
+            code_hash: Some(registration.code_hash),
+            token_ids: registration.token_ids,
+            reject_unlisted_token_ids: registration.reject_unlisted_token_ids,
+        },
+        None => QueryAnswer::RegisteredCodeHash {
+            code_hash: None,
+            token_ids: None,
+            reject_unlisted_token_ids: false,
+        },
+    };
+
+    to_binary(&response)
+}
+
+fn query_swap_offer(deps: Deps, viewer: &Addr, offer_id: u64) -> StdResult<Binary> {
+    let offer = swap_offers_r(deps.storage)
+        .may_load(&offer_id.to_be_bytes())?
+        .ok_or_else(|| StdError::generic_err(format!("swap offer {} does not exist", offer_id)))?;
+    if &offer.maker != viewer && offer.counterparty.as_ref() != Some(viewer) {
+        return Err(StdError::generic_err(format!(
+            "only the maker and counterparty can view swap offer {}",
+            offer_id
+        )));
+    }
+
+    to_binary(&QueryAnswer::SwapOffer(offer))
+}
+
+fn query_token_ids_by_curator(
+    deps: Deps,
+    curator: &Addr,
+    page: u32,
+    page_size: u32,
+) -> StdResult<Binary> {
+    let (token_ids, total) = list_curator_token_ids(deps.storage, curator, page, page_size)?;
+    let quota = curator_quotas_r(deps.storage).may_load(to_binary(curator)?.as_slice())?;
+
+    let response = QueryAnswer::TokenIdsByCurator {
+        token_ids,
+        total,
+        quota,
+    };
+    to_binary(&response)
+}
+
+fn query_namespaces(deps: Deps) -> StdResult<Binary> {
+    let namespaces = contr_conf_r(deps.storage).load()?.namespaces;
+    to_binary(&QueryAnswer::Namespaces { namespaces })
+}
+
+fn query_role_holders(deps: Deps) -> StdResult<Binary> {
+    let config = contr_conf_r(deps.storage).load()?;
+    to_binary(&QueryAnswer::RoleHolders {
+        admin: config.admin,
+        role_holders: config.roles,
+    })
+}
+
+fn query_queued_admin_actions(deps: Deps) -> StdResult<Binary> {
+    let config = contr_conf_r(deps.storage).load()?;
+    let actions = admin_actions_r(deps.storage)
+        .may_load()?
+        .unwrap_or_default();
+    to_binary(&QueryAnswer::QueuedAdminActions {
+        admin_action_delay: config.admin_action_delay,
+        actions,
+    })
+}
+
+fn query_edition_series(deps: Deps, parent_token_id: String) -> StdResult<Binary> {
+    let mut series = edition_series_r(deps.storage)
+        .may_load(parent_token_id.as_bytes())?
+        .ok_or_else(|| {
+            StdError::generic_err(format!("edition series {} does not exist", parent_token_id))
+        })?;
+    // private metadata is only viewable by each edition's owner, through `TokenIdPrivateInfo`
+    series.token_info.private_metadata = None;
+
+    to_binary(&QueryAnswer::EditionSeries(series))
+}
+
+fn query_transfer_allowed(
+    deps: Deps,
+    token_id: String,
+    from: Addr,
+    recipient: Addr,
+    amount: Uint256,
+) -> StdResult<Binary> {
+    if tkn_info_r(deps.storage)
+        .may_load(token_id.as_bytes())?
+        .is_none()
+    {
+        return Err(StdError::generic_err(format!(
+            "token_id {} does not exist",
+            token_id
+        )));
+    }
+    let policy = transfer_policies_r(deps.storage)
+        .may_load(token_id.as_bytes())?
+        .unwrap_or_default();
+    let reason = transfer_policy_violation(
+        deps.storage,
+        &token_id,
+        Some(&from),
+        Some(&recipient),
+        amount,
+    )?;
+    to_binary(&QueryAnswer::TransferAllowed {
+        policy,
+        allowed: reason.is_none(),
+        reason,
+    })
+}
+
+fn query_nft_user(deps: Deps, viewer: &Addr, token_id: String) -> StdResult<Binary> {
+    if tkn_info_r(deps.storage)
+        .may_load(token_id.as_bytes())?
+        .is_none()
+    {
+        return Err(StdError::generic_err(format!(
+            "token_id {} does not exist",
+            token_id
+        )));
+    }
+
+    let nft_user = may_load_active_nft_user(deps.storage, &token_id)?;
+    let is_owner = may_get_current_owner(deps.storage, &token_id)?.as_ref() == Some(viewer);
+    let is_user = nft_user.as_ref().map(|u| &u.user) == Some(viewer);
+    if !is_owner && !is_user {
+        return Err(StdError::generic_err(format!(
+            "only the owner and user of token_id {} can view its user",
+            token_id
+        )));
+    }
+
+    let response = match nft_user {
+        Some(nft_user) => QueryAnswer::NftUser {
+            user: Some(nft_user.user),
+            expires: Some(nft_user.expires),
+        },
+        None => QueryAnswer::NftUser {
+            user: None,
+            expires: None,
         },
-        None => QueryAnswer::RegisteredCodeHash { code_hash: None },
     };
+    to_binary(&response)
+}
+
+/// loads the user of an NFT, if it has not expired as of the latest executed transaction
+fn may_load_active_nft_user(storage: &dyn Storage, token_id: &str) -> StdResult<Option<NftUser>> {
+    let nft_user = match nft_users_r(storage).may_load(token_id.as_bytes())? {
+        Some(i) => i,
+        None => return Ok(None),
+    };
+    let block = blockinfo_r(storage)
+        .may_load()?
+        .unwrap_or_else(|| BlockInfo {
+            height: 1,
+            time: Timestamp::from_seconds(1),
+            chain_id: "not used".to_string(),
+            random: None,
+        });
+    if nft_user.expires.is_expired(&block) {
+        return Ok(None);
+    }
+    Ok(Some(nft_user))
+}
+
+fn query_sale(deps: Deps, token_id: String) -> StdResult<Binary> {
+    let sale = sales_r(deps.storage)
+        .may_load(token_id.as_bytes())?
+        .ok_or_else(|| {
+            StdError::generic_err(format!("token_id {} does not have a sale", token_id))
+        })?;
+
+    to_binary(&QueryAnswer::Sale(sale))
+}
 
+fn query_vesting_schedule(deps: Deps, beneficiary: &Addr, token_id: String) -> StdResult<Binary> {
+    let schedule = vesting_r(deps.storage, beneficiary)
+        .may_load(token_id.as_bytes())?
+        .ok_or_else(|| {
+            StdError::generic_err(format!(
+                "no vesting schedule found for token_id {}",
+                token_id
+            ))
+        })?;
+    let now = blockinfo_r(deps.storage).load()?.time.seconds();
+    let vested = schedule.vested(now);
+
+    let response = QueryAnswer::VestingSchedule {
+        vested,
+        claimed: schedule.claimed,
+        locked: schedule.total - vested,
+        schedule,
+    };
     to_binary(&response)
 }
 
+fn query_list_channels() -> StdResult<Binary> {
+    to_binary(&QueryAnswer::ListChannels {
+        channels: CHANNELS.iter().map(|c| c.to_string()).collect(),
+    })
+}
+
+fn query_channel_info(deps: Deps, viewer: &Addr, channels: Vec<String>) -> StdResult<Binary> {
+    let channels = channels
+        .iter()
+        .map(|channel| channel_info(deps.storage, deps.api, viewer, channel))
+        .collect::<StdResult<Vec<_>>>()?;
+
+    to_binary(&QueryAnswer::ChannelInfo { channels })
+}
+
+#[allow(clippy::needless_else)]
 fn query_balance(deps: Deps, owner: &Addr, viewer: &Addr, token_id: String) -> StdResult<Binary> {
     if owner != viewer {
         let permission_op = may_load_any_permission(deps.storage, owner, &token_id, viewer)?;
@@ -320,11 +721,14 @@ fn query_balance(deps: Deps, owner: &Addr, viewer: &Addr, token_id: String) -> S
     to_binary(&response)
 }
 
+/// if `permitted_token_ids` == Some(_), only balances of these token_ids are returned
+#[allow(clippy::unnecessary_map_or)]
 fn query_all_balances(
     deps: Deps,
     account: &Addr,
     tx_history_page: Option<u32>,
     tx_history_page_size: Option<u32>,
+    permitted_token_ids: Option<&BTreeSet<String>>,
 ) -> StdResult<Binary> {
     let address = deps.api.addr_canonicalize(account.as_str())?;
     let (txs, _total) = get_txs(
@@ -339,6 +743,7 @@ fn query_all_balances(
     let token_ids = txs
         .into_iter()
         .map(|tx| tx.token_id)
+        .filter(|token_id| permitted_token_ids.map_or(true, |ids| ids.contains(token_id)))
         .collect::<BTreeSet<_>>();
 
     // get balances for this list of token_ids, only if balance == Some(_), ie: user has had some balance before
@@ -11,11 +11,12 @@ use crate::{msg::space_pad, state::RESPONSE_BLOCK_SIZE};
 #[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
 #[serde(rename_all = "snake_case")]
 pub struct Snip1155ReceiveMsg {
-    /// the address that sent the `Send` or `BatchSend` message
+    /// the address that sent the `Send` or `BatchSend` message, or the minter for mint callbacks
     pub sender: Addr,
     /// unique token_id `String`
     pub token_id: String,
-    /// the previous owner of the tokens being transferred
+    /// the previous owner of the tokens being transferred. For mint callbacks (see
+    /// `notify_receivers` in `MintTokens` and `CurateTokenIds`) this is the minter
     pub from: Addr,
     /// amount of tokens being transferred
     pub amount: Uint256,
@@ -0,0 +1,42 @@
+//! Messages sent to SNIP-20 contracts that are accepted as payment for sales. See
+//! [SetSale](crate::msg::ExecuteMsg::SetSale) and [Receive](crate::msg::ExecuteMsg::Receive)
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use cosmwasm_std::{to_binary, Addr, CosmosMsg, StdResult, Uint128, WasmMsg};
+
+use crate::{msg::space_pad, state::RESPONSE_BLOCK_SIZE};
+
+/// the subset of SNIP-20 handle messages that this contract sends
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+#[serde(rename_all = "snake_case")]
+pub enum Snip20HandleMsg {
+    /// registers this contract's code hash, so the SNIP-20 sends `Receive` callbacks when
+    /// tokens are sent to this contract
+    RegisterReceive {
+        code_hash: String,
+        padding: Option<String>,
+    },
+    Transfer {
+        recipient: Addr,
+        amount: Uint128,
+        memo: Option<String>,
+        padding: Option<String>,
+    },
+}
+
+impl Snip20HandleMsg {
+    /// creates a cosmos_msg sending this message, padded to 256 bytes, to the named SNIP-20
+    pub fn into_cosmos_msg(self, code_hash: String, contract_addr: Addr) -> StdResult<CosmosMsg> {
+        let mut msg = to_binary(&self)?;
+        space_pad(RESPONSE_BLOCK_SIZE, &mut msg.0);
+        let execute = WasmMsg::Execute {
+            msg,
+            code_hash,
+            contract_addr: contract_addr.to_string(),
+            funds: vec![],
+        };
+        Ok(execute.into())
+    }
+}
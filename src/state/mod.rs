@@ -7,6 +7,8 @@ pub mod txhistory;
 
 use cosmwasm_std::{to_binary, Addr, BlockInfo, StdError, StdResult, Storage, Uint256};
 
+use secret_toolkit::storage::AppendStore;
+
 use cosmwasm_storage::{
     bucket, bucket_read, singleton, singleton_read, Bucket, PrefixedStorage, ReadonlyBucket,
     ReadonlyPrefixedStorage, ReadonlySingleton, Singleton,
@@ -15,7 +17,11 @@ use cosmwasm_storage::{
 use self::{
     expiration::Expiration,
     permissions::Permission,
-    state_structs::{ContractConfig, StoredTokenInfo},
+    save_load_functions::{json_may_load, json_save},
+    state_structs::{
+        AirdropRound, ContractConfig, EditionSeries, Escrow, NftUser, QueuedAdminAction,
+        ReceiverRegistration, Sale, StoredTokenInfo, SwapOffer, TransferPolicy, VestingSchedule,
+    },
 };
 
 pub const RESPONSE_BLOCK_SIZE: usize = 256;
@@ -27,6 +33,8 @@ pub const BALANCES: &[u8] = b"balances";
 pub const TKN_INFO: &[u8] = b"tokeninfo";
 /// storage key for the BlockInfo when the last handle was executed
 pub const BLOCK_KEY: &[u8] = b"blockinfo";
+/// storage key for the queue of time-locked admin actions
+pub const ADMIN_ACTIONS_KEY: &[u8] = b"adminactions";
 
 /// prefix for storage of transactions
 pub const PREFIX_TXS: &[u8] = b"preftxs";
@@ -40,51 +48,183 @@ pub const PREFIX_PERMISSIONS: &[u8] = b"permissions";
 pub const PREFIX_PERMISSION_ID: &[u8] = b"permid";
 pub const PREFIX_REVOKED_PERMITS: &str = "revokedperms";
 pub const PREFIX_RECEIVERS: &[u8] = b"s1155receivers";
+/// prefix for storing SNIP-52 notification counters
+pub const PREFIX_NOTIFICATION_COUNTERS: &[u8] = b"notifcounters";
+/// prefix for storing the nonces used by each owner's transfer permits
+pub const PREFIX_TRANSFER_NONCES: &[u8] = b"transfernonces";
+/// prefix for storing open swap offers
+pub const PREFIX_SWAP_OFFERS: &[u8] = b"swapoffers";
+/// prefix for storing unclaimed escrows
+pub const PREFIX_ESCROWS: &[u8] = b"escrows";
+/// prefix for storing vesting schedules
+pub const PREFIX_VESTING: &[u8] = b"vesting";
+/// prefix for storing airdrop rounds
+pub const PREFIX_AIRDROPS: &[u8] = b"airdrops";
+/// prefix for storing claimed airdrops
+pub const PREFIX_AIRDROP_CLAIMS: &[u8] = b"airdropclaims";
+/// prefix for storing sales
+pub const PREFIX_SALES: &[u8] = b"sales";
+/// prefix for storing the amount each address has purchased from a sale
+pub const PREFIX_SALE_PURCHASES: &[u8] = b"salepurchases";
+/// prefix for storing the users of NFTs
+pub const PREFIX_NFT_USERS: &[u8] = b"nftusers";
+/// prefix for storing edition series
+pub const PREFIX_EDITION_SERIES: &[u8] = b"editionseries";
+/// prefix for the token_ids curated by each curator
+pub const PREFIX_CURATOR_TOKEN_IDS: &[u8] = b"curatortokenids";
+/// prefix for storing curator quotas
+pub const PREFIX_CURATOR_QUOTAS: &[u8] = b"curatorquotas";
+/// prefix for storing the addresses whose balances of a token_id are frozen
+pub const PREFIX_FROZEN_ACCOUNTS: &[u8] = b"frozenaccounts";
+/// prefix for storing the transfer policy of each token_id
+pub const PREFIX_TRANSFER_POLICIES: &[u8] = b"transferpolicies";
+/// prefix for storing the allowlist of each token_id
+pub const PREFIX_TRANSFER_ALLOWLISTS: &[u8] = b"transferallowlists";
+/// prefix for storing the transfers approved by curators
+pub const PREFIX_TRANSFER_APPROVALS: &[u8] = b"transferapprovals";
+
+pub static CURATOR_TOKEN_ID_STORE: AppendStore<String> = AppendStore::new(PREFIX_CURATOR_TOKEN_IDS);
 
 /////////////////////////////////////////////////////////////////////////////////
 // Singletons
 /////////////////////////////////////////////////////////////////////////////////
 
 /// Contract configuration: stores information on this contract
+#[allow(mismatched_lifetime_syntaxes)]
 pub fn contr_conf_w(storage: &mut dyn Storage) -> Singleton<ContractConfig> {
     singleton(storage, CONTR_CONF)
 }
 /// Contract configuration: reads information on this contract
+#[allow(mismatched_lifetime_syntaxes)]
 pub fn contr_conf_r(storage: &dyn Storage) -> ReadonlySingleton<ContractConfig> {
     singleton_read(storage, CONTR_CONF)
 }
 
 /// Saves BlockInfo of latest tx. Should not be necessary after env becomes available to queries
+#[allow(mismatched_lifetime_syntaxes)]
 pub fn blockinfo_w(storage: &mut dyn Storage) -> Singleton<BlockInfo> {
     singleton(storage, BLOCK_KEY)
 }
 /// Reads BlockInfo of latest tx. Should not be necessary after env becomes available to queries
+#[allow(mismatched_lifetime_syntaxes)]
 pub fn blockinfo_r(storage: &dyn Storage) -> ReadonlySingleton<BlockInfo> {
     singleton_read(storage, BLOCK_KEY)
 }
 
+/// Queued admin actions: saves the admin actions waiting for their time lock to pass
+#[allow(mismatched_lifetime_syntaxes)]
+pub fn admin_actions_w(storage: &mut dyn Storage) -> Singleton<Vec<QueuedAdminAction>> {
+    singleton(storage, ADMIN_ACTIONS_KEY)
+}
+/// Queued admin actions: reads the admin actions waiting for their time lock to pass
+#[allow(mismatched_lifetime_syntaxes)]
+pub fn admin_actions_r(storage: &dyn Storage) -> ReadonlySingleton<Vec<QueuedAdminAction>> {
+    singleton_read(storage, ADMIN_ACTIONS_KEY)
+}
+
 /////////////////////////////////////////////////////////////////////////////////
 // Buckets
 /////////////////////////////////////////////////////////////////////////////////
 
 /// token_id configs. Key is `token_id.as_bytes()`
+#[allow(mismatched_lifetime_syntaxes)]
 pub fn tkn_info_w(storage: &mut dyn Storage) -> Bucket<StoredTokenInfo> {
     bucket(storage, TKN_INFO)
 }
 /// token_id configs. Key is `token_id.as_bytes()`
+#[allow(mismatched_lifetime_syntaxes)]
 pub fn tkn_info_r(storage: &dyn Storage) -> ReadonlyBucket<StoredTokenInfo> {
     bucket_read(storage, TKN_INFO)
 }
 
 /// total supply of a token_id. Key is `token_id.as_bytes()`
+#[allow(mismatched_lifetime_syntaxes)]
 pub fn tkn_tot_supply_w(storage: &mut dyn Storage) -> Bucket<Uint256> {
     bucket(storage, TKN_TOTAL_SUPPLY)
 }
 /// total supply of a token_id. Key is `token_id.as_bytes()`
+#[allow(mismatched_lifetime_syntaxes)]
 pub fn tkn_tot_supply_r(storage: &dyn Storage) -> ReadonlyBucket<Uint256> {
     bucket_read(storage, TKN_TOTAL_SUPPLY)
 }
 
+/// open swap offers. Key is `offer_id.to_be_bytes()`
+#[allow(mismatched_lifetime_syntaxes)]
+pub fn swap_offers_w(storage: &mut dyn Storage) -> Bucket<SwapOffer> {
+    bucket(storage, PREFIX_SWAP_OFFERS)
+}
+/// open swap offers. Key is `offer_id.to_be_bytes()`
+#[allow(mismatched_lifetime_syntaxes)]
+pub fn swap_offers_r(storage: &dyn Storage) -> ReadonlyBucket<SwapOffer> {
+    bucket_read(storage, PREFIX_SWAP_OFFERS)
+}
+
+/// unclaimed escrows. Key is `escrow_id.to_be_bytes()`
+#[allow(mismatched_lifetime_syntaxes)]
+pub fn escrows_w(storage: &mut dyn Storage) -> Bucket<Escrow> {
+    bucket(storage, PREFIX_ESCROWS)
+}
+/// unclaimed escrows. Key is `escrow_id.to_be_bytes()`
+#[allow(mismatched_lifetime_syntaxes)]
+pub fn escrows_r(storage: &dyn Storage) -> ReadonlyBucket<Escrow> {
+    bucket_read(storage, PREFIX_ESCROWS)
+}
+
+/// sales. Key is `token_id.as_bytes()`
+#[allow(mismatched_lifetime_syntaxes)]
+pub fn sales_w(storage: &mut dyn Storage) -> Bucket<Sale> {
+    bucket(storage, PREFIX_SALES)
+}
+/// sales. Key is `token_id.as_bytes()`
+#[allow(mismatched_lifetime_syntaxes)]
+pub fn sales_r(storage: &dyn Storage) -> ReadonlyBucket<Sale> {
+    bucket_read(storage, PREFIX_SALES)
+}
+
+/// users of NFTs. Key is `token_id.as_bytes()`
+#[allow(mismatched_lifetime_syntaxes)]
+pub fn nft_users_w(storage: &mut dyn Storage) -> Bucket<NftUser> {
+    bucket(storage, PREFIX_NFT_USERS)
+}
+/// users of NFTs. Key is `token_id.as_bytes()`
+#[allow(mismatched_lifetime_syntaxes)]
+pub fn nft_users_r(storage: &dyn Storage) -> ReadonlyBucket<NftUser> {
+    bucket_read(storage, PREFIX_NFT_USERS)
+}
+
+/// edition series. Key is `parent_token_id.as_bytes()`
+#[allow(mismatched_lifetime_syntaxes)]
+pub fn edition_series_w(storage: &mut dyn Storage) -> Bucket<EditionSeries> {
+    bucket(storage, PREFIX_EDITION_SERIES)
+}
+/// edition series. Key is `parent_token_id.as_bytes()`
+#[allow(mismatched_lifetime_syntaxes)]
+pub fn edition_series_r(storage: &dyn Storage) -> ReadonlyBucket<EditionSeries> {
+    bucket_read(storage, PREFIX_EDITION_SERIES)
+}
+
+/// maximum number of token_ids each curator can curate. Key is `to_binary(&Addr)?.as_slice()`
+#[allow(mismatched_lifetime_syntaxes)]
+pub fn curator_quotas_w(storage: &mut dyn Storage) -> Bucket<u64> {
+    bucket(storage, PREFIX_CURATOR_QUOTAS)
+}
+/// maximum number of token_ids each curator can curate. Key is `to_binary(&Addr)?.as_slice()`
+#[allow(mismatched_lifetime_syntaxes)]
+pub fn curator_quotas_r(storage: &dyn Storage) -> ReadonlyBucket<u64> {
+    bucket_read(storage, PREFIX_CURATOR_QUOTAS)
+}
+
+/// transfer policy of each token_id. Token_ids without one are `Open`
+#[allow(mismatched_lifetime_syntaxes)]
+pub fn transfer_policies_w(storage: &mut dyn Storage) -> Bucket<TransferPolicy> {
+    bucket(storage, PREFIX_TRANSFER_POLICIES)
+}
+/// transfer policy of each token_id. Token_ids without one are `Open`
+#[allow(mismatched_lifetime_syntaxes)]
+pub fn transfer_policies_r(storage: &dyn Storage) -> ReadonlyBucket<TransferPolicy> {
+    bucket_read(storage, PREFIX_TRANSFER_POLICIES)
+}
+
 /////////////////////////////////////////////////////////////////////////////////
 // Multi-level Buckets
 /////////////////////////////////////////////////////////////////////////////////
@@ -101,6 +241,141 @@ pub fn balances_r<'a>(storage: &'a dyn Storage, token_id: &str) -> ReadonlyBucke
     ReadonlyBucket::multilevel(storage, &[BALANCES, token_id.as_bytes()])
 }
 
+/// number of notifications emitted to each address on a SNIP-52 channel. Key is
+/// the canonical address of the notified address
+pub fn notification_counter_w<'a>(storage: &'a mut dyn Storage, channel: &str) -> Bucket<'a, u64> {
+    Bucket::multilevel(storage, &[PREFIX_NOTIFICATION_COUNTERS, channel.as_bytes()])
+}
+/// number of notifications emitted to each address on a SNIP-52 channel. Key is
+/// the canonical address of the notified address
+pub fn notification_counter_r<'a>(
+    storage: &'a dyn Storage,
+    channel: &str,
+) -> ReadonlyBucket<'a, u64> {
+    ReadonlyBucket::multilevel(storage, &[PREFIX_NOTIFICATION_COUNTERS, channel.as_bytes()])
+}
+
+/// nonces of transfer permits that an owner has used. Key is `nonce.to_be_bytes()`
+pub fn transfer_nonces_w<'a>(storage: &'a mut dyn Storage, owner: &Addr) -> Bucket<'a, bool> {
+    let owner_bin = to_binary(owner).unwrap();
+    Bucket::multilevel(storage, &[PREFIX_TRANSFER_NONCES, owner_bin.as_slice()])
+}
+/// nonces of transfer permits that an owner has used. Key is `nonce.to_be_bytes()`
+pub fn transfer_nonces_r<'a>(storage: &'a dyn Storage, owner: &Addr) -> ReadonlyBucket<'a, bool> {
+    let owner_bin = to_binary(owner).unwrap();
+    ReadonlyBucket::multilevel(storage, &[PREFIX_TRANSFER_NONCES, owner_bin.as_slice()])
+}
+
+/// vesting schedules of a beneficiary. Key is `token_id.as_bytes()`
+pub fn vesting_w<'a>(
+    storage: &'a mut dyn Storage,
+    beneficiary: &Addr,
+) -> Bucket<'a, VestingSchedule> {
+    let beneficiary_bin = to_binary(beneficiary).unwrap();
+    Bucket::multilevel(storage, &[PREFIX_VESTING, beneficiary_bin.as_slice()])
+}
+/// vesting schedules of a beneficiary. Key is `token_id.as_bytes()`
+pub fn vesting_r<'a>(
+    storage: &'a dyn Storage,
+    beneficiary: &Addr,
+) -> ReadonlyBucket<'a, VestingSchedule> {
+    let beneficiary_bin = to_binary(beneficiary).unwrap();
+    ReadonlyBucket::multilevel(storage, &[PREFIX_VESTING, beneficiary_bin.as_slice()])
+}
+
+/// airdrop rounds of a token_id. Key is `round.to_be_bytes()`
+pub fn airdrops_w<'a>(storage: &'a mut dyn Storage, token_id: &str) -> Bucket<'a, AirdropRound> {
+    Bucket::multilevel(storage, &[PREFIX_AIRDROPS, token_id.as_bytes()])
+}
+/// airdrop rounds of a token_id. Key is `round.to_be_bytes()`
+pub fn airdrops_r<'a>(
+    storage: &'a dyn Storage,
+    token_id: &str,
+) -> ReadonlyBucket<'a, AirdropRound> {
+    ReadonlyBucket::multilevel(storage, &[PREFIX_AIRDROPS, token_id.as_bytes()])
+}
+
+/// addresses that have claimed an airdrop round of a token_id. Key is `to_binary(&Addr)?.as_slice()`
+pub fn airdrop_claims_w<'a>(
+    storage: &'a mut dyn Storage,
+    token_id: &str,
+    round: u64,
+) -> Bucket<'a, bool> {
+    Bucket::multilevel(
+        storage,
+        &[
+            PREFIX_AIRDROP_CLAIMS,
+            token_id.as_bytes(),
+            &round.to_be_bytes(),
+        ],
+    )
+}
+/// addresses that have claimed an airdrop round of a token_id. Key is `to_binary(&Addr)?.as_slice()`
+pub fn airdrop_claims_r<'a>(
+    storage: &'a dyn Storage,
+    token_id: &str,
+    round: u64,
+) -> ReadonlyBucket<'a, bool> {
+    ReadonlyBucket::multilevel(
+        storage,
+        &[
+            PREFIX_AIRDROP_CLAIMS,
+            token_id.as_bytes(),
+            &round.to_be_bytes(),
+        ],
+    )
+}
+
+/// amount of a token_id each address has purchased from its sale. Key is `to_binary(&Addr)?.as_slice()`
+pub fn sale_purchases_w<'a>(storage: &'a mut dyn Storage, token_id: &str) -> Bucket<'a, Uint256> {
+    Bucket::multilevel(storage, &[PREFIX_SALE_PURCHASES, token_id.as_bytes()])
+}
+/// amount of a token_id each address has purchased from its sale. Key is `to_binary(&Addr)?.as_slice()`
+pub fn sale_purchases_r<'a>(
+    storage: &'a dyn Storage,
+    token_id: &str,
+) -> ReadonlyBucket<'a, Uint256> {
+    ReadonlyBucket::multilevel(storage, &[PREFIX_SALE_PURCHASES, token_id.as_bytes()])
+}
+
+/// addresses whose balances of a token_id are frozen. Key is `to_binary(&Addr)?.as_slice()`
+pub fn frozen_accounts_w<'a>(storage: &'a mut dyn Storage, token_id: &str) -> Bucket<'a, bool> {
+    Bucket::multilevel(storage, &[PREFIX_FROZEN_ACCOUNTS, token_id.as_bytes()])
+}
+/// addresses whose balances of a token_id are frozen. Key is `to_binary(&Addr)?.as_slice()`
+pub fn frozen_accounts_r<'a>(storage: &'a dyn Storage, token_id: &str) -> ReadonlyBucket<'a, bool> {
+    ReadonlyBucket::multilevel(storage, &[PREFIX_FROZEN_ACCOUNTS, token_id.as_bytes()])
+}
+
+/// addresses on a token_id's allowlist. Key is `to_binary(&Addr)?.as_slice()`
+pub fn transfer_allowlists_w<'a>(storage: &'a mut dyn Storage, token_id: &str) -> Bucket<'a, bool> {
+    Bucket::multilevel(storage, &[PREFIX_TRANSFER_ALLOWLISTS, token_id.as_bytes()])
+}
+/// addresses on a token_id's allowlist. Key is `to_binary(&Addr)?.as_slice()`
+pub fn transfer_allowlists_r<'a>(
+    storage: &'a dyn Storage,
+    token_id: &str,
+) -> ReadonlyBucket<'a, bool> {
+    ReadonlyBucket::multilevel(storage, &[PREFIX_TRANSFER_ALLOWLISTS, token_id.as_bytes()])
+}
+
+/// remaining amount of a token_id the curator has approved to move from one address to
+/// another. Key is `to_binary(&(from, recipient))?.as_slice()`
+pub fn transfer_approvals_w<'a>(
+    storage: &'a mut dyn Storage,
+    token_id: &str,
+) -> Bucket<'a, Uint256> {
+    Bucket::multilevel(storage, &[PREFIX_TRANSFER_APPROVALS, token_id.as_bytes()])
+}
+/// remaining amount of a token_id the curator has approved to move from one address to
+/// another. Key is `to_binary(&(from, recipient))?.as_slice()`
+pub fn transfer_approvals_r<'a>(
+    storage: &'a dyn Storage,
+    token_id: &str,
+) -> ReadonlyBucket<'a, Uint256> {
+    ReadonlyBucket::multilevel(storage, &[PREFIX_TRANSFER_APPROVALS, token_id.as_bytes()])
+}
+
 /// private functions.
 /// To store permission. key is to be [`owner`, `token_id`, `allowed_addr`]
 /// `allowed_addr` is `to_binary(&Addr)?.as_slice()`
@@ -154,19 +429,138 @@ pub fn perm_r<'a>(
     )
 }
 
+/////////////////////////////////////////////////////////////////////////////////
+// Curator index
+/////////////////////////////////////////////////////////////////////////////////
+
+/// records that `curator` curated `token_id`
+pub fn append_curator_token_id(
+    storage: &mut dyn Storage,
+    curator: &Addr,
+    token_id: &str,
+) -> StdResult<()> {
+    let curator_store = CURATOR_TOKEN_ID_STORE.add_suffix(to_binary(curator)?.as_slice());
+    curator_store.push(storage, &token_id.to_string())
+}
+
+/// returns the number of token_ids curated by `curator`
+pub fn curator_token_id_count(storage: &dyn Storage, curator: &Addr) -> StdResult<u64> {
+    let curator_store = CURATOR_TOKEN_ID_STORE.add_suffix(to_binary(curator)?.as_slice());
+    Ok(curator_store.get_len(storage)? as u64)
+}
+
+/// returns a page of the token_ids curated by `curator` in the order they were curated, and the
+/// total number of token_ids curated by `curator`
+pub fn list_curator_token_ids(
+    storage: &dyn Storage,
+    curator: &Addr,
+    page: u32,
+    page_size: u32,
+) -> StdResult<(Vec<String>, u64)> {
+    let curator_store = CURATOR_TOKEN_ID_STORE.add_suffix(to_binary(curator)?.as_slice());
+    let total = curator_store.get_len(storage)? as u64;
+    let token_ids: StdResult<Vec<String>> = curator_store
+        .iter(storage)?
+        .skip((page * page_size) as _)
+        .take(page_size as _)
+        .collect();
+
+    token_ids.map(|t| (t, total))
+}
+
+/////////////////////////////////////////////////////////////////////////////////
+// Transfer policies
+/////////////////////////////////////////////////////////////////////////////////
+
+/// returns why a token_id's transfer policy does not allow `amount` tokens to move from `from` to
+/// `recipient`, or `None` if it does. `from` == `None` for mints, and `recipient` == `None` for burns
+pub fn transfer_policy_violation(
+    storage: &dyn Storage,
+    token_id: &str,
+    from: Option<&Addr>,
+    recipient: Option<&Addr>,
+    amount: Uint256,
+) -> StdResult<Option<String>> {
+    let policy = transfer_policies_r(storage)
+        .may_load(token_id.as_bytes())?
+        .unwrap_or_default();
+    match policy {
+        TransferPolicy::Open => Ok(None),
+        TransferPolicy::Allowlist => {
+            let contract_address = contr_conf_r(storage).load()?.contract_address;
+            for address in [from, recipient].into_iter().flatten() {
+                if address != &contract_address
+                    && transfer_allowlists_r(storage, token_id)
+                        .may_load(to_binary(address)?.as_slice())?
+                        .is_none()
+                {
+                    return Ok(Some(format!(
+                        "{} is not on the allowlist of token_id {}",
+                        address, token_id
+                    )));
+                }
+            }
+            Ok(None)
+        }
+        TransferPolicy::CuratorApproval => match (from, recipient) {
+            (Some(from), Some(recipient)) => {
+                let contract_address = contr_conf_r(storage).load()?.contract_address;
+                if from == &contract_address || recipient == &contract_address {
+                    return Ok(None);
+                }
+                let approved = transfer_approvals_r(storage, token_id)
+                    .may_load(to_binary(&(from, recipient))?.as_slice())?
+                    .unwrap_or_default();
+                if approved < amount {
+                    return Ok(Some(format!(
+                        "the curator of token_id {} has not approved this transfer from {} to {}",
+                        token_id, from, recipient
+                    )));
+                }
+                Ok(None)
+            }
+            _ => Ok(None),
+        },
+    }
+}
+
 /////////////////////////////////////////////////////////////////////////////////
 // Receiver Interface
 /////////////////////////////////////////////////////////////////////////////////
 
-pub fn get_receiver_hash(store: &dyn Storage, account: &Addr) -> Option<StdResult<String>> {
+/// returns the code hash and callback filters registered by `account`, if any. Registrations
+/// stored before callback filters existed are a plain code hash String, and have no filters
+pub fn may_load_receiver(
+    store: &dyn Storage,
+    account: &Addr,
+) -> StdResult<Option<ReceiverRegistration>> {
     let store = ReadonlyPrefixedStorage::new(store, PREFIX_RECEIVERS);
-    store.get(account.as_str().as_bytes()).map(|data| {
-        String::from_utf8(data)
-            .map_err(|_err| StdError::invalid_utf8("stored code hash was not a valid String"))
-    })
+    match json_may_load(&store, account.as_str().as_bytes()) {
+        Ok(registration) => Ok(registration),
+        Err(_) => {
+            let data = store.get(account.as_str().as_bytes()).unwrap_or_default();
+            let code_hash = String::from_utf8(data).map_err(|_err| {
+                StdError::invalid_utf8("stored code hash was not a valid String")
+            })?;
+            Ok(Some(ReceiverRegistration {
+                code_hash,
+                token_ids: None,
+                reject_unlisted_token_ids: false,
+            }))
+        }
+    }
+}
+
+pub fn set_receiver(
+    store: &mut dyn Storage,
+    account: &Addr,
+    registration: &ReceiverRegistration,
+) -> StdResult<()> {
+    let mut store = PrefixedStorage::new(store, PREFIX_RECEIVERS);
+    json_save(&mut store, account.as_str().as_bytes(), registration)
 }
 
-pub fn set_receiver_hash(store: &mut dyn Storage, account: &Addr, code_hash: String) {
+pub fn remove_receiver(store: &mut dyn Storage, account: &Addr) {
     let mut store = PrefixedStorage::new(store, PREFIX_RECEIVERS);
-    store.set(account.as_str().as_bytes(), code_hash.as_bytes());
+    store.remove(account.as_str().as_bytes());
 }
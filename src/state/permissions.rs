@@ -158,6 +158,10 @@ pub struct Permission {
     pub view_pr_metadata_exp: Expiration,
     pub trfer_allowance_perm: Uint256,
     pub trfer_allowance_exp: Expiration,
+    #[serde(default)]
+    pub burn_allowance_perm: Uint256,
+    #[serde(default)]
+    pub burn_allowance_exp: Expiration,
 }
 
 impl Permission {
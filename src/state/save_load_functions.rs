@@ -52,21 +52,21 @@ pub fn json_load<T: DeserializeOwned>(storage: &dyn Storage, key: &[u8]) -> StdR
     )
 }
 
-// /// Returns StdResult<Option<T>> from retrieving the item with the specified key using Json
-// /// (de)serialization because bincode2 annoyingly uses a float op when deserializing an enum.
-// /// Returns Ok(None) if there is no item with that key
-// ///
-// /// # Arguments
-// ///
-// /// * `storage` - a reference to the storage this item is in
-// /// * `key` - a byte slice representing the key that accesses the stored item
-// pub fn json_may_load<T: DeserializeOwned, S: ReadonlyStorage>(
-//     storage: &S,
-//     key: &[u8],
-// ) -> StdResult<Option<T>> {
-//     match storage.get(key) {
-//         Some(value) => Json::deserialize(&value).map(Some),
-//         None => Ok(None),
-//     }
-// }
+/// Returns StdResult<Option<T>> from retrieving the item with the specified key using Json
+/// (de)serialization because bincode2 annoyingly uses a float op when deserializing an enum.
+/// Returns Ok(None) if there is no item with that key
+///
+/// # Arguments
+///
+/// * `storage` - a reference to the storage this item is in
+/// * `key` - a byte slice representing the key that accesses the stored item
+pub fn json_may_load<T: DeserializeOwned>(
+    storage: &dyn Storage,
+    key: &[u8],
+) -> StdResult<Option<T>> {
+    match storage.get(key) {
+        Some(value) => Json::deserialize(&value).map(Some),
+        None => Ok(None),
+    }
+}
 
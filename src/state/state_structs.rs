@@ -3,7 +3,7 @@ use super::*;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
-use cosmwasm_std::{Addr, Uint256};
+use cosmwasm_std::{Addr, Binary, Coin, Uint128, Uint256};
 
 use self::metadata::Metadata;
 
@@ -26,6 +26,158 @@ pub struct ContractConfig {
     pub tx_cnt: u64,
     pub prng_seed: Vec<u8>,
     pub contract_address: Addr,
+    /// number of swap offers created, used as the id of the next swap offer
+    #[serde(default)]
+    pub swap_offer_cnt: u64,
+    /// number of escrows created, used as the id of the next escrow
+    #[serde(default)]
+    pub escrow_cnt: u64,
+    /// SNIP-20 contracts that sales can accept as payment
+    #[serde(default)]
+    pub payment_tokens: Vec<PaymentToken>,
+    /// receives the proceeds of sales paid with SNIP-20 tokens
+    #[serde(default)]
+    pub treasury: Option<Addr>,
+    /// token_id prefixes reserved for specific curators
+    #[serde(default)]
+    pub namespaces: Vec<Namespace>,
+    /// roles granted by admins, in addition to `admin`, which holds every role
+    #[serde(default)]
+    pub roles: Vec<RoleHolder>,
+    #[serde(default)]
+    pub status: ContractStatus,
+    /// an admin proposed with `ProposeAdmin`, which becomes admin once it calls `AcceptAdmin`
+    #[serde(default)]
+    pub pending_admin: Option<AdminProposal>,
+    /// seconds between queuing a time-locked admin action and when it can be executed. If
+    /// non-zero, time-locked actions can only be performed through `QueueAdminAction`
+    #[serde(default)]
+    pub admin_action_delay: u64,
+    /// number of admin actions queued, used as the id of the next queued action
+    #[serde(default)]
+    pub admin_action_cnt: u64,
+}
+
+/// admin actions that are time-locked when the contract has an `admin_action_delay`
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum AdminAction {
+    AddCurators {
+        add_curators: Vec<Addr>,
+    },
+    RemoveCurators {
+        remove_curators: Vec<Addr>,
+    },
+    ChangeAdmin {
+        new_admin: Addr,
+    },
+    ProposeAdmin {
+        new_admin: Addr,
+        expiration: Option<Expiration>,
+    },
+    GrantRole {
+        role: Role,
+        address: Addr,
+    },
+    RevokeRole {
+        role: Role,
+        address: Addr,
+    },
+    SetContractStatus {
+        level: ContractStatus,
+    },
+}
+
+impl AdminAction {
+    /// the role required to queue, execute or cancel this action
+    pub fn role(&self) -> Role {
+        match self {
+            AdminAction::AddCurators { .. } | AdminAction::RemoveCurators { .. } => {
+                Role::CuratorManager
+            }
+            AdminAction::ChangeAdmin { .. }
+            | AdminAction::ProposeAdmin { .. }
+            | AdminAction::GrantRole { .. }
+            | AdminAction::RevokeRole { .. } => Role::Admin,
+            AdminAction::SetContractStatus { .. } => Role::Pauser,
+        }
+    }
+}
+
+/// an admin action waiting for its time lock to pass
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct QueuedAdminAction {
+    pub id: u64,
+    pub action: AdminAction,
+    pub queued_by: Addr,
+    /// block time, in seconds, from which the action can be executed
+    pub executable_at: u64,
+}
+
+/// a proposed admin, which can accept the proposal until `expiration`, if any
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct AdminProposal {
+    pub address: Addr,
+    pub expiration: Option<Expiration>,
+}
+
+/// permissions that admins can grant to other addresses. Holders of `Admin` can call every
+/// admin function, including granting and revoking roles, and hold every other role except
+/// `MetadataManager`
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum Role {
+    Admin,
+    /// can add and remove curators, set curator quotas and manage namespaces
+    CuratorManager,
+    /// can change the contract status
+    Pauser,
+    /// can change the metadata of any token_id whose token_id config lets minters change it
+    MetadataManager,
+}
+
+impl Role {
+    pub fn as_str(&self) -> &str {
+        match self {
+            Role::Admin => "admin",
+            Role::CuratorManager => "curator_manager",
+            Role::Pauser => "pauser",
+            Role::MetadataManager => "metadata_manager",
+        }
+    }
+}
+
+/// an address that has been granted a role
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct RoleHolder {
+    pub role: Role,
+    pub address: Addr,
+}
+
+/// `StopAll` rejects every message except `SetContractStatus`, `GrantRole`, `RevokeRole` and
+/// the messages that queue, execute and cancel admin actions
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ContractStatus {
+    #[default]
+    NormalRun,
+    StopAll,
+}
+
+/// a token_id prefix, eg: `gamestudio/`, registered by the admin. Only `owner` can curate
+/// token_ids that start with `prefix`, and a curator that owns namespaces can only curate
+/// token_ids within them
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Namespace {
+    pub prefix: String,
+    pub owner: Addr,
+}
+
+/// a SNIP-20 contract that sales can accept as payment
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct PaymentToken {
+    pub address: Addr,
+    pub code_hash: String,
 }
 
 /// message sent my instantiator and curators for a specific `token_id`'s token info
@@ -49,6 +201,9 @@ impl TokenInfoMsg {
             public_metadata: self.public_metadata.clone(),
             private_metadata: self.private_metadata.clone(),
             curator: curator.clone(),
+            fractionalized_as: None,
+            fraction_of: None,
+            metadata_frozen: false,
         }
     }
 }
@@ -63,6 +218,15 @@ pub struct StoredTokenInfo {
     pub public_metadata: Option<Metadata>,
     pub private_metadata: Option<Metadata>,
     pub curator: Addr,
+    /// for NFTs locked in the contract by `Fractionalize`, the fungible token_id of its shares
+    #[serde(default)]
+    pub fractionalized_as: Option<String>,
+    /// for shares created by `Fractionalize`, the token_id of the locked NFT
+    #[serde(default)]
+    pub fraction_of: Option<String>,
+    /// set permanently by `FreezeMetadata`, after which the metadata cannot be changed
+    #[serde(default)]
+    pub metadata_frozen: bool,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
@@ -79,6 +243,18 @@ pub enum TknConfig {
         enable_mint: bool,
         enable_burn: bool,
         minter_may_update_metadata: bool,
+        /// allows minters to burn tokens on behalf of owners, eg: to redeem tickets. Requires
+        /// `enable_burn` == true
+        #[serde(default)]
+        minter_may_burn: bool,
+        /// allows minters and the curator to freeze an address's balance, eg: pending a compliance
+        /// investigation. Frozen balances cannot be transferred, sent or burnt
+        #[serde(default)]
+        freezable: bool,
+        /// transfers are rejected until this expiration, eg: until the launch of an IDO
+        /// allocation. Does not apply to minting or burning
+        #[serde(default)]
+        transfers_locked_until: Option<Expiration>,
     },
     /// no `enable_mint` option because NFT can be minted only once using `CurateTokenIds`
     Nft {
@@ -90,6 +266,18 @@ pub enum TknConfig {
         enable_burn: bool,
         owner_may_update_metadata: bool,
         minter_may_update_metadata: bool,
+        /// allows minters to burn the NFT on behalf of its owner, eg: to redeem a ticket. Requires
+        /// `enable_burn` == true
+        #[serde(default)]
+        minter_may_burn: bool,
+        /// allows minters and the curator to freeze an address's balance, eg: pending a compliance
+        /// investigation. Frozen balances cannot be transferred, sent or burnt
+        #[serde(default)]
+        freezable: bool,
+        /// transfers are rejected until this expiration, eg: until the launch of an IDO
+        /// allocation. Does not apply to minting or burning
+        #[serde(default)]
+        transfers_locked_until: Option<Expiration>,
     },
 }
 
@@ -104,6 +292,9 @@ impl TknConfig {
                 enable_mint,
                 enable_burn,
                 minter_may_update_metadata,
+                minter_may_burn,
+                freezable,
+                transfers_locked_until,
             } => {
                 TknConfigFlat {
                     is_nft: false,
@@ -116,6 +307,9 @@ impl TknConfig {
                     minter_may_update_metadata: *minter_may_update_metadata,
                     // there can be multiple owners, so owners cannot update metadata
                     owner_may_update_metadata: false,
+                    minter_may_burn: *minter_may_burn,
+                    freezable: *freezable,
+                    transfers_locked_until: *transfers_locked_until,
                 }
             }
             TknConfig::Nft {
@@ -125,6 +319,9 @@ impl TknConfig {
                 enable_burn,
                 owner_may_update_metadata,
                 minter_may_update_metadata,
+                minter_may_burn,
+                freezable,
+                transfers_locked_until,
             } => {
                 TknConfigFlat {
                     is_nft: true,
@@ -138,6 +335,9 @@ impl TknConfig {
                     enable_burn: *enable_burn,
                     minter_may_update_metadata: *minter_may_update_metadata,
                     owner_may_update_metadata: *owner_may_update_metadata,
+                    minter_may_burn: *minter_may_burn,
+                    freezable: *freezable,
+                    transfers_locked_until: *transfers_locked_until,
                 }
             }
         }
@@ -153,6 +353,10 @@ impl TknConfig {
             enable_mint: true,
             enable_burn: true,
             minter_may_update_metadata: true,
+            // `false`, as it allows minters to burn tokens of other owners
+            minter_may_burn: false,
+            freezable: false,
+            transfers_locked_until: None,
         }
     }
 
@@ -165,6 +369,9 @@ impl TknConfig {
             enable_burn: true,
             owner_may_update_metadata: true,
             minter_may_update_metadata: true,
+            minter_may_burn: false,
+            freezable: false,
+            transfers_locked_until: None,
         }
     }
 }
@@ -181,6 +388,9 @@ pub struct TknConfigFlat {
     pub enable_burn: bool,
     pub minter_may_update_metadata: bool,
     pub owner_may_update_metadata: bool,
+    pub minter_may_burn: bool,
+    pub freezable: bool,
+    pub transfers_locked_until: Option<Expiration>,
 }
 
 impl TknConfigFlat {
@@ -193,6 +403,9 @@ impl TknConfigFlat {
                 enable_burn: self.enable_burn,
                 owner_may_update_metadata: self.owner_may_update_metadata,
                 minter_may_update_metadata: self.minter_may_update_metadata,
+                minter_may_burn: self.minter_may_burn,
+                freezable: self.freezable,
+                transfers_locked_until: self.transfers_locked_until,
             },
             false => TknConfig::Fungible {
                 minters: self.minters.clone(),
@@ -201,6 +414,9 @@ impl TknConfigFlat {
                 enable_mint: self.enable_mint,
                 enable_burn: self.enable_burn,
                 minter_may_update_metadata: self.minter_may_update_metadata,
+                minter_may_burn: self.minter_may_burn,
+                freezable: self.freezable,
+                transfers_locked_until: self.transfers_locked_until,
             },
         }
     }
@@ -266,3 +482,168 @@ pub struct OwnerBalance {
     pub token_id: String,
     pub amount: Uint256,
 }
+
+/// code hash and callback filters registered by a contract using `RegisterReceive`
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ReceiverRegistration {
+    pub code_hash: String,
+    /// if `Some`, `Snip1155Receive` callbacks are only sent for these token_ids
+    pub token_ids: Option<Vec<String>>,
+    /// if `true`, sending token_ids that are not in `token_ids` to this contract fails,
+    /// instead of transferring the tokens without a callback
+    pub reject_unlisted_token_ids: bool,
+}
+
+impl ReceiverRegistration {
+    /// returns true if callbacks should be sent for this `token_id`
+    pub fn is_listed(&self, token_id: &str) -> bool {
+        match &self.token_ids {
+            None => true,
+            Some(token_ids) => token_ids.iter().any(|id| id == token_id),
+        }
+    }
+}
+
+/// an offer to swap tokens, created using `CreateSwapOffer`. The offered tokens are held by the
+/// contract until the offer is accepted or cancelled
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct SwapOffer {
+    pub offer_id: u64,
+    pub maker: Addr,
+    pub offer_token_id: String,
+    pub offer_amount: Uint256,
+    /// token_id and amount the maker wants in exchange
+    pub ask_token_id: String,
+    pub ask_amount: Uint256,
+    /// if `Some`, only this address can accept the offer
+    pub counterparty: Option<Addr>,
+    /// the offer cannot be accepted after this expiration
+    pub expiration: Expiration,
+}
+
+/// tokens held by the contract for a beneficiary until `release`, created using `CreateEscrow`
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Escrow {
+    pub escrow_id: u64,
+    pub token_id: String,
+    pub depositor: Addr,
+    pub beneficiary: Addr,
+    pub amount: Uint256,
+    /// the beneficiary can claim the tokens after this expiration
+    pub release: Expiration,
+    /// if `true`, the depositor can take back the tokens before `release`
+    pub clawback: bool,
+}
+
+/// linear vesting schedule of a token_id for a beneficiary, created by a curator using
+/// `CreateVestingSchedule`. Times are in seconds since 01/01/1970
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct VestingSchedule {
+    pub token_id: String,
+    pub beneficiary: Addr,
+    /// the curator that funded the schedule
+    pub funder: Addr,
+    pub total: Uint256,
+    pub claimed: Uint256,
+    /// tokens vest linearly from `start` to `end`
+    pub start: u64,
+    /// no tokens can be claimed before `cliff`
+    pub cliff: u64,
+    pub end: u64,
+}
+
+impl VestingSchedule {
+    /// returns the total amount vested at time `now`, including tokens already claimed
+    pub fn vested(&self, now: u64) -> Uint256 {
+        if now < self.cliff {
+            Uint256::zero()
+        } else if now >= self.end {
+            self.total
+        } else {
+            self.total
+                .multiply_ratio(now - self.start, self.end - self.start)
+        }
+    }
+}
+
+/// Merkle root of an airdrop round of a token_id, registered by a curator with `RegisterAirdrop`
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct AirdropRound {
+    pub token_id: String,
+    pub round: u64,
+    pub merkle_root: Binary,
+    /// the curator that registered the round, recorded as the minter of claimed tokens
+    pub curator: Addr,
+}
+
+/// public sale of a token_id in a native denom, configured by the token_id's curator with `SetSale`
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Sale {
+    pub token_id: String,
+    /// the curator of the token_id, who can withdraw the proceeds
+    pub curator: Addr,
+    /// price of one token
+    pub price: Coin,
+    /// maximum number of tokens each address can purchase
+    pub per_address_limit: Option<Uint256>,
+    /// the sale opens once `start` has passed. `None` means the sale opens immediately
+    pub start: Option<Expiration>,
+    /// the sale closes once `end` has passed. `None` means the sale does not close
+    pub end: Option<Expiration>,
+    /// maximum number of tokens that can be sold
+    pub max_sold: Option<Uint256>,
+    pub sold: Uint256,
+    /// proceeds that have not been withdrawn by the curator, in `price.denom`
+    pub proceeds: Uint128,
+    /// prices of one token in accepted SNIP-20 tokens. Proceeds in SNIP-20 tokens are
+    /// forwarded to the contract's treasury
+    #[serde(default)]
+    pub snip20_prices: Vec<Snip20Price>,
+}
+
+/// price of one token in a SNIP-20 token, which must be one of the contract's `payment_tokens`
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Snip20Price {
+    /// address of the SNIP-20 contract
+    pub token: Addr,
+    pub amount: Uint128,
+}
+
+/// restricts which addresses a token_id can move between. Set by the token_id's curator with
+/// `SetTransferPolicy`
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum TransferPolicy {
+    /// tokens can move between any addresses
+    #[default]
+    Open,
+    /// the sender and recipient must both be on the token_id's allowlist, including when tokens
+    /// are minted or burnt. This contract is always allowed
+    Allowlist,
+    /// transfers between owners must be approved by the curator with `ApproveTransfer`. Mints
+    /// and burns do not need approval. Transfers to and from this contract do not need approval
+    /// either: escrows, vesting schedules and swap offers need approval for the addresses the
+    /// tokens will be released to when they are created, and NFTs cannot be fractionalized
+    CuratorApproval,
+}
+
+/// address that can use an NFT without owning it, set by the owner with `SetUser`. Cleared
+/// when the NFT is transferred or burnt
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct NftUser {
+    pub user: Addr,
+    pub expires: Expiration,
+}
+
+/// series of numbered NFT editions sharing the token info of a parent token_id, created by a
+/// curator with `CreateEditionSeries`. Editions are curated as `{parent_token_id}/{serial}`
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct EditionSeries {
+    /// token info inherited by each edition. `token_id` is the parent token_id
+    pub token_info: TokenInfoMsg,
+    pub max_editions: u64,
+    /// number of editions minted. Serials already taken by other token_ids are skipped, so the
+    /// serial of the latest edition can be higher than `minted`
+    pub minted: u64,
+    pub curator: Addr,
+}
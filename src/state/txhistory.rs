@@ -3,7 +3,7 @@ use super::*;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
-use cosmwasm_std::{Addr, Api, BlockInfo, CanonicalAddr, StdResult, Storage, Uint256};
+use cosmwasm_std::{Addr, Api, BlockInfo, CanonicalAddr, StdError, StdResult, Storage, Uint256};
 
 use cosmwasm_storage::{PrefixedStorage, ReadonlyPrefixedStorage};
 
@@ -189,6 +189,85 @@ pub fn store_burn(
     Ok(())
 }
 
+/// stores an escrow deposit, claim or clawback. The tx is added to the history of both the
+/// depositor and the beneficiary
+pub fn store_escrow(
+    storage: &mut dyn Storage,
+    config: &mut ContractConfig,
+    block: &BlockInfo,
+    token_id: &str,
+    action: StoredTxAction,
+    memo: Option<String>,
+) -> StdResult<()> {
+    let (depositor, beneficiary) = match &action {
+        StoredTxAction::EscrowDeposit {
+            depositor,
+            beneficiary,
+            ..
+        }
+        | StoredTxAction::EscrowClaim {
+            depositor,
+            beneficiary,
+            ..
+        }
+        | StoredTxAction::EscrowClawback {
+            depositor,
+            beneficiary,
+            ..
+        } => (depositor.clone(), beneficiary.clone()),
+        _ => return Err(StdError::generic_err("not an escrow tx")),
+    };
+    let tx = StoredTx {
+        tx_id: config.tx_cnt,
+        block_height: block.height,
+        block_time: block.time.seconds(),
+        token_id: token_id.to_string(),
+        action,
+        memo,
+    };
+    let mut tx_store = PrefixedStorage::new(storage, PREFIX_TXS);
+    json_save(&mut tx_store, &config.tx_cnt.to_le_bytes(), &tx)?;
+    append_tx_for_addr(storage, config.tx_cnt, &depositor)?;
+    if beneficiary != depositor {
+        append_tx_for_addr(storage, config.tx_cnt, &beneficiary)?;
+    }
+    config.tx_cnt += 1;
+    Ok(())
+}
+
+/// stores the freezing or unfreezing of an address's balance. The tx is added to the history of
+/// both the address and the minter or curator that froze it
+pub fn store_freeze(
+    storage: &mut dyn Storage,
+    config: &mut ContractConfig,
+    block: &BlockInfo,
+    token_id: &str,
+    address: CanonicalAddr,
+    frozen_by: CanonicalAddr,
+    frozen: bool,
+) -> StdResult<()> {
+    let tx = StoredTx {
+        tx_id: config.tx_cnt,
+        block_height: block.height,
+        block_time: block.time.seconds(),
+        token_id: token_id.to_string(),
+        action: StoredTxAction::Freeze {
+            address: address.clone(),
+            frozen_by: frozen_by.clone(),
+            frozen,
+        },
+        memo: None,
+    };
+    let mut tx_store = PrefixedStorage::new(storage, PREFIX_TXS);
+    json_save(&mut tx_store, &config.tx_cnt.to_le_bytes(), &tx)?;
+    append_tx_for_addr(storage, config.tx_cnt, &address)?;
+    if frozen_by != address {
+        append_tx_for_addr(storage, config.tx_cnt, &frozen_by)?;
+    }
+    config.tx_cnt += 1;
+    Ok(())
+}
+
 /// Returns StdResult<()> after saving tx id
 ///
 /// # Arguments
@@ -232,6 +311,35 @@ pub enum StoredTxAction {
         /// amount of tokens transferred
         amount: Uint256,
     },
+    /// tokens locked in escrow for a beneficiary
+    EscrowDeposit {
+        escrow_id: u64,
+        depositor: CanonicalAddr,
+        beneficiary: CanonicalAddr,
+        amount: Uint256,
+        /// the beneficiary can claim the tokens after this expiration
+        release: Expiration,
+    },
+    /// escrowed tokens claimed by the beneficiary
+    EscrowClaim {
+        escrow_id: u64,
+        depositor: CanonicalAddr,
+        beneficiary: CanonicalAddr,
+        amount: Uint256,
+    },
+    /// escrowed tokens returned to the depositor before release
+    EscrowClawback {
+        escrow_id: u64,
+        depositor: CanonicalAddr,
+        beneficiary: CanonicalAddr,
+        amount: Uint256,
+    },
+    /// an address's balance frozen (or unfrozen if `frozen` == false) by a minter or curator
+    Freeze {
+        address: CanonicalAddr,
+        frozen_by: CanonicalAddr,
+        frozen: bool,
+    },
 }
 
 /// tx in storage
@@ -298,6 +406,50 @@ impl StoredTx {
                     amount,
                 }
             }
+            StoredTxAction::EscrowDeposit {
+                escrow_id,
+                depositor,
+                beneficiary,
+                amount,
+                release,
+            } => TxAction::EscrowDeposit {
+                escrow_id,
+                depositor: api.addr_humanize(&depositor)?,
+                beneficiary: api.addr_humanize(&beneficiary)?,
+                amount,
+                release,
+            },
+            StoredTxAction::EscrowClaim {
+                escrow_id,
+                depositor,
+                beneficiary,
+                amount,
+            } => TxAction::EscrowClaim {
+                escrow_id,
+                depositor: api.addr_humanize(&depositor)?,
+                beneficiary: api.addr_humanize(&beneficiary)?,
+                amount,
+            },
+            StoredTxAction::EscrowClawback {
+                escrow_id,
+                depositor,
+                beneficiary,
+                amount,
+            } => TxAction::EscrowClawback {
+                escrow_id,
+                depositor: api.addr_humanize(&depositor)?,
+                beneficiary: api.addr_humanize(&beneficiary)?,
+                amount,
+            },
+            StoredTxAction::Freeze {
+                address,
+                frozen_by,
+                frozen,
+            } => TxAction::Freeze {
+                address: api.addr_humanize(&address)?,
+                frozen_by: api.addr_humanize(&frozen_by)?,
+                frozen,
+            },
         };
         let tx = Tx {
             tx_id: self.tx_id,
@@ -339,6 +491,35 @@ pub enum TxAction {
         /// amount of tokens transferred
         amount: Uint256,
     },
+    /// tokens locked in escrow for a beneficiary
+    EscrowDeposit {
+        escrow_id: u64,
+        depositor: Addr,
+        beneficiary: Addr,
+        amount: Uint256,
+        /// the beneficiary can claim the tokens after this expiration
+        release: Expiration,
+    },
+    /// escrowed tokens claimed by the beneficiary
+    EscrowClaim {
+        escrow_id: u64,
+        depositor: Addr,
+        beneficiary: Addr,
+        amount: Uint256,
+    },
+    /// escrowed tokens returned to the depositor before release
+    EscrowClawback {
+        escrow_id: u64,
+        depositor: Addr,
+        beneficiary: Addr,
+        amount: Uint256,
+    },
+    /// an address's balance frozen (or unfrozen if `frozen` == false) by a minter or curator
+    Freeze {
+        address: Addr,
+        frozen_by: Addr,
+        frozen: bool,
+    },
 }
 
 /// tx in storage
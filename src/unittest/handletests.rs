@@ -5,11 +5,16 @@ use super::super::{
     msg::*,
     queries::*,
     receiver::{ReceiverHandleMsg, Snip1155ReceiveMsg},
+    snip20::Snip20HandleMsg,
     state::*,
     state::{expiration::*, metadata::*, permissions::*, state_structs::*},
 };
 
-use cosmwasm_std::{from_binary, testing::*, to_binary, Addr, Response, StdResult, Uint256};
+use cosmwasm_std::{
+    coin, coins, from_binary, testing::*, to_binary, Addr, BankMsg, Binary, CosmosMsg, Response,
+    StdResult, Storage, Timestamp, Uint128, Uint256, Uint64,
+};
+use cosmwasm_storage::PrefixedStorage;
 use secret_toolkit::{crypto::sha_256, permit::RevokedPermits};
 
 /////////////////////////////////////////////////////////////////////////////////
@@ -144,6 +149,7 @@ fn test_curate_token_id() -> StdResult<()> {
     let mut msg = ExecuteMsg::CurateTokenIds {
         initial_tokens: vec![curate],
         memo: None,
+        notify_receivers: None,
         padding: None,
     };
     let mut result = execute(deps.as_mut(), mock_env(), info.clone(), msg);
@@ -158,6 +164,7 @@ fn test_curate_token_id() -> StdResult<()> {
     msg = ExecuteMsg::CurateTokenIds {
         initial_tokens: vec![curate],
         memo: None,
+        notify_receivers: None,
         padding: None,
     };
     result = execute(deps.as_mut(), mock_env(), info.clone(), msg);
@@ -171,6 +178,7 @@ fn test_curate_token_id() -> StdResult<()> {
     msg = ExecuteMsg::CurateTokenIds {
         initial_tokens: vec![curate],
         memo: None,
+        notify_receivers: None,
         padding: None,
     };
     result = execute(deps.as_mut(), mock_env(), info, msg);
@@ -204,6 +212,7 @@ fn test_curate_token_id() -> StdResult<()> {
 }
 
 #[test]
+#[allow(unused_variables)]
 fn test_mint_tokens() -> StdResult<()> {
     // init addresses
     let addr = init_addrs();
@@ -227,6 +236,7 @@ fn test_mint_tokens() -> StdResult<()> {
     let msg = ExecuteMsg::MintTokens {
         mint_tokens: vec![mint_non_exist],
         memo: None,
+        notify_receivers: None,
         padding: None,
     };
     // curator not able to mint token if token_id is non-existent
@@ -251,6 +261,7 @@ fn test_mint_tokens() -> StdResult<()> {
     let msg = ExecuteMsg::MintTokens {
         mint_tokens: vec![mint],
         memo: None,
+        notify_receivers: None,
         padding: None,
     };
     execute(deps.as_mut(), mock_env(), info.clone(), msg.clone())?;
@@ -282,6 +293,7 @@ fn test_mint_tokens() -> StdResult<()> {
     let msg = ExecuteMsg::MintTokens {
         mint_tokens: vec![mint],
         memo: None,
+        notify_receivers: None,
         padding: None,
     };
     let result = execute(deps.as_mut(), mock_env(), info, msg);
@@ -409,6 +421,9 @@ fn test_change_metadata_nft() -> StdResult<()> {
         curators: vec![addr.b()],
         initial_tokens: vec![],
         entropy: "seedentropy".to_string(),
+        payment_tokens: vec![],
+        treasury: None,
+        admin_action_delay: None,
     };
     instantiate(deps.as_mut(), mock_env(), info.clone(), init_msg)?;
 
@@ -449,6 +464,7 @@ fn test_change_metadata_nft() -> StdResult<()> {
     let msg_curate = ExecuteMsg::CurateTokenIds {
         initial_tokens: vec![curate0, curate1, curate2],
         memo: None,
+        notify_receivers: None,
         padding: None,
     };
     info.sender = addr.b();
@@ -547,6 +563,8 @@ fn test_change_metadata_nft() -> StdResult<()> {
         recipient: addr.d(),
         amount: Uint256::from(1u128),
         memo: None,
+        decoys: None,
+        entropy: None,
         padding: None,
     };
     info.sender = addr.c();
@@ -657,6 +675,9 @@ fn test_change_metadata_fungible() -> StdResult<()> {
         curators: vec![addr.b()],
         initial_tokens: vec![],
         entropy: "seedentropy".to_string(),
+        payment_tokens: vec![],
+        treasury: None,
+        admin_action_delay: None,
     };
     instantiate(deps.as_mut(), mock_env(), info.clone(), init_msg)?;
 
@@ -687,6 +708,7 @@ fn test_change_metadata_fungible() -> StdResult<()> {
     let msg_curate = ExecuteMsg::CurateTokenIds {
         initial_tokens: vec![curate0, curate1],
         memo: None,
+        notify_receivers: None,
         padding: None,
     };
     info.sender = addr.b();
@@ -839,6 +861,8 @@ fn test_transfer() -> StdResult<()> {
         recipient: addr1.clone(),
         amount: Uint256::from(800u128),
         memo: None,
+        decoys: None,
+        entropy: None,
         padding: None,
     };
     execute(deps.as_mut(), mock_env(), info.clone(), msg.clone())?;
@@ -865,6 +889,8 @@ fn test_transfer() -> StdResult<()> {
         recipient: addr1.clone(),
         amount: Uint256::from(0u128),
         memo: None,
+        decoys: None,
+        entropy: None,
         padding: None,
     };
     let result = execute(deps.as_mut(), mock_env(), info.clone(), msg);
@@ -877,6 +903,8 @@ fn test_transfer() -> StdResult<()> {
         recipient: addr1.clone(),
         amount: Uint256::from(1u128),
         memo: None,
+        decoys: None,
+        entropy: None,
         padding: None,
     };
     execute(deps.as_mut(), mock_env(), info, msg)?;
@@ -928,6 +956,8 @@ fn test_send() -> StdResult<()> {
         amount: Uint256::from(800u128),
         msg: Some(to_binary(&"msg_str")?),
         memo: None,
+        decoys: None,
+        entropy: None,
         padding: None,
     };
     let response = execute(deps.as_mut(), mock_env(), info, msg)?;
@@ -984,6 +1014,7 @@ fn test_batch_transfer_and_send_sanity() -> StdResult<()> {
                 recipient: addr.b(),
                 amount: Uint256::from(10u128),
                 memo: None,
+                decoys: None,
             },
             TransferAction {
                 token_id: "0a".to_string(),
@@ -991,8 +1022,10 @@ fn test_batch_transfer_and_send_sanity() -> StdResult<()> {
                 recipient: addr.c(),
                 amount: Uint256::from(20u128),
                 memo: None,
+                decoys: None,
             },
         ],
+        entropy: None,
         padding: None,
     };
     execute(deps.as_mut(), mock_env(), info.clone(), msg_batch_trans)?;
@@ -1017,6 +1050,7 @@ fn test_batch_transfer_and_send_sanity() -> StdResult<()> {
                 amount: Uint256::from(20u128),
                 msg: Some(to_binary(&"test message to b")?),
                 memo: None,
+                decoys: None,
             },
             SendAction {
                 token_id: "0a".to_string(),
@@ -1026,8 +1060,10 @@ fn test_batch_transfer_and_send_sanity() -> StdResult<()> {
                 amount: Uint256::from(30u128),
                 msg: Some(to_binary(&"test message to c")?),
                 memo: None,
+                decoys: None,
             },
         ],
+        entropy: None,
         padding: None,
     };
     let response = execute(deps.as_mut(), mock_env(), info, msg_batch_send)?;
@@ -1096,6 +1132,7 @@ fn test_batch_transfer_and_send_errors() -> StdResult<()> {
                 recipient: addr.b(),
                 amount: Uint256::from(10u128),
                 memo: None,
+                decoys: None,
             },
             TransferAction {
                 token_id: "0a".to_string(),
@@ -1103,8 +1140,10 @@ fn test_batch_transfer_and_send_errors() -> StdResult<()> {
                 recipient: addr.c(),
                 amount: Uint256::from(20u128),
                 memo: None,
+                decoys: None,
             },
         ],
+        entropy: None,
         padding: None,
     };
     let info = mock_info("addr0", &[]);
@@ -1145,6 +1184,8 @@ fn test_transfer_permissions_fungible() -> StdResult<()> {
         recipient: addr1.clone(),
         amount: Uint256::from(10u128),
         memo: None,
+        decoys: None,
+        entropy: None,
         padding: None,
     };
     let mut result = execute(deps.as_mut(), mock_env(), info.clone(), msg_trnsf_0.clone());
@@ -1162,6 +1203,8 @@ fn test_transfer_permissions_fungible() -> StdResult<()> {
         view_private_metadata_expiry: None,
         transfer: Some(Uint256::from(11u128)),
         transfer_expiry: None,
+        burn: None,
+        burn_expiry: None,
         padding: None,
     };
     execute(deps.as_mut(), mock_env(), info.clone(), msg0_perm_1)?;
@@ -1175,6 +1218,8 @@ fn test_transfer_permissions_fungible() -> StdResult<()> {
         view_private_metadata_expiry: None,
         transfer: Some(Uint256::from(9u128)),
         transfer_expiry: None,
+        burn: None,
+        burn_expiry: None,
         padding: None,
     };
     execute(deps.as_mut(), mock_env(), info.clone(), msg0_perm_1)?;
@@ -1194,6 +1239,8 @@ fn test_transfer_permissions_fungible() -> StdResult<()> {
         view_private_metadata_expiry: None,
         transfer: Some(Uint256::from(15u128)),
         transfer_expiry: None,
+        burn: None,
+        burn_expiry: None,
         padding: None,
     };
     execute(deps.as_mut(), mock_env(), info.clone(), msg0_perm_2)?;
@@ -1213,6 +1260,8 @@ fn test_transfer_permissions_fungible() -> StdResult<()> {
         view_private_metadata_expiry: None,
         transfer: Some(Uint256::from(10u128)),
         transfer_expiry: None,
+        burn: None,
+        burn_expiry: None,
         padding: None,
     };
     execute(deps.as_mut(), mock_env(), info.clone(), msg1_perm_1)?;
@@ -1301,6 +1350,8 @@ fn test_transfer_permissions_nft() -> StdResult<()> {
         recipient: addr0.clone(),
         amount: Uint256::from(1u128),
         memo: None,
+        decoys: None,
+        entropy: None,
         padding: None,
     };
     let mut result = execute(
@@ -1328,6 +1379,8 @@ fn test_transfer_permissions_nft() -> StdResult<()> {
         view_private_metadata_expiry: None,
         transfer: Some(Uint256::from(10u128)),
         transfer_expiry: None,
+        burn: None,
+        burn_expiry: None,
         padding: None,
     };
     execute(deps.as_mut(), mock_env(), info.clone(), msg2_perm_1)?;
@@ -1373,6 +1426,8 @@ fn test_transfer_permissions_nft() -> StdResult<()> {
         view_private_metadata_expiry: None,
         transfer: Some(Uint256::from(1u128)),
         transfer_expiry: None,
+        burn: None,
+        burn_expiry: None,
         padding: None,
     };
     execute(deps.as_mut(), mock_env(), info.clone(), msg2_perm_1)?;
@@ -1386,6 +1441,8 @@ fn test_transfer_permissions_nft() -> StdResult<()> {
             view_pr_metadata_exp: Expiration::default(),
             trfer_allowance_perm: Uint256::from(1u128),
             trfer_allowance_exp: Expiration::default(),
+            burn_allowance_perm: Uint256::from(0u128),
+            burn_allowance_exp: Expiration::default(),
         }
     );
 
@@ -1397,6 +1454,8 @@ fn test_transfer_permissions_nft() -> StdResult<()> {
         recipient: addr0.clone(),
         amount: Uint256::from(1u128),
         memo: None,
+        decoys: None,
+        entropy: None,
         padding: None,
     };
     execute(deps.as_mut(), mock_env(), info.clone(), msg)?;
@@ -1418,6 +1477,8 @@ fn test_transfer_permissions_nft() -> StdResult<()> {
         recipient: addr1.clone(),
         amount: Uint256::from(1u128),
         memo: None,
+        decoys: None,
+        entropy: None,
         padding: None,
     };
     result = execute(deps.as_mut(), mock_env(), info, msg);
@@ -1450,6 +1511,8 @@ fn test_revoke_permission_sanity() -> StdResult<()> {
         view_private_metadata_expiry: None,
         transfer: Some(Uint256::from(10u128)),
         transfer_expiry: None,
+        burn: None,
+        burn_expiry: None,
         padding: None,
     };
     let mut info = mock_info("addr0", &[]);
@@ -1587,6 +1650,7 @@ fn test_add_remove_curators() -> StdResult<()> {
     let msg_curate = ExecuteMsg::CurateTokenIds {
         initial_tokens: vec![curate0],
         memo: None,
+        notify_receivers: None,
         padding: None,
     };
     let mut result = execute(deps.as_mut(), mock_env(), info.clone(), msg_curate.clone());
@@ -1684,6 +1748,7 @@ fn test_add_remove_curators() -> StdResult<()> {
     let msg_curate_1 = ExecuteMsg::CurateTokenIds {
         initial_tokens: vec![curate1],
         memo: None,
+        notify_receivers: None,
         padding: None,
     };
     info.sender = addr.b();
@@ -2059,6 +2124,7 @@ fn test_remove_admin() -> StdResult<()> {
             admin,
             curators,
             all_token_ids,
+            ..
         } => {
             assert_eq!(admin, Some(addr.a()));
             assert_eq!(curators, vec![addr.a()]);
@@ -2146,6 +2212,7 @@ fn test_remove_admin() -> StdResult<()> {
             admin,
             curators,
             all_token_ids,
+            ..
         } => {
             assert_eq!(admin, None);
             assert_eq!(curators, vec![addr.a(), addr.b()]);
@@ -2172,6 +2239,9 @@ fn test_instantiate_admin_inputs() -> StdResult<()> {
         curators: vec![],
         initial_tokens: vec![],
         entropy: "seedentropy".to_string(),
+        payment_tokens: vec![],
+        treasury: None,
+        admin_action_delay: None,
     };
 
     instantiate(deps.as_mut(), mock_env(), info.clone(), init_msg)?;
@@ -2186,6 +2256,9 @@ fn test_instantiate_admin_inputs() -> StdResult<()> {
         curators: vec![],
         initial_tokens: vec![],
         entropy: "seedentropy".to_string(),
+        payment_tokens: vec![],
+        treasury: None,
+        admin_action_delay: None,
     };
 
     instantiate(deps.as_mut(), mock_env(), info.clone(), init_msg)?;
@@ -2200,6 +2273,9 @@ fn test_instantiate_admin_inputs() -> StdResult<()> {
         curators: vec![],
         initial_tokens: vec![],
         entropy: "seedentropy".to_string(),
+        payment_tokens: vec![],
+        treasury: None,
+        admin_action_delay: None,
     };
 
     info.sender = addr.a();
@@ -2215,6 +2291,9 @@ fn test_instantiate_admin_inputs() -> StdResult<()> {
         curators: vec![],
         initial_tokens: vec![],
         entropy: "seedentropy".to_string(),
+        payment_tokens: vec![],
+        treasury: None,
+        admin_action_delay: None,
     };
 
     info.sender = addr.a();
@@ -2242,6 +2321,8 @@ fn test_receiver_sanity() -> StdResult<()> {
         amount: Uint256::from(800u128),
         msg: Some(to_binary(&"msg_str")?),
         memo: Some("some memo".to_string()),
+        decoys: None,
+        entropy: None,
         padding: None,
     };
     let response = execute(deps.as_mut(), mock_env(), info, msg)?;
@@ -2263,3 +2344,3062 @@ fn test_receiver_sanity() -> StdResult<()> {
 
     Ok(())
 }
+
+#[test]
+fn test_mint_receiver_callbacks() -> StdResult<()> {
+    // init addresses
+    let addr = init_addrs();
+
+    // instantiate
+    let (_init_result, mut deps) = init_helper_default();
+
+    // addr.b registers receive
+    let mut info = mock_info(addr.b().as_str(), &[]);
+    let msg = ExecuteMsg::RegisterReceive {
+        code_hash: addr.b_hash(),
+        token_ids: None,
+        reject_unlisted_token_ids: None,
+        padding: None,
+    };
+    execute(deps.as_mut(), mock_env(), info.clone(), msg)?;
+
+    // mint without `notify_receivers` => no callback
+    info.sender = addr.a();
+    let mint = TokenAmount {
+        token_id: "0".to_string(),
+        balances: vec![
+            TokenIdBalance {
+                address: addr.b(),
+                amount: Uint256::from(10u128),
+            },
+            TokenIdBalance {
+                address: addr.c(),
+                amount: Uint256::from(10u128),
+            },
+        ],
+    };
+    let msg = ExecuteMsg::MintTokens {
+        mint_tokens: vec![mint.clone()],
+        memo: None,
+        notify_receivers: None,
+        padding: None,
+    };
+    let response = execute(deps.as_mut(), mock_env(), info.clone(), msg)?;
+    assert!(response.messages.is_empty());
+
+    // mint with `notify_receivers` => callback only to registered addr.b
+    let msg = ExecuteMsg::MintTokens {
+        mint_tokens: vec![mint],
+        memo: Some("mint memo".to_string()),
+        notify_receivers: Some(true),
+        padding: None,
+    };
+    let response = execute(deps.as_mut(), mock_env(), info.clone(), msg)?;
+    assert_eq!(response.messages.len(), 1);
+    let (receiver_msg, receiver_addr, receiver_hash) =
+        extract_cosmos_msg::<ReceiverHandleMsg>(&response.messages[0].msg)?;
+    assert_eq!(receiver_addr, Some(addr.b()));
+    assert_eq!(receiver_hash, &addr.b_hash());
+    let exp_receive_msg = Snip1155ReceiveMsg {
+        sender: addr.a(),
+        token_id: "0".to_string(),
+        from: addr.a(),
+        amount: Uint256::from(10u128),
+        memo: Some("mint memo".to_string()),
+        msg: None,
+    };
+    match receiver_msg {
+        ReceiverHandleMsg::Snip1155Receive(i) => assert_eq!(i, exp_receive_msg),
+    }
+    assert_eq!(
+        chk_bal(&deps.storage, "0", &addr.b()).unwrap(),
+        Uint256::from(20u128)
+    );
+
+    // curate with `notify_receivers` => callback to registered addr.b
+    let mut curate = CurateTokenId::default();
+    curate.token_info.token_id = "test0".to_string();
+    curate.balances[0].address = addr.b();
+    let msg = ExecuteMsg::CurateTokenIds {
+        initial_tokens: vec![curate],
+        memo: None,
+        notify_receivers: Some(true),
+        padding: None,
+    };
+    let response = execute(deps.as_mut(), mock_env(), info, msg)?;
+    assert_eq!(response.messages.len(), 1);
+    let (receiver_msg, receiver_addr, _) =
+        extract_cosmos_msg::<ReceiverHandleMsg>(&response.messages[0].msg)?;
+    assert_eq!(receiver_addr, Some(addr.b()));
+    match receiver_msg {
+        ReceiverHandleMsg::Snip1155Receive(i) => {
+            assert_eq!(i.token_id, "test0".to_string());
+            assert_eq!(i.from, addr.a());
+            assert_eq!(i.amount, Uint256::from(1000u128));
+        }
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_register_receive_filters() -> StdResult<()> {
+    // init addresses
+    let addr = init_addrs();
+
+    // instantiate
+    let (_init_result, mut deps) = init_helper_default();
+    let mut info = mock_info(addr.a().as_str(), &[]);
+    curate_addtl_default(&mut deps, mock_env(), info.clone())?;
+
+    // addr.b registers receive for token_id "0" only
+    info.sender = addr.b();
+    let msg = ExecuteMsg::RegisterReceive {
+        code_hash: addr.b_hash(),
+        token_ids: Some(vec!["0".to_string()]),
+        reject_unlisted_token_ids: None,
+        padding: None,
+    };
+    execute(deps.as_mut(), mock_env(), info.clone(), msg)?;
+
+    // send listed token_id "0" => callback
+    info.sender = addr.a();
+    let send = |token_id: &str| ExecuteMsg::Send {
+        token_id: token_id.to_string(),
+        from: addr.a(),
+        recipient: addr.b(),
+        recipient_code_hash: None,
+        amount: Uint256::from(10u128),
+        msg: None,
+        memo: None,
+        decoys: None,
+        entropy: None,
+        padding: None,
+    };
+    let response = execute(deps.as_mut(), mock_env(), info.clone(), send("0"))?;
+    assert_eq!(response.messages.len(), 1);
+
+    // send unlisted token_id "0a" => transferred without callback
+    let response = execute(deps.as_mut(), mock_env(), info.clone(), send("0a"))?;
+    assert!(response.messages.is_empty());
+    assert_eq!(
+        chk_bal(&deps.storage, "0a", &addr.b()).unwrap(),
+        Uint256::from(10u128)
+    );
+
+    // addr.b now rejects unlisted token_ids
+    info.sender = addr.b();
+    let msg = ExecuteMsg::RegisterReceive {
+        code_hash: addr.b_hash(),
+        token_ids: Some(vec!["0".to_string()]),
+        reject_unlisted_token_ids: Some(true),
+        padding: None,
+    };
+    execute(deps.as_mut(), mock_env(), info.clone(), msg)?;
+
+    info.sender = addr.a();
+    let result = execute(deps.as_mut(), mock_env(), info.clone(), send("0a"));
+    assert!(extract_error_msg(&result).contains("does not accept token_id 0a"));
+    assert_eq!(
+        chk_bal(&deps.storage, "0a", &addr.b()).unwrap(),
+        Uint256::from(10u128)
+    );
+
+    // unregister => no callbacks, and no filters applied
+    info.sender = addr.b();
+    let msg = ExecuteMsg::UnregisterReceive { padding: None };
+    execute(deps.as_mut(), mock_env(), info.clone(), msg)?;
+    assert_eq!(may_load_receiver(&deps.storage, &addr.b())?, None);
+
+    info.sender = addr.a();
+    let response = execute(deps.as_mut(), mock_env(), info.clone(), send("0"))?;
+    assert!(response.messages.is_empty());
+    let response = execute(deps.as_mut(), mock_env(), info.clone(), send("0a"))?;
+    assert!(response.messages.is_empty());
+    assert_eq!(
+        chk_bal(&deps.storage, "0a", &addr.b()).unwrap(),
+        Uint256::from(20u128)
+    );
+
+    // registrations stored as a plain code hash by earlier versions still receive callbacks
+    PrefixedStorage::new(&mut deps.storage, PREFIX_RECEIVERS)
+        .set(addr.b().as_bytes(), addr.b_hash().as_bytes());
+    assert_eq!(
+        may_load_receiver(&deps.storage, &addr.b())?,
+        Some(ReceiverRegistration {
+            code_hash: addr.b_hash(),
+            token_ids: None,
+            reject_unlisted_token_ids: false,
+        })
+    );
+    let response = execute(deps.as_mut(), mock_env(), info, send("0a"))?;
+    assert_eq!(response.messages.len(), 1);
+
+    Ok(())
+}
+
+#[test]
+fn test_burn_permissions() -> StdResult<()> {
+    // init addresses
+    let addr = init_addrs();
+
+    // instantiate
+    let (_init_result, mut deps) = init_helper_default();
+    let mut info = mock_info(addr.a().as_str(), &[]);
+    curate_addtl_default(&mut deps, mock_env(), info.clone())?;
+
+    // addr.c cannot burn addr.b's tokens without burn allowance
+    let burn = |amount: u128| ExecuteMsg::BurnTokens {
+        burn_tokens: vec![TokenAmount {
+            token_id: "1".to_string(),
+            balances: vec![TokenIdBalance {
+                address: addr.b(),
+                amount: Uint256::from(amount),
+            }],
+        }],
+        memo: None,
+        padding: None,
+    };
+    info.sender = addr.c();
+    let result = execute(deps.as_mut(), mock_env(), info.clone(), burn(10));
+    assert!(extract_error_msg(&result).contains("you do not have permission to burn "));
+
+    // transfer allowance does not allow burning
+    info.sender = addr.b();
+    let msg_perm = ExecuteMsg::GivePermission {
+        allowed_address: addr.c(),
+        token_id: "1".to_string(),
+        view_balance: None,
+        view_balance_expiry: None,
+        view_private_metadata: None,
+        view_private_metadata_expiry: None,
+        transfer: Some(Uint256::from(100u128)),
+        transfer_expiry: None,
+        burn: None,
+        burn_expiry: None,
+        padding: None,
+    };
+    execute(deps.as_mut(), mock_env(), info.clone(), msg_perm)?;
+    info.sender = addr.c();
+    let result = execute(deps.as_mut(), mock_env(), info.clone(), burn(10));
+    assert!(extract_error_msg(&result).contains("you do not have permission to burn "));
+
+    // addr.b gives addr.c burn allowance of 15
+    info.sender = addr.b();
+    let msg_perm = ExecuteMsg::GivePermission {
+        allowed_address: addr.c(),
+        token_id: "1".to_string(),
+        view_balance: None,
+        view_balance_expiry: None,
+        view_private_metadata: None,
+        view_private_metadata_expiry: None,
+        transfer: None,
+        transfer_expiry: None,
+        burn: Some(Uint256::from(15u128)),
+        burn_expiry: None,
+        padding: None,
+    };
+    execute(deps.as_mut(), mock_env(), info.clone(), msg_perm)?;
+
+    // addr.c can burn within allowance
+    info.sender = addr.c();
+    execute(deps.as_mut(), mock_env(), info.clone(), burn(10))?;
+    assert_eq!(
+        chk_bal(&deps.storage, "1", &addr.b()).unwrap(),
+        Uint256::from(490u128)
+    );
+    let addr_c_bin = &to_binary(&addr.c())?;
+    let perm = perm_r(&deps.storage, &addr.b(), "1").load(addr_c_bin.as_slice())?;
+    assert_eq!(perm.burn_allowance_perm, Uint256::from(5u128));
+    assert_eq!(perm.trfer_allowance_perm, Uint256::from(100u128));
+
+    // addr.c cannot exceed allowance
+    let result = execute(deps.as_mut(), mock_env(), info.clone(), burn(10));
+    assert!(extract_error_msg(&result).contains("Insufficient burn allowance: 5"));
+
+    // burner is recorded in tx history
+    let deps_ref = deps.as_ref();
+    let addr_b_canon = deps_ref.api.addr_canonicalize(addr.b().as_str())?;
+    let (txs, _) = txhistory::get_txs(deps_ref.api, deps_ref.storage, &addr_b_canon, 0, 1)?;
+    match &txs[0].action {
+        txhistory::TxAction::Burn {
+            burner,
+            owner,
+            amount,
+        } => {
+            assert_eq!(burner, &Some(addr.c()));
+            assert_eq!(owner, &addr.b());
+            assert_eq!(amount, &Uint256::from(10u128));
+        }
+        _ => panic!("expected burn tx"),
+    }
+
+    // minter addr.a cannot burn addr.b's tokens if `minter_may_burn` == false
+    info.sender = addr.a();
+    let result = execute(deps.as_mut(), mock_env(), info.clone(), burn(10));
+    assert!(extract_error_msg(&result).contains("you do not have permission to burn "));
+
+    // minter can burn on behalf of owner if `minter_may_burn` == true
+    let mut curate = CurateTokenId::default();
+    curate.token_info.token_id = "ticket".to_string();
+    curate.token_info.token_config = TknConfig::Nft {
+        minters: vec![addr.a()],
+        public_total_supply: true,
+        owner_is_public: true,
+        enable_burn: true,
+        owner_may_update_metadata: false,
+        minter_may_update_metadata: false,
+        minter_may_burn: true,
+        freezable: false,
+        transfers_locked_until: None,
+    };
+    curate.balances = vec![TokenIdBalance {
+        address: addr.b(),
+        amount: Uint256::from(1u128),
+    }];
+    let msg = ExecuteMsg::CurateTokenIds {
+        initial_tokens: vec![curate],
+        memo: None,
+        notify_receivers: None,
+        padding: None,
+    };
+    execute(deps.as_mut(), mock_env(), info.clone(), msg)?;
+    let msg = ExecuteMsg::BurnTokens {
+        burn_tokens: vec![TokenAmount {
+            token_id: "ticket".to_string(),
+            balances: vec![TokenIdBalance {
+                address: addr.b(),
+                amount: Uint256::from(1u128),
+            }],
+        }],
+        memo: None,
+        padding: None,
+    };
+    execute(deps.as_mut(), mock_env(), info.clone(), msg)?;
+    assert_eq!(
+        chk_bal(&deps.storage, "ticket", &addr.b()).unwrap(),
+        Uint256::from(0u128)
+    );
+
+    // ... but not tokens locked in the contract
+    let mut curate = CurateTokenId::default();
+    curate.token_info.token_id = "burnable".to_string();
+    curate.token_info.token_config = TknConfig::Fungible {
+        minters: vec![addr.a()],
+        decimals: 0,
+        public_total_supply: true,
+        enable_mint: true,
+        enable_burn: true,
+        minter_may_update_metadata: false,
+        minter_may_burn: true,
+        freezable: false,
+        transfers_locked_until: None,
+    };
+    curate.balances = vec![TokenIdBalance {
+        address: addr.b(),
+        amount: Uint256::from(10u128),
+    }];
+    let msg = ExecuteMsg::CurateTokenIds {
+        initial_tokens: vec![curate],
+        memo: None,
+        notify_receivers: None,
+        padding: None,
+    };
+    execute(deps.as_mut(), mock_env(), info.clone(), msg)?;
+    let msg = ExecuteMsg::CreateEscrow {
+        token_id: "burnable".to_string(),
+        amount: Uint256::from(5u128),
+        beneficiary: addr.c(),
+        release: Expiration::AtHeight(mock_env().block.height + 10),
+        clawback: None,
+        memo: None,
+        padding: None,
+    };
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(addr.b().as_str(), &[]),
+        msg,
+    )?;
+    let contract = mock_env().contract.address;
+    let msg = ExecuteMsg::BurnTokens {
+        burn_tokens: vec![TokenAmount {
+            token_id: "burnable".to_string(),
+            balances: vec![TokenIdBalance {
+                address: contract.clone(),
+                amount: Uint256::from(5u128),
+            }],
+        }],
+        memo: None,
+        padding: None,
+    };
+    let result = execute(deps.as_mut(), mock_env(), info, msg);
+    assert_eq!(
+        extract_error_msg(&result),
+        "tokens of token_id burnable held by the contract cannot be burnt"
+    );
+    assert_eq!(
+        chk_bal(&deps.storage, "burnable", &contract).unwrap(),
+        Uint256::from(5u128)
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_transfer_with_decoys() -> StdResult<()> {
+    // init addresses
+    let addr = init_addrs();
+
+    // instantiate
+    let (_init_result, mut deps) = init_helper_default();
+    let info = mock_info(addr.a().as_str(), &[]);
+    curate_addtl_default(&mut deps, mock_env(), info.clone())?;
+
+    // transfer with decoys addr.c (no balance) and addr.d (no balance)
+    let msg = ExecuteMsg::Transfer {
+        token_id: "0".to_string(),
+        from: addr.a(),
+        recipient: addr.b(),
+        amount: Uint256::from(100u128),
+        memo: None,
+        decoys: Some(vec![addr.c(), addr.d()]),
+        entropy: Some(to_binary(&"decoy entropy")?),
+        padding: None,
+    };
+    execute(deps.as_mut(), mock_env(), info.clone(), msg)?;
+    assert_eq!(
+        chk_bal(&deps.storage, "0", &addr.a()).unwrap(),
+        Uint256::from(900u128)
+    );
+    assert_eq!(
+        chk_bal(&deps.storage, "0", &addr.b()).unwrap(),
+        Uint256::from(100u128)
+    );
+    // decoy balances are written, but unchanged
+    assert_eq!(
+        chk_bal(&deps.storage, "0", &addr.c()),
+        Some(Uint256::from(0u128))
+    );
+    assert_eq!(
+        chk_bal(&deps.storage, "0", &addr.d()),
+        Some(Uint256::from(0u128))
+    );
+
+    // batch send with the recipient and sender also listed as decoys
+    let msg = ExecuteMsg::BatchSend {
+        actions: vec![SendAction {
+            token_id: "0".to_string(),
+            from: addr.a(),
+            recipient: addr.b(),
+            recipient_code_hash: None,
+            amount: Uint256::from(50u128),
+            msg: None,
+            memo: None,
+            decoys: Some(vec![addr.a(), addr.b(), addr.c()]),
+        }],
+        entropy: None,
+        padding: None,
+    };
+    execute(deps.as_mut(), mock_env(), info.clone(), msg)?;
+    assert_eq!(
+        chk_bal(&deps.storage, "0", &addr.a()).unwrap(),
+        Uint256::from(850u128)
+    );
+    assert_eq!(
+        chk_bal(&deps.storage, "0", &addr.b()).unwrap(),
+        Uint256::from(150u128)
+    );
+    assert_eq!(
+        chk_bal(&deps.storage, "0", &addr.c()),
+        Some(Uint256::from(0u128))
+    );
+
+    // decoys do not appear in tx history
+    let deps_ref = deps.as_ref();
+    let addr_d_canon = deps_ref.api.addr_canonicalize(addr.d().as_str())?;
+    let (_, total) = txhistory::get_txs(deps_ref.api, deps_ref.storage, &addr_d_canon, 0, 10)?;
+    assert_eq!(total, 0);
+
+    // decoys must be valid addresses
+    let msg = ExecuteMsg::Transfer {
+        token_id: "0".to_string(),
+        from: addr.a(),
+        recipient: addr.b(),
+        amount: Uint256::from(10u128),
+        memo: None,
+        decoys: Some(vec![addr.c(), Addr::unchecked("NotNormalized")]),
+        entropy: None,
+        padding: None,
+    };
+    let result = execute(deps.as_mut(), mock_env(), info, msg);
+    assert!(extract_error_msg(&result).contains("not normalized"));
+    assert_eq!(
+        chk_bal(&deps.storage, "0", &addr.a()).unwrap(),
+        Uint256::from(850u128)
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_transfer_with_permit() -> StdResult<()> {
+    // init addresses
+    let addr = init_addrs();
+
+    // instantiate
+    let (init_result, mut deps) = init_helper_default();
+    assert_eq!(init_result.unwrap(), Response::default());
+
+    let private_key = [9u8; 32];
+    let permit_transfer = PermitTransfer {
+        token_id: "0".to_string(),
+        recipient: addr.c(),
+        amount: Uint256::from(10u128),
+        memo: None,
+        nonce: Uint64::from(1u64),
+        expiration: Expiration::AtHeight(mock_env().block.height + 10),
+    };
+    let (permit, owner) = sign_permit(
+        &deps,
+        &private_key,
+        "transfer0",
+        vec![permit_transfer.clone()],
+    )?;
+
+    // addr.a transfers tokens to the permit signer
+    let msg = ExecuteMsg::Transfer {
+        token_id: "0".to_string(),
+        from: addr.a(),
+        recipient: owner.clone(),
+        amount: Uint256::from(100u128),
+        memo: None,
+        decoys: None,
+        entropy: None,
+        padding: None,
+    };
+    let info = mock_info(addr.a().as_str(), &[]);
+    execute(deps.as_mut(), mock_env(), info, msg)?;
+
+    // addr.b submits the owner's signed transfer
+    let msg = ExecuteMsg::TransferWithPermit {
+        permit: permit.clone(),
+        padding: None,
+    };
+    let info = mock_info(addr.b().as_str(), &[]);
+    execute(deps.as_mut(), mock_env(), info.clone(), msg.clone())?;
+    assert_eq!(
+        chk_bal(&deps.storage, "0", &owner).unwrap(),
+        Uint256::from(90u128)
+    );
+    assert_eq!(
+        chk_bal(&deps.storage, "0", &addr.c()).unwrap(),
+        Uint256::from(10u128)
+    );
+
+    // cannot replay the same permit
+    let result = execute(deps.as_mut(), mock_env(), info.clone(), msg);
+    assert_eq!(
+        extract_error_msg(&result),
+        "Transfer permit nonce 1 has already been used"
+    );
+
+    // cannot alter signed transfer
+    let mut altered_permit = permit;
+    altered_permit.params.permissions[0].nonce = Uint64::from(2u64);
+    altered_permit.params.permissions[0].amount = Uint256::from(90u128);
+    let msg = ExecuteMsg::TransferWithPermit {
+        permit: altered_permit,
+        padding: None,
+    };
+    let result = execute(deps.as_mut(), mock_env(), info.clone(), msg);
+    assert!(extract_error_msg(&result).contains("Failed to verify signatures"));
+
+    // cannot use an expired permit
+    let (permit, _) = sign_permit(
+        &deps,
+        &private_key,
+        "transfer1",
+        vec![PermitTransfer {
+            nonce: Uint64::from(3u64),
+            ..permit_transfer.clone()
+        }],
+    )?;
+    let msg = ExecuteMsg::TransferWithPermit {
+        permit,
+        padding: None,
+    };
+    let mut env = mock_env();
+    env.block.height += 10;
+    let result = execute(deps.as_mut(), env, info.clone(), msg);
+    assert_eq!(
+        extract_error_msg(&result),
+        "Transfer permit has expired: expiration height: 12355"
+    );
+
+    // owner can revoke an unused permit
+    let (permit, _) = sign_permit(
+        &deps,
+        &private_key,
+        "transfer2",
+        vec![PermitTransfer {
+            nonce: Uint64::from(4u64),
+            ..permit_transfer
+        }],
+    )?;
+    let msg = ExecuteMsg::RevokePermit {
+        permit_name: "transfer2".to_string(),
+        padding: None,
+    };
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(owner.as_str(), &[]),
+        msg,
+    )?;
+    let msg = ExecuteMsg::TransferWithPermit {
+        permit,
+        padding: None,
+    };
+    let result = execute(deps.as_mut(), mock_env(), info, msg);
+    assert!(extract_error_msg(&result).contains("was revoked"));
+    assert_eq!(
+        chk_bal(&deps.storage, "0", &owner).unwrap(),
+        Uint256::from(90u128)
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_swap_offers() -> StdResult<()> {
+    // init addresses
+    let addr = init_addrs();
+
+    // instantiate
+    let (init_result, mut deps) = init_helper_default();
+    assert_eq!(init_result.unwrap(), Response::default());
+    // addr.a has 1000 of token_id "0"; curate NFT "2" owned by addr2
+    let info = mock_info(addr.a().as_str(), &[]);
+    curate_addtl_default(&mut deps, mock_env(), info.clone())?;
+    let addr2 = Addr::unchecked("addr2");
+    let contract = mock_env().contract.address;
+
+    // addr.a offers 100 token "0" for NFT "2", only to addr2
+    let msg = ExecuteMsg::CreateSwapOffer {
+        offer_token_id: "0".to_string(),
+        offer_amount: Uint256::from(100u128),
+        ask_token_id: "2".to_string(),
+        ask_amount: Uint256::from(1u128),
+        counterparty: Some(addr2.clone()),
+        expiration: Some(Expiration::AtHeight(mock_env().block.height + 10)),
+        padding: None,
+    };
+    let response = execute(deps.as_mut(), mock_env(), info.clone(), msg)?;
+    let offer_id = match from_binary::<ExecuteAnswer>(&response.data.unwrap())? {
+        ExecuteAnswer::CreateSwapOffer { offer_id } => offer_id,
+        _ => panic!("unexpected answer"),
+    };
+    assert_eq!(offer_id, 0);
+    assert_eq!(
+        chk_bal(&deps.storage, "0", &addr.a()).unwrap(),
+        Uint256::from(900u128)
+    );
+    assert_eq!(
+        chk_bal(&deps.storage, "0", &contract).unwrap(),
+        Uint256::from(100u128)
+    );
+
+    // only the maker and counterparty can query the offer
+    let vks = generate_viewing_keys(
+        &mut deps,
+        mock_env(),
+        info.clone(),
+        vec![addr.a(), addr.b()],
+    )?;
+    let q_answer = from_binary::<QueryAnswer>(&query(
+        deps.as_ref(),
+        mock_env(),
+        QueryMsg::SwapOffer {
+            viewer: addr.a(),
+            key: vks.a(),
+            offer_id,
+        },
+    )?)?;
+    match q_answer {
+        QueryAnswer::SwapOffer(offer) => {
+            assert_eq!(offer.maker, addr.a());
+            assert_eq!(offer.counterparty, Some(addr2.clone()));
+        }
+        _ => panic!("query error"),
+    }
+    let q_result = query(
+        deps.as_ref(),
+        mock_env(),
+        QueryMsg::SwapOffer {
+            viewer: addr.b(),
+            key: vks.b(),
+            offer_id,
+        },
+    );
+    assert_eq!(
+        extract_error_msg(&q_result),
+        "only the maker and counterparty can view swap offer 0"
+    );
+    let q_answer = from_binary::<QueryAnswer>(&query(
+        deps.as_ref(),
+        mock_env(),
+        QueryMsg::SwapOffer {
+            viewer: addr.a(),
+            key: vks.b(),
+            offer_id,
+        },
+    )?)?;
+    match q_answer {
+        QueryAnswer::ViewingKeyError { .. } => (),
+        _ => panic!("unexpected answer"),
+    }
+
+    // other addresses cannot accept the offer
+    let msg = ExecuteMsg::AcceptSwapOffer {
+        offer_id,
+        padding: None,
+    };
+    let info_b = mock_info(addr.b().as_str(), &[]);
+    let result = execute(deps.as_mut(), mock_env(), info_b, msg.clone());
+    assert_eq!(
+        extract_error_msg(&result),
+        "swap offer 0 can only be accepted by addr2"
+    );
+
+    // cannot accept after expiration
+    let info2 = mock_info(addr2.as_str(), &[]);
+    let mut env = mock_env();
+    env.block.height += 10;
+    let result = execute(deps.as_mut(), env, info2.clone(), msg.clone());
+    assert_eq!(extract_error_msg(&result), "swap offer 0 has expired");
+
+    // addr2 accepts the offer
+    execute(deps.as_mut(), mock_env(), info2.clone(), msg.clone())?;
+    assert_eq!(
+        chk_bal(&deps.storage, "2", &addr.a()).unwrap(),
+        Uint256::from(1u128)
+    );
+    assert_eq!(
+        chk_bal(&deps.storage, "0", &addr2).unwrap(),
+        Uint256::from(100u128)
+    );
+    assert_eq!(
+        chk_bal(&deps.storage, "0", &contract).unwrap(),
+        Uint256::zero()
+    );
+    let result = execute(deps.as_mut(), mock_env(), info2, msg);
+    assert_eq!(extract_error_msg(&result), "swap offer 0 does not exist");
+
+    // addr.a creates another offer, which only addr.a can cancel
+    let msg = ExecuteMsg::CreateSwapOffer {
+        offer_token_id: "0".to_string(),
+        offer_amount: Uint256::from(50u128),
+        ask_token_id: "1".to_string(),
+        ask_amount: Uint256::from(10u128),
+        counterparty: None,
+        expiration: None,
+        padding: None,
+    };
+    execute(deps.as_mut(), mock_env(), info.clone(), msg)?;
+    let msg = ExecuteMsg::CancelSwapOffer {
+        offer_id: 1,
+        padding: None,
+    };
+    let info_b = mock_info(addr.b().as_str(), &[]);
+    let result = execute(deps.as_mut(), mock_env(), info_b, msg.clone());
+    assert_eq!(
+        extract_error_msg(&result),
+        "only the maker can cancel swap offer 1"
+    );
+    execute(deps.as_mut(), mock_env(), info, msg)?;
+    assert_eq!(
+        chk_bal(&deps.storage, "0", &addr.a()).unwrap(),
+        Uint256::from(900u128)
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_escrow() -> StdResult<()> {
+    // init addresses
+    let addr = init_addrs();
+
+    // instantiate
+    let (init_result, mut deps) = init_helper_default();
+    assert_eq!(init_result.unwrap(), Response::default());
+    let release_height = mock_env().block.height + 10;
+    let mut env_released = mock_env();
+    env_released.block.height = release_height;
+
+    // addr.a escrows 100 tokens for addr.b, with clawback allowed
+    let msg = ExecuteMsg::CreateEscrow {
+        token_id: "0".to_string(),
+        amount: Uint256::from(100u128),
+        beneficiary: addr.b(),
+        release: Expiration::AtHeight(release_height),
+        clawback: Some(true),
+        memo: None,
+        padding: None,
+    };
+    let info_a = mock_info(addr.a().as_str(), &[]);
+    let response = execute(deps.as_mut(), mock_env(), info_a.clone(), msg)?;
+    match from_binary::<ExecuteAnswer>(&response.data.unwrap())? {
+        ExecuteAnswer::CreateEscrow { escrow_id } => assert_eq!(escrow_id, 0),
+        _ => panic!("unexpected answer"),
+    }
+    assert_eq!(
+        chk_bal(&deps.storage, "0", &addr.a()).unwrap(),
+        Uint256::from(900u128)
+    );
+    // the contract is not notified of the tokens it holds
+    assert!(!response
+        .attributes
+        .iter()
+        .any(|attr| attr.key.starts_with("snip52:")));
+
+    // addr.b cannot claim before release
+    let msg_claim = ExecuteMsg::ClaimEscrow {
+        escrow_id: 0,
+        padding: None,
+    };
+    let info_b = mock_info(addr.b().as_str(), &[]);
+    let result = execute(deps.as_mut(), mock_env(), info_b.clone(), msg_claim);
+    assert_eq!(
+        extract_error_msg(&result),
+        format!(
+            "escrow 0 cannot be claimed before expiration height: {}",
+            release_height
+        )
+    );
+
+    // addr.a claws back before release
+    let msg_clawback = ExecuteMsg::ClawbackEscrow {
+        escrow_id: 0,
+        padding: None,
+    };
+    execute(deps.as_mut(), mock_env(), info_a.clone(), msg_clawback)?;
+    assert_eq!(
+        chk_bal(&deps.storage, "0", &addr.a()).unwrap(),
+        Uint256::from(1000u128)
+    );
+
+    // second escrow, without clawback
+    let msg = ExecuteMsg::CreateEscrow {
+        token_id: "0".to_string(),
+        amount: Uint256::from(100u128),
+        beneficiary: addr.b(),
+        release: Expiration::AtHeight(release_height),
+        clawback: None,
+        memo: None,
+        padding: None,
+    };
+    execute(deps.as_mut(), mock_env(), info_a.clone(), msg)?;
+    let msg_clawback = ExecuteMsg::ClawbackEscrow {
+        escrow_id: 1,
+        padding: None,
+    };
+    let result = execute(deps.as_mut(), mock_env(), info_a, msg_clawback);
+    assert_eq!(
+        extract_error_msg(&result),
+        "escrow 1 does not allow clawback"
+    );
+
+    // only the beneficiary can claim, after release
+    let msg_claim = ExecuteMsg::ClaimEscrow {
+        escrow_id: 1,
+        padding: None,
+    };
+    let info_c = mock_info(addr.c().as_str(), &[]);
+    let result = execute(
+        deps.as_mut(),
+        env_released.clone(),
+        info_c,
+        msg_claim.clone(),
+    );
+    assert_eq!(
+        extract_error_msg(&result),
+        "only the beneficiary can claim escrow 1"
+    );
+    execute(
+        deps.as_mut(),
+        env_released.clone(),
+        info_b.clone(),
+        msg_claim.clone(),
+    )?;
+    assert_eq!(
+        chk_bal(&deps.storage, "0", &addr.b()).unwrap(),
+        Uint256::from(100u128)
+    );
+    let result = execute(deps.as_mut(), env_released, info_b.clone(), msg_claim);
+    assert_eq!(extract_error_msg(&result), "escrow 1 does not exist");
+
+    // escrow txs are recorded in the beneficiary's history
+    let vks = generate_viewing_keys(&mut deps, mock_env(), info_b, vec![addr.b()])?;
+    let msg_q = QueryMsg::TransactionHistory {
+        address: addr.b(),
+        key: vks.a(),
+        page: None,
+        page_size: 10,
+    };
+    let q_answer = from_binary::<QueryAnswer>(&query(deps.as_ref(), mock_env(), msg_q)?)?;
+    match q_answer {
+        QueryAnswer::TransactionHistory { txs, total } => {
+            assert_eq!(total, 5);
+            assert!(matches!(
+                txs[0].action,
+                txhistory::TxAction::EscrowClaim { escrow_id: 1, .. }
+            ));
+            assert!(matches!(
+                txs[1].action,
+                txhistory::TxAction::Transfer { .. }
+            ));
+            assert_eq!(txs[1].memo, Some("escrow 1".to_string()));
+            assert!(matches!(
+                txs[3].action,
+                txhistory::TxAction::EscrowClawback { escrow_id: 0, .. }
+            ));
+        }
+        _ => panic!("query error"),
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_vesting() -> StdResult<()> {
+    // init addresses
+    let addr = init_addrs();
+
+    // instantiate
+    let (init_result, mut deps) = init_helper_default();
+    assert_eq!(init_result.unwrap(), Response::default());
+    let start = mock_env().block.time.seconds();
+    let env_at = |secs: u64| {
+        let mut env = mock_env();
+        env.block.time = env.block.time.plus_seconds(secs);
+        env
+    };
+
+    // only curators can create vesting schedules
+    let msg = ExecuteMsg::CreateVestingSchedule {
+        token_id: "0".to_string(),
+        beneficiary: addr.b(),
+        total: Uint256::from(400u128),
+        start,
+        cliff: start + 100,
+        end: start + 400,
+        padding: None,
+    };
+    let info_b = mock_info(addr.b().as_str(), &[]);
+    let result = execute(deps.as_mut(), mock_env(), info_b.clone(), msg.clone());
+    assert_eq!(
+        extract_error_msg(&result),
+        "Only curators are allowed to curate token_ids"
+    );
+
+    // invalid schedule
+    let msg_invalid = ExecuteMsg::CreateVestingSchedule {
+        token_id: "0".to_string(),
+        beneficiary: addr.b(),
+        total: Uint256::from(400u128),
+        start,
+        cliff: start + 500,
+        end: start + 400,
+        padding: None,
+    };
+    let info_a = mock_info(addr.a().as_str(), &[]);
+    let result = execute(deps.as_mut(), mock_env(), info_a.clone(), msg_invalid);
+    assert_eq!(
+        extract_error_msg(&result),
+        "vesting schedule must satisfy start <= cliff <= end and start < end"
+    );
+
+    // addr.a (curator) funds a schedule for addr.b
+    execute(deps.as_mut(), mock_env(), info_a.clone(), msg.clone())?;
+    assert_eq!(
+        chk_bal(&deps.storage, "0", &addr.a()).unwrap(),
+        Uint256::from(600u128)
+    );
+    let result = execute(deps.as_mut(), mock_env(), info_a, msg);
+    assert_eq!(
+        extract_error_msg(&result),
+        "addr1 already has a vesting schedule for token_id 0"
+    );
+
+    // nothing can be claimed before the cliff
+    let msg_claim = ExecuteMsg::ClaimVested {
+        token_id: "0".to_string(),
+        padding: None,
+    };
+    let result = execute(deps.as_mut(), env_at(99), info_b.clone(), msg_claim.clone());
+    assert_eq!(
+        extract_error_msg(&result),
+        "no vested tokens to claim for token_id 0"
+    );
+
+    // claim at 1/4 of the schedule
+    let response = execute(
+        deps.as_mut(),
+        env_at(100),
+        info_b.clone(),
+        msg_claim.clone(),
+    )?;
+    match from_binary::<ExecuteAnswer>(&response.data.unwrap())? {
+        ExecuteAnswer::ClaimVested { amount } => assert_eq!(amount, Uint256::from(100u128)),
+        _ => panic!("unexpected answer"),
+    }
+    assert_eq!(
+        chk_bal(&deps.storage, "0", &addr.b()).unwrap(),
+        Uint256::from(100u128)
+    );
+
+    // query vesting schedule at 3/4 of the schedule
+    let vks = generate_viewing_keys(&mut deps, env_at(300), info_b.clone(), vec![addr.b()])?;
+    let msg_q = QueryMsg::VestingSchedule {
+        address: addr.b(),
+        key: vks.a(),
+        token_id: "0".to_string(),
+    };
+    match from_binary::<QueryAnswer>(&query(deps.as_ref(), mock_env(), msg_q)?)? {
+        QueryAnswer::VestingSchedule {
+            vested,
+            claimed,
+            locked,
+            schedule,
+        } => {
+            assert_eq!(vested, Uint256::from(300u128));
+            assert_eq!(claimed, Uint256::from(100u128));
+            assert_eq!(locked, Uint256::from(100u128));
+            assert_eq!(schedule.funder, addr.a());
+        }
+        _ => panic!("unexpected answer"),
+    }
+
+    // claim everything after the end; schedule is removed once fully claimed
+    execute(
+        deps.as_mut(),
+        env_at(1000),
+        info_b.clone(),
+        msg_claim.clone(),
+    )?;
+    assert_eq!(
+        chk_bal(&deps.storage, "0", &addr.b()).unwrap(),
+        Uint256::from(400u128)
+    );
+    let result = execute(deps.as_mut(), env_at(1000), info_b, msg_claim);
+    assert_eq!(
+        extract_error_msg(&result),
+        "no vesting schedule found for token_id 0"
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_airdrop() -> StdResult<()> {
+    // init addresses
+    let addr = init_addrs();
+
+    // instantiate
+    let (init_result, mut deps) = init_helper_default();
+    assert_eq!(init_result.unwrap(), Response::default());
+
+    // Merkle tree of (addr.b, 100), (addr.c, 200) and (addr.d, 50)
+    let leaf = |address: &Addr, amount: u128| {
+        let mut bytes = address.as_bytes().to_vec();
+        bytes.extend_from_slice(&Uint256::from(amount).to_be_bytes());
+        sha_256(&bytes)
+    };
+    let parent = |a: [u8; 32], b: [u8; 32]| {
+        let (first, second) = if a <= b { (a, b) } else { (b, a) };
+        sha_256(&[first, second].concat())
+    };
+    let leaf_b = leaf(&addr.b(), 100);
+    let leaf_c = leaf(&addr.c(), 200);
+    let leaf_d = leaf(&addr.d(), 50);
+    let node_bc = parent(leaf_b, leaf_c);
+    let root = parent(node_bc, leaf_d);
+
+    // only curators can register airdrops
+    let msg_register = ExecuteMsg::RegisterAirdrop {
+        token_id: "0".to_string(),
+        round: 1,
+        merkle_root: Binary::from(root.as_slice()),
+        padding: None,
+    };
+    let info_b = mock_info(addr.b().as_str(), &[]);
+    let result = execute(
+        deps.as_mut(),
+        mock_env(),
+        info_b.clone(),
+        msg_register.clone(),
+    );
+    assert_eq!(
+        extract_error_msg(&result),
+        "Only curators are allowed to curate token_ids"
+    );
+    let info_a = mock_info(addr.a().as_str(), &[]);
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        info_a.clone(),
+        msg_register.clone(),
+    )?;
+    let result = execute(deps.as_mut(), mock_env(), info_a.clone(), msg_register);
+    assert_eq!(
+        extract_error_msg(&result),
+        "airdrop round 1 of token_id 0 already exists"
+    );
+
+    // other curators cannot register airdrops of token_ids they did not curate
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        info_a,
+        ExecuteMsg::AddCurators {
+            add_curators: vec![addr.b()],
+            padding: None,
+        },
+    )?;
+    let result = execute(
+        deps.as_mut(),
+        mock_env(),
+        info_b.clone(),
+        ExecuteMsg::RegisterAirdrop {
+            token_id: "0".to_string(),
+            round: 2,
+            merkle_root: Binary::from(root.as_slice()),
+            padding: None,
+        },
+    );
+    assert_eq!(
+        extract_error_msg(&result),
+        "only the curator of token_id 0 can register its airdrops"
+    );
+
+    // wrong amount fails verification
+    let msg_claim = ExecuteMsg::ClaimAirdrop {
+        token_id: "0".to_string(),
+        round: 1,
+        amount: Uint256::from(1000u128),
+        proof: vec![
+            Binary::from(leaf_c.as_slice()),
+            Binary::from(leaf_d.as_slice()),
+        ],
+        padding: None,
+    };
+    let result = execute(deps.as_mut(), mock_env(), info_b.clone(), msg_claim);
+    assert_eq!(extract_error_msg(&result), "invalid airdrop proof");
+
+    // addr.b claims, which is minted by the curator with a notification and a receiver callback
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        info_b.clone(),
+        ExecuteMsg::RegisterReceive {
+            code_hash: addr.b_hash(),
+            token_ids: None,
+            reject_unlisted_token_ids: None,
+            padding: None,
+        },
+    )?;
+    let msg_claim = ExecuteMsg::ClaimAirdrop {
+        token_id: "0".to_string(),
+        round: 1,
+        amount: Uint256::from(100u128),
+        proof: vec![
+            Binary::from(leaf_c.as_slice()),
+            Binary::from(leaf_d.as_slice()),
+        ],
+        padding: None,
+    };
+    let response = execute(deps.as_mut(), mock_env(), info_b.clone(), msg_claim.clone())?;
+    assert!(response
+        .attributes
+        .iter()
+        .any(|attr| attr.key.starts_with("snip52:")));
+    assert_eq!(response.messages.len(), 1);
+    let (receiver_msg, receiver_addr, _) =
+        extract_cosmos_msg::<ReceiverHandleMsg>(&response.messages[0].msg)?;
+    assert_eq!(receiver_addr, Some(addr.b()));
+    let exp_receive_msg = Snip1155ReceiveMsg {
+        sender: addr.a(),
+        token_id: "0".to_string(),
+        from: addr.a(),
+        amount: Uint256::from(100u128),
+        memo: Some("airdrop round 1".to_string()),
+        msg: None,
+    };
+    match receiver_msg {
+        ReceiverHandleMsg::Snip1155Receive(i) => assert_eq!(i, exp_receive_msg),
+    }
+    assert_eq!(
+        chk_bal(&deps.storage, "0", &addr.b()).unwrap(),
+        Uint256::from(100u128)
+    );
+    assert_eq!(
+        tkn_tot_supply_r(&deps.storage).load("0".as_bytes())?,
+        Uint256::from(1100u128)
+    );
+
+    // cannot claim twice
+    let result = execute(deps.as_mut(), mock_env(), info_b, msg_claim);
+    assert_eq!(
+        extract_error_msg(&result),
+        "airdrop round 1 of token_id 0 has already been claimed"
+    );
+
+    // addr.d claims with a single-node proof
+    let msg_claim = ExecuteMsg::ClaimAirdrop {
+        token_id: "0".to_string(),
+        round: 1,
+        amount: Uint256::from(50u128),
+        proof: vec![Binary::from(node_bc.as_slice())],
+        padding: None,
+    };
+    let info_d = mock_info(addr.d().as_str(), &[]);
+    execute(deps.as_mut(), mock_env(), info_d, msg_claim)?;
+    assert_eq!(
+        chk_bal(&deps.storage, "0", &addr.d()).unwrap(),
+        Uint256::from(50u128)
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_sale() -> StdResult<()> {
+    // init addresses
+    let addr = init_addrs();
+
+    // instantiate
+    let (init_result, mut deps) = init_helper_default();
+    assert_eq!(init_result.unwrap(), Response::default());
+    let end_height = mock_env().block.height + 10;
+    let mut env_ended = mock_env();
+    env_ended.block.height = end_height;
+
+    // only the token_id's curator can set a sale
+    let msg_set = ExecuteMsg::SetSale {
+        token_id: "0".to_string(),
+        price: coin(10, "uscrt"),
+        per_address_limit: Some(Uint256::from(5u128)),
+        start: None,
+        end: Some(Expiration::AtHeight(end_height)),
+        max_sold: Some(Uint256::from(8u128)),
+        snip20_prices: None,
+        padding: None,
+    };
+    let info_b = mock_info(addr.b().as_str(), &[]);
+    let result = execute(deps.as_mut(), mock_env(), info_b, msg_set.clone());
+    assert_eq!(
+        extract_error_msg(&result),
+        "only the curator of token_id 0 can set its sale"
+    );
+    let info_a = mock_info(addr.a().as_str(), &[]);
+    execute(deps.as_mut(), mock_env(), info_a.clone(), msg_set)?;
+
+    // insufficient payment
+    let msg_purchase = |quantity: u128| ExecuteMsg::Purchase {
+        token_id: "0".to_string(),
+        quantity: Uint256::from(quantity),
+        padding: None,
+    };
+    let info_b = mock_info(addr.b().as_str(), &coins(20, "uscrt"));
+    let result = execute(deps.as_mut(), mock_env(), info_b, msg_purchase(3));
+    assert_eq!(
+        extract_error_msg(&result),
+        "insufficient payment: 3 tokens cost 30uscrt"
+    );
+
+    // wrong denom
+    let info_b = mock_info(addr.b().as_str(), &coins(30, "uatom"));
+    let result = execute(deps.as_mut(), mock_env(), info_b, msg_purchase(3));
+    assert_eq!(
+        extract_error_msg(&result),
+        "sale of token_id 0 only accepts uscrt"
+    );
+
+    // overpayment is refunded
+    let info_b = mock_info(addr.b().as_str(), &coins(45, "uscrt"));
+    let response = execute(deps.as_mut(), mock_env(), info_b, msg_purchase(4))?;
+    assert_eq!(
+        response.messages[0].msg,
+        CosmosMsg::Bank(BankMsg::Send {
+            to_address: addr.b().to_string(),
+            amount: coins(5, "uscrt"),
+        })
+    );
+    assert_eq!(
+        chk_bal(&deps.storage, "0", &addr.b()).unwrap(),
+        Uint256::from(4u128)
+    );
+
+    // per-address limit
+    let info_b = mock_info(addr.b().as_str(), &coins(20, "uscrt"));
+    let result = execute(deps.as_mut(), mock_env(), info_b, msg_purchase(2));
+    assert_eq!(
+        extract_error_msg(&result),
+        "purchase exceeds the limit of 5 tokens of token_id 0 per address"
+    );
+
+    // max sold
+    let info_c = mock_info(addr.c().as_str(), &coins(50, "uscrt"));
+    let result = execute(deps.as_mut(), mock_env(), info_c, msg_purchase(5));
+    assert_eq!(
+        extract_error_msg(&result),
+        "only 4 tokens of token_id 0 remain for sale"
+    );
+
+    // sale has ended
+    let info_c = mock_info(addr.c().as_str(), &coins(10, "uscrt"));
+    let result = execute(deps.as_mut(), env_ended, info_c, msg_purchase(1));
+    assert_eq!(extract_error_msg(&result), "sale of token_id 0 has ended");
+
+    // curator withdraws proceeds
+    let msg_withdraw = ExecuteMsg::WithdrawSaleProceeds {
+        token_id: "0".to_string(),
+        padding: None,
+    };
+    let response = execute(deps.as_mut(), mock_env(), info_a.clone(), msg_withdraw)?;
+    assert_eq!(
+        response.messages[0].msg,
+        CosmosMsg::Bank(BankMsg::Send {
+            to_address: addr.a().to_string(),
+            amount: coins(40, "uscrt"),
+        })
+    );
+
+    // error: max_sold cannot be set below the tokens already sold
+    let msg_set = ExecuteMsg::SetSale {
+        token_id: "0".to_string(),
+        price: coin(10, "uscrt"),
+        per_address_limit: None,
+        start: None,
+        end: None,
+        max_sold: Some(Uint256::from(3u128)),
+        snip20_prices: None,
+        padding: None,
+    };
+    let result = execute(deps.as_mut(), mock_env(), info_a, msg_set);
+    assert_eq!(
+        extract_error_msg(&result),
+        "max_sold cannot be less than the 4 tokens of token_id 0 already sold"
+    );
+    let msg_q = QueryMsg::Sale {
+        token_id: "0".to_string(),
+    };
+    match from_binary::<QueryAnswer>(&query(deps.as_ref(), mock_env(), msg_q)?)? {
+        QueryAnswer::Sale(sale) => {
+            assert_eq!(sale.sold, Uint256::from(4u128));
+            assert_eq!(sale.proceeds, Uint128::zero());
+        }
+        _ => panic!("unexpected answer"),
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_snip20_purchase() -> StdResult<()> {
+    // init addresses
+    let addr = init_addrs();
+    let snip20 = Addr::unchecked("snip20".to_string());
+    let treasury = Addr::unchecked("treasury".to_string());
+
+    // instantiate
+    let (init_result, mut deps) = init_helper_default();
+    assert_eq!(init_result.unwrap(), Response::default());
+
+    // SNIP-20 must be whitelisted before it can be used in a sale
+    let msg_set = ExecuteMsg::SetSale {
+        token_id: "0".to_string(),
+        price: coin(10, "uscrt"),
+        per_address_limit: None,
+        start: None,
+        end: None,
+        max_sold: None,
+        snip20_prices: Some(vec![Snip20Price {
+            token: snip20.clone(),
+            amount: Uint128::from(7u128),
+        }]),
+        padding: None,
+    };
+    let info_a = mock_info(addr.a().as_str(), &[]);
+    let result = execute(deps.as_mut(), mock_env(), info_a.clone(), msg_set.clone());
+    assert_eq!(
+        extract_error_msg(&result),
+        "snip20 is not an accepted payment token"
+    );
+
+    // admin whitelists the SNIP-20, which registers this contract with it
+    let msg_add = ExecuteMsg::AddPaymentTokens {
+        payment_tokens: vec![PaymentToken {
+            address: snip20.clone(),
+            code_hash: "snip20_hash".to_string(),
+        }],
+        treasury: Some(treasury.clone()),
+        padding: None,
+    };
+    let info_b = mock_info(addr.b().as_str(), &[]);
+    let result = execute(deps.as_mut(), mock_env(), info_b, msg_add.clone());
+    assert!(extract_error_msg(&result).contains("This is an admin function"));
+    let response = execute(deps.as_mut(), mock_env(), info_a.clone(), msg_add)?;
+    let (register_msg, contract, hash) =
+        extract_cosmos_msg::<Snip20HandleMsg>(&response.messages[0].msg)?;
+    assert_eq!(
+        register_msg,
+        Snip20HandleMsg::RegisterReceive {
+            code_hash: mock_env().contract.code_hash,
+            padding: None,
+        }
+    );
+    assert_eq!(contract, Some(snip20.clone()));
+    assert_eq!(hash, "snip20_hash");
+    execute(deps.as_mut(), mock_env(), info_a, msg_set)?;
+
+    // only whitelisted SNIP-20s can call `Receive`
+    let msg_receive = ExecuteMsg::Receive {
+        sender: addr.b(),
+        from: addr.b(),
+        amount: Uint128::from(25u128),
+        memo: None,
+        msg: Some(to_binary(&Snip20ReceiveMsg::Purchase {
+            token_id: "0".to_string(),
+            quantity: Uint256::from(3u128),
+        })?),
+    };
+    let info_fake = mock_info("fake_snip20", &[]);
+    let result = execute(deps.as_mut(), mock_env(), info_fake, msg_receive.clone());
+    assert_eq!(
+        extract_error_msg(&result),
+        "fake_snip20 is not an accepted payment token"
+    );
+
+    // addr.b purchases 3 tokens for 21, with 4 refunded. The purchase is minted by the curator
+    // with a notification and a receiver callback to the buyer
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(addr.b().as_str(), &[]),
+        ExecuteMsg::RegisterReceive {
+            code_hash: addr.b_hash(),
+            token_ids: None,
+            reject_unlisted_token_ids: None,
+            padding: None,
+        },
+    )?;
+    let info_snip20 = mock_info(snip20.as_str(), &[]);
+    let response = execute(deps.as_mut(), mock_env(), info_snip20, msg_receive)?;
+    assert_eq!(
+        chk_bal(&deps.storage, "0", &addr.b()).unwrap(),
+        Uint256::from(3u128)
+    );
+    assert!(response
+        .attributes
+        .iter()
+        .any(|attr| attr.key.starts_with("snip52:")));
+    assert_eq!(response.messages.len(), 3);
+    let (receiver_msg, receiver_addr, _) =
+        extract_cosmos_msg::<ReceiverHandleMsg>(&response.messages[0].msg)?;
+    assert_eq!(receiver_addr, Some(addr.b()));
+    let exp_receive_msg = Snip1155ReceiveMsg {
+        sender: addr.a(),
+        token_id: "0".to_string(),
+        from: addr.a(),
+        amount: Uint256::from(3u128),
+        memo: Some("purchase".to_string()),
+        msg: None,
+    };
+    match receiver_msg {
+        ReceiverHandleMsg::Snip1155Receive(i) => assert_eq!(i, exp_receive_msg),
+    }
+    let (forward_msg, _, _) = extract_cosmos_msg::<Snip20HandleMsg>(&response.messages[1].msg)?;
+    assert_eq!(
+        forward_msg,
+        Snip20HandleMsg::Transfer {
+            recipient: treasury,
+            amount: Uint128::from(21u128),
+            memo: Some("sale of token_id 0".to_string()),
+            padding: None,
+        }
+    );
+    let (refund_msg, _, _) = extract_cosmos_msg::<Snip20HandleMsg>(&response.messages[2].msg)?;
+    assert_eq!(
+        refund_msg,
+        Snip20HandleMsg::Transfer {
+            recipient: addr.b(),
+            amount: Uint128::from(4u128),
+            memo: Some("refund".to_string()),
+            padding: None,
+        }
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_nft_user() -> StdResult<()> {
+    // init addresses
+    let addr = init_addrs();
+
+    // instantiate + curate more tokens
+    let (init_result, mut deps) = init_helper_default();
+    assert_eq!(init_result.unwrap(), Response::default());
+    let mut info = mock_info(addr.a().as_str(), &[]);
+    curate_addtl_default(&mut deps, mock_env(), info.clone())?;
+    let vks = generate_viewing_keys(
+        &mut deps,
+        mock_env(),
+        info.clone(),
+        vec![addr.a(), addr.b(), addr.c()],
+    )?;
+
+    // only the owner can set the user
+    let msg = ExecuteMsg::SetUser {
+        token_id: "2".to_string(),
+        user: Some(addr.b()),
+        expires: Some(Expiration::AtHeight(mock_env().block.height + 10)),
+        padding: None,
+    };
+    let result = execute(deps.as_mut(), mock_env(), info.clone(), msg.clone());
+    assert_eq!(
+        extract_error_msg(&result),
+        "only the owner of token_id 2 can set its user"
+    );
+    info.sender = addr.c();
+    execute(deps.as_mut(), mock_env(), info.clone(), msg)?;
+
+    // user is visible to the owner and the user, and can view private metadata
+    for (viewer, key) in [(addr.c(), vks.c()), (addr.b(), vks.b())] {
+        let msg_q = QueryMsg::NftUser {
+            viewer,
+            key,
+            token_id: "2".to_string(),
+        };
+        match from_binary::<QueryAnswer>(&query(deps.as_ref(), mock_env(), msg_q)?)? {
+            QueryAnswer::NftUser { user, .. } => assert_eq!(user, Some(addr.b())),
+            _ => panic!("unexpected answer"),
+        }
+    }
+    let msg_q = QueryMsg::NftUser {
+        viewer: addr.a(),
+        key: vks.a(),
+        token_id: "2".to_string(),
+    };
+    let result = query(deps.as_ref(), mock_env(), msg_q.clone());
+    assert_eq!(
+        extract_error_msg(&result),
+        "only the owner and user of token_id 2 can view its user"
+    );
+    let msg_private = QueryMsg::TokenIdPrivateInfo {
+        address: addr.b(),
+        key: vks.b(),
+        token_id: "2".to_string(),
+    };
+    query(deps.as_ref(), mock_env(), msg_private.clone())?;
+
+    // transferring the NFT clears its user
+    let msg_transfer = ExecuteMsg::Transfer {
+        token_id: "2".to_string(),
+        from: addr.c(),
+        recipient: addr.a(),
+        amount: Uint256::from(1u128),
+        memo: None,
+        decoys: None,
+        entropy: None,
+        padding: None,
+    };
+    execute(deps.as_mut(), mock_env(), info, msg_transfer)?;
+    match from_binary::<QueryAnswer>(&query(deps.as_ref(), mock_env(), msg_q)?)? {
+        QueryAnswer::NftUser { user, expires } => {
+            assert_eq!(user, None);
+            assert_eq!(expires, None);
+        }
+        _ => panic!("unexpected answer"),
+    }
+    let result = query(deps.as_ref(), mock_env(), msg_private);
+    assert_eq!(
+        extract_error_msg(&result),
+        "you do have have permission to view private token info"
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_fractionalize() -> StdResult<()> {
+    // init addresses
+    let addr = init_addrs();
+
+    // instantiate + curate more tokens
+    let (init_result, mut deps) = init_helper_default();
+    assert_eq!(init_result.unwrap(), Response::default());
+    let info_a = mock_info(addr.a().as_str(), &[]);
+    curate_addtl_default(&mut deps, mock_env(), info_a)?;
+
+    // only the owner can fractionalize
+    let msg = ExecuteMsg::Fractionalize {
+        nft_token_id: "2".to_string(),
+        new_token_id: "2shares".to_string(),
+        shares: Uint256::from(100u128),
+        padding: None,
+    };
+    let info_b = mock_info(addr.b().as_str(), &[]);
+    let result = execute(deps.as_mut(), mock_env(), info_b.clone(), msg.clone());
+    assert_eq!(
+        extract_error_msg(&result),
+        "only the owner of token_id 2 can fractionalize it"
+    );
+
+    // the new token_id is validated like any other curated token_id
+    let info_a = mock_info(addr.a().as_str(), &[]);
+    let msg_register = ExecuteMsg::RegisterNamespace {
+        prefix: "studio/".to_string(),
+        owner: addr.a(),
+        padding: None,
+    };
+    execute(deps.as_mut(), mock_env(), info_a.clone(), msg_register)?;
+    let msg_namespaced = ExecuteMsg::Fractionalize {
+        nft_token_id: "2".to_string(),
+        new_token_id: "studio/2shares".to_string(),
+        shares: Uint256::from(100u128),
+        padding: None,
+    };
+    let info_c = mock_info(addr.c().as_str(), &[]);
+    let result = execute(deps.as_mut(), mock_env(), info_c.clone(), msg_namespaced);
+    assert_eq!(
+        extract_error_msg(&result),
+        "token_id studio/2shares is in namespace studio/, which belongs to addr0"
+    );
+    let msg_quota = ExecuteMsg::SetCuratorQuota {
+        curator: addr.c(),
+        quota: Some(0),
+        padding: None,
+    };
+    execute(deps.as_mut(), mock_env(), info_a.clone(), msg_quota)?;
+    let result = execute(deps.as_mut(), mock_env(), info_c.clone(), msg.clone());
+    assert_eq!(
+        extract_error_msg(&result),
+        "curator addr2 has reached its quota of 0 token_ids"
+    );
+    let msg_quota = ExecuteMsg::SetCuratorQuota {
+        curator: addr.c(),
+        quota: None,
+        padding: None,
+    };
+    execute(deps.as_mut(), mock_env(), info_a, msg_quota)?;
+
+    // addr.c fractionalizes NFT "2" into 100 shares
+    execute(deps.as_mut(), mock_env(), info_c.clone(), msg)?;
+    assert_eq!(
+        chk_bal(&deps.storage, "2", &mock_env().contract.address).unwrap(),
+        Uint256::from(1u128)
+    );
+    assert_eq!(
+        chk_bal(&deps.storage, "2shares", &addr.c()).unwrap(),
+        Uint256::from(100u128)
+    );
+    let nft_info = tkn_info_r(&deps.storage).load("2".as_bytes())?;
+    assert_eq!(nft_info.fractionalized_as, Some("2shares".to_string()));
+    let shares_info = tkn_info_r(&deps.storage).load("2shares".as_bytes())?;
+    assert_eq!(shares_info.fraction_of, Some("2".to_string()));
+
+    // a holder of only some shares cannot reconstitute
+    let msg_transfer = ExecuteMsg::Transfer {
+        token_id: "2shares".to_string(),
+        from: addr.c(),
+        recipient: addr.b(),
+        amount: Uint256::from(40u128),
+        memo: None,
+        decoys: None,
+        entropy: None,
+        padding: None,
+    };
+    execute(deps.as_mut(), mock_env(), info_c.clone(), msg_transfer)?;
+    let msg_reconstitute = ExecuteMsg::Reconstitute {
+        nft_token_id: "2".to_string(),
+        padding: None,
+    };
+    let result = execute(
+        deps.as_mut(),
+        mock_env(),
+        info_c.clone(),
+        msg_reconstitute.clone(),
+    );
+    assert_eq!(
+        extract_error_msg(&result),
+        "all 100 shares of token_id 2shares are required to reconstitute token_id 2"
+    );
+
+    // addr.b collects all shares and reconstitutes the NFT
+    let msg_transfer = ExecuteMsg::Transfer {
+        token_id: "2shares".to_string(),
+        from: addr.c(),
+        recipient: addr.b(),
+        amount: Uint256::from(60u128),
+        memo: None,
+        decoys: None,
+        entropy: None,
+        padding: None,
+    };
+    execute(deps.as_mut(), mock_env(), info_c, msg_transfer)?;
+    execute(deps.as_mut(), mock_env(), info_b, msg_reconstitute)?;
+    assert_eq!(
+        chk_bal(&deps.storage, "2", &addr.b()).unwrap(),
+        Uint256::from(1u128)
+    );
+    assert_eq!(
+        tkn_tot_supply_r(&deps.storage).load("2shares".as_bytes())?,
+        Uint256::zero()
+    );
+    let nft_info = tkn_info_r(&deps.storage).load("2".as_bytes())?;
+    assert_eq!(nft_info.fractionalized_as, None);
+
+    Ok(())
+}
+
+#[test]
+fn test_editions() -> StdResult<()> {
+    // init addresses
+    let addr = init_addrs();
+
+    // instantiate
+    let (init_result, mut deps) = init_helper_default();
+    assert_eq!(init_result.unwrap(), Response::default());
+
+    // addr.b (another curator) curates "print/1" before the series exists
+    let info_a = mock_info(addr.a().as_str(), &[]);
+    let info_b = mock_info(addr.b().as_str(), &[]);
+    let msg = ExecuteMsg::AddCurators {
+        add_curators: vec![addr.b()],
+        padding: None,
+    };
+    execute(deps.as_mut(), mock_env(), info_a.clone(), msg)?;
+    let curate = |token_id: &str| {
+        let mut curate = CurateTokenId::default();
+        curate.token_info.token_id = token_id.to_string();
+        ExecuteMsg::CurateTokenIds {
+            initial_tokens: vec![curate],
+            memo: None,
+            notify_receivers: None,
+            padding: None,
+        }
+    };
+    execute(deps.as_mut(), mock_env(), info_b.clone(), curate("print/1"))?;
+
+    // addr.a (curator) creates a series of 2 editions
+    let mut series = CurateTokenId::default().token_info;
+    series.token_id = "print".to_string();
+    series.token_config = TknConfig::default_nft();
+    let msg = ExecuteMsg::CreateEditionSeries {
+        series,
+        max_editions: 2,
+        padding: None,
+    };
+    execute(deps.as_mut(), mock_env(), info_a.clone(), msg)?;
+
+    // error: other curators can no longer curate token_ids derived from the series
+    let result = execute(deps.as_mut(), mock_env(), info_b, curate("print/3"));
+    assert_eq!(
+        extract_error_msg(&result),
+        "token_id print/3 is reserved for the curator of token_id print"
+    );
+
+    // first edition inherits the series' metadata, and skips the serial that is already taken
+    let msg_mint = ExecuteMsg::MintEdition {
+        parent_token_id: "print".to_string(),
+        recipient: addr.b(),
+        public_metadata: None,
+        private_metadata: None,
+        memo: None,
+        padding: None,
+    };
+    let response = execute(deps.as_mut(), mock_env(), info_a.clone(), msg_mint)?;
+    match from_binary::<ExecuteAnswer>(&response.data.unwrap())? {
+        ExecuteAnswer::MintEdition { token_id } => assert_eq!(token_id, "print/2"),
+        _ => panic!("unexpected answer"),
+    }
+    assert_eq!(
+        chk_bal(&deps.storage, "print/2", &addr.b()).unwrap(),
+        Uint256::from(1u128)
+    );
+    let edition1 = tkn_info_r(&deps.storage).load("print/2".as_bytes())?;
+    assert_eq!(
+        edition1.public_metadata.unwrap().token_uri,
+        Some("public uri".to_string())
+    );
+
+    // second edition overrides its public metadata
+    let msg_mint = ExecuteMsg::MintEdition {
+        parent_token_id: "print".to_string(),
+        recipient: addr.c(),
+        public_metadata: Some(Metadata {
+            token_uri: Some("print 2 uri".to_string()),
+            extension: None,
+        }),
+        private_metadata: None,
+        memo: None,
+        padding: None,
+    };
+    execute(deps.as_mut(), mock_env(), info_a.clone(), msg_mint.clone())?;
+    let edition2 = tkn_info_r(&deps.storage).load("print/3".as_bytes())?;
+    assert_eq!(
+        edition2.public_metadata.unwrap().token_uri,
+        Some("print 2 uri".to_string())
+    );
+    assert_eq!(
+        edition2.private_metadata.unwrap().token_uri,
+        Some("private uri".to_string())
+    );
+
+    // max editions reached
+    let result = execute(deps.as_mut(), mock_env(), info_a.clone(), msg_mint.clone());
+    assert_eq!(
+        extract_error_msg(&result),
+        "all 2 editions of series print have been minted"
+    );
+    let msg_q = QueryMsg::EditionSeries {
+        parent_token_id: "print".to_string(),
+    };
+    match from_binary::<QueryAnswer>(&query(deps.as_ref(), mock_env(), msg_q)?)? {
+        QueryAnswer::EditionSeries(series) => {
+            assert_eq!(series.minted, 2);
+            // the public query does not reveal private metadata
+            assert_eq!(series.token_info.private_metadata, None);
+        }
+        _ => panic!("unexpected answer"),
+    }
+
+    // error: a removed curator cannot mint editions
+    let msg = ExecuteMsg::RemoveCurators {
+        remove_curators: vec![addr.a()],
+        padding: None,
+    };
+    execute(deps.as_mut(), mock_env(), info_a.clone(), msg)?;
+    let result = execute(deps.as_mut(), mock_env(), info_a, msg_mint);
+    assert_eq!(
+        extract_error_msg(&result),
+        "Only curators are allowed to curate token_ids"
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_namespaces() -> StdResult<()> {
+    // init addresses
+    let addr = init_addrs();
+
+    // instantiate
+    let (init_result, mut deps) = init_helper_default();
+    assert_eq!(init_result.unwrap(), Response::default());
+    let info_a = mock_info(addr.a().as_str(), &[]);
+    let msg_add_curators = ExecuteMsg::AddCurators {
+        add_curators: vec![addr.b()],
+        padding: None,
+    };
+    execute(deps.as_mut(), mock_env(), info_a.clone(), msg_add_curators)?;
+
+    // admin reserves `studio/` for addr.b
+    let msg_register = ExecuteMsg::RegisterNamespace {
+        prefix: "studio/".to_string(),
+        owner: addr.b(),
+        padding: None,
+    };
+    let info_b = mock_info(addr.b().as_str(), &[]);
+    let result = execute(
+        deps.as_mut(),
+        mock_env(),
+        info_b.clone(),
+        msg_register.clone(),
+    );
+    assert!(extract_error_msg(&result).contains("This is an admin function"));
+    execute(deps.as_mut(), mock_env(), info_a.clone(), msg_register)?;
+    let msg_overlap = ExecuteMsg::RegisterNamespace {
+        prefix: "studio/games/".to_string(),
+        owner: addr.a(),
+        padding: None,
+    };
+    let result = execute(deps.as_mut(), mock_env(), info_a.clone(), msg_overlap);
+    assert_eq!(
+        extract_error_msg(&result),
+        "namespace studio/games/ overlaps with existing namespace studio/"
+    );
+
+    let curate = |token_id: &str| {
+        let mut curate = CurateTokenId::default();
+        curate.token_info.token_id = token_id.to_string();
+        ExecuteMsg::CurateTokenIds {
+            initial_tokens: vec![curate],
+            memo: None,
+            notify_receivers: None,
+            padding: None,
+        }
+    };
+
+    // other curators cannot curate in the namespace
+    let result = execute(deps.as_mut(), mock_env(), info_a, curate("studio/sword"));
+    assert_eq!(
+        extract_error_msg(&result),
+        "token_id studio/sword is in namespace studio/, which belongs to addr1"
+    );
+
+    // the namespace owner can only curate within its namespaces
+    let result = execute(deps.as_mut(), mock_env(), info_b.clone(), curate("sword"));
+    assert_eq!(
+        extract_error_msg(&result),
+        "token_id sword must be in one of your namespaces"
+    );
+    execute(deps.as_mut(), mock_env(), info_b, curate("studio/sword"))?;
+    assert!(tkn_info_r(&deps.storage)
+        .may_load("studio/sword".as_bytes())?
+        .is_some());
+
+    let msg_q = QueryMsg::Namespaces {};
+    match from_binary::<QueryAnswer>(&query(deps.as_ref(), mock_env(), msg_q)?)? {
+        QueryAnswer::Namespaces { namespaces } => assert_eq!(
+            namespaces,
+            vec![Namespace {
+                prefix: "studio/".to_string(),
+                owner: addr.b(),
+            }]
+        ),
+        _ => panic!("unexpected answer"),
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_curator_quota() -> StdResult<()> {
+    // init addresses
+    let addr = init_addrs();
+
+    // instantiate: addr.a has curated token_id "0"
+    let (init_result, mut deps) = init_helper_default();
+    assert_eq!(init_result.unwrap(), Response::default());
+    let info_a = mock_info(addr.a().as_str(), &[]);
+
+    // quota of 3, including the token_id curated at instantiation
+    let msg_quota = ExecuteMsg::SetCuratorQuota {
+        curator: addr.a(),
+        quota: Some(3),
+        padding: None,
+    };
+    let info_b = mock_info(addr.b().as_str(), &[]);
+    let result = execute(deps.as_mut(), mock_env(), info_b, msg_quota.clone());
+    assert!(extract_error_msg(&result).contains("This is an admin function"));
+    execute(deps.as_mut(), mock_env(), info_a.clone(), msg_quota)?;
+
+    let curate = |token_id: &str| {
+        let mut curate = CurateTokenId::default();
+        curate.token_info.token_id = token_id.to_string();
+        ExecuteMsg::CurateTokenIds {
+            initial_tokens: vec![curate],
+            memo: None,
+            notify_receivers: None,
+            padding: None,
+        }
+    };
+    execute(deps.as_mut(), mock_env(), info_a.clone(), curate("t1"))?;
+    execute(deps.as_mut(), mock_env(), info_a.clone(), curate("t2"))?;
+    let result = execute(deps.as_mut(), mock_env(), info_a, curate("t3"));
+    assert_eq!(
+        extract_error_msg(&result),
+        "curator addr0 has reached its quota of 3 token_ids"
+    );
+
+    // paginated token_ids by curator
+    let msg_q = QueryMsg::TokenIdsByCurator {
+        curator: addr.a(),
+        page: Some(1),
+        page_size: 2,
+    };
+    match from_binary::<QueryAnswer>(&query(deps.as_ref(), mock_env(), msg_q)?)? {
+        QueryAnswer::TokenIdsByCurator {
+            token_ids,
+            total,
+            quota,
+        } => {
+            assert_eq!(token_ids, vec!["t2".to_string()]);
+            assert_eq!(total, 3);
+            assert_eq!(quota, Some(3));
+        }
+        _ => panic!("unexpected answer"),
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_roles() -> StdResult<()> {
+    // init addresses
+    let addr = init_addrs();
+
+    // instantiate: addr.a is the admin
+    let (init_result, mut deps) = init_helper_default();
+    assert_eq!(init_result.unwrap(), Response::default());
+    let info_a = mock_info(addr.a().as_str(), &[]);
+    let info_b = mock_info(addr.b().as_str(), &[]);
+    let info_c = mock_info(addr.c().as_str(), &[]);
+    let info_d = mock_info(addr.d().as_str(), &[]);
+
+    // error: addr.b cannot add curators before it is a curator manager
+    let msg_add_curators = ExecuteMsg::AddCurators {
+        add_curators: vec![addr.c()],
+        padding: None,
+    };
+    let result = execute(
+        deps.as_mut(),
+        mock_env(),
+        info_b.clone(),
+        msg_add_curators.clone(),
+    );
+    assert!(extract_error_msg(&result).contains("curator_manager role"));
+
+    let grant = |role: Role, address: Addr| ExecuteMsg::GrantRole {
+        role,
+        address,
+        padding: None,
+    };
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        info_a.clone(),
+        grant(Role::CuratorManager, addr.b()),
+    )?;
+    execute(deps.as_mut(), mock_env(), info_b.clone(), msg_add_curators)?;
+
+    // error: only admins can grant roles
+    let result = execute(
+        deps.as_mut(),
+        mock_env(),
+        info_b,
+        grant(Role::Pauser, addr.c()),
+    );
+    assert_eq!(extract_error_msg(&result), "This is an admin function");
+
+    // pauser stops the contract, after which only status and role changes are allowed
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        info_a.clone(),
+        grant(Role::Pauser, addr.c()),
+    )?;
+    let msg_status = |level: ContractStatus| ExecuteMsg::SetContractStatus {
+        level,
+        padding: None,
+    };
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        info_c.clone(),
+        msg_status(ContractStatus::StopAll),
+    )?;
+    let msg_trnsf = ExecuteMsg::Transfer {
+        token_id: "0".to_string(),
+        from: addr.a(),
+        recipient: addr.d(),
+        amount: Uint256::from(10u128),
+        memo: None,
+        decoys: None,
+        entropy: None,
+        padding: None,
+    };
+    let result = execute(deps.as_mut(), mock_env(), info_a.clone(), msg_trnsf.clone());
+    assert!(extract_error_msg(&result).contains("contract is stopped"));
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        info_c,
+        msg_status(ContractStatus::NormalRun),
+    )?;
+    execute(deps.as_mut(), mock_env(), info_a.clone(), msg_trnsf)?;
+
+    // metadata managers can change metadata of token_ids they do not mint, as long as the
+    // token_id config lets minters change it
+    let mut curate = CurateTokenId::default();
+    curate.token_info.token_id = "fixed".to_string();
+    curate.token_info.token_config = TknConfig::Fungible {
+        minters: vec![addr.a()],
+        decimals: 0,
+        public_total_supply: true,
+        enable_mint: true,
+        enable_burn: true,
+        minter_may_update_metadata: false,
+        minter_may_burn: false,
+        freezable: false,
+        transfers_locked_until: None,
+    };
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        info_a.clone(),
+        ExecuteMsg::CurateTokenIds {
+            initial_tokens: vec![curate],
+            memo: None,
+            notify_receivers: None,
+            padding: None,
+        },
+    )?;
+    let msg_metadata = ExecuteMsg::ChangeMetadata {
+        token_id: "0".to_string(),
+        public_metadata: Box::new(Some(Metadata {
+            token_uri: Some("managed uri".to_string()),
+            extension: None,
+        })),
+        private_metadata: Box::new(None),
+    };
+    let result = execute(
+        deps.as_mut(),
+        mock_env(),
+        info_d.clone(),
+        msg_metadata.clone(),
+    );
+    assert!(extract_error_msg(&result).contains("unable to change the metadata"));
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        info_a.clone(),
+        grant(Role::MetadataManager, addr.d()),
+    )?;
+    execute(deps.as_mut(), mock_env(), info_d.clone(), msg_metadata)?;
+    assert_eq!(
+        tkn_info_r(&deps.storage)
+            .load("0".as_bytes())?
+            .public_metadata
+            .unwrap()
+            .token_uri,
+        Some("managed uri".to_string())
+    );
+    let result = execute(
+        deps.as_mut(),
+        mock_env(),
+        info_d,
+        ExecuteMsg::ChangeMetadata {
+            token_id: "fixed".to_string(),
+            public_metadata: Box::new(Some(Metadata {
+                token_uri: Some("managed uri".to_string()),
+                extension: None,
+            })),
+            private_metadata: Box::new(None),
+        },
+    );
+    assert_eq!(
+        extract_error_msg(&result),
+        "unable to change the metadata for token_id fixed"
+    );
+
+    // role holders are public
+    let msg_q = QueryMsg::RoleHolders {};
+    match from_binary::<QueryAnswer>(&query(deps.as_ref(), mock_env(), msg_q.clone())?)? {
+        QueryAnswer::RoleHolders {
+            admin,
+            role_holders,
+        } => {
+            assert_eq!(admin, Some(addr.a()));
+            assert_eq!(
+                role_holders,
+                vec![
+                    RoleHolder {
+                        role: Role::CuratorManager,
+                        address: addr.b()
+                    },
+                    RoleHolder {
+                        role: Role::Pauser,
+                        address: addr.c()
+                    },
+                    RoleHolder {
+                        role: Role::MetadataManager,
+                        address: addr.d()
+                    },
+                ]
+            );
+        }
+        _ => panic!("unexpected answer"),
+    }
+
+    // revoking and removing the admin
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        info_a.clone(),
+        ExecuteMsg::RevokeRole {
+            role: Role::Pauser,
+            address: addr.c(),
+            padding: None,
+        },
+    )?;
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        info_a,
+        ExecuteMsg::RemoveAdmin {
+            current_admin: addr.a(),
+            contract_address: mock_env().contract.address,
+            padding: None,
+        },
+    )?;
+    match from_binary::<QueryAnswer>(&query(deps.as_ref(), mock_env(), msg_q)?)? {
+        QueryAnswer::RoleHolders {
+            admin,
+            role_holders,
+        } => {
+            assert_eq!(admin, None);
+            assert!(role_holders.is_empty());
+        }
+        _ => panic!("unexpected answer"),
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_propose_admin() -> StdResult<()> {
+    // init addresses
+    let addr = init_addrs();
+
+    // instantiate: addr.a is the admin
+    let (init_result, mut deps) = init_helper_default();
+    assert_eq!(init_result.unwrap(), Response::default());
+    let info_a = mock_info(addr.a().as_str(), &[]);
+    let info_b = mock_info(addr.b().as_str(), &[]);
+
+    let msg_propose = ExecuteMsg::ProposeAdmin {
+        new_admin: addr.b(),
+        expiration: Some(Expiration::AtHeight(mock_env().block.height + 10)),
+        padding: None,
+    };
+    let result = execute(
+        deps.as_mut(),
+        mock_env(),
+        info_b.clone(),
+        msg_propose.clone(),
+    );
+    assert_eq!(extract_error_msg(&result), "This is an admin function");
+    execute(deps.as_mut(), mock_env(), info_a.clone(), msg_propose)?;
+
+    // pending admin is public
+    match from_binary::<QueryAnswer>(&query(
+        deps.as_ref(),
+        mock_env(),
+        QueryMsg::ContractInfo {},
+    )?)? {
+        QueryAnswer::ContractInfo {
+            admin,
+            pending_admin,
+            ..
+        } => {
+            assert_eq!(admin, Some(addr.a()));
+            assert_eq!(
+                pending_admin,
+                Some(AdminProposal {
+                    address: addr.b(),
+                    expiration: Some(Expiration::AtHeight(mock_env().block.height + 10)),
+                })
+            );
+        }
+        _ => panic!("unexpected answer"),
+    }
+
+    // error: only the proposed admin can accept, and only before the proposal expires
+    let msg_accept = ExecuteMsg::AcceptAdmin { padding: None };
+    let info_c = mock_info(addr.c().as_str(), &[]);
+    let result = execute(deps.as_mut(), mock_env(), info_c, msg_accept.clone());
+    assert_eq!(
+        extract_error_msg(&result),
+        "you have not been proposed as the admin"
+    );
+    let mut env_late = mock_env();
+    env_late.block.height += 10;
+    let result = execute(deps.as_mut(), env_late, info_b.clone(), msg_accept.clone());
+    assert_eq!(extract_error_msg(&result), "the admin proposal has expired");
+
+    // cancelled proposals cannot be accepted
+    let msg_cancel = ExecuteMsg::CancelAdminProposal { padding: None };
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        info_a.clone(),
+        msg_cancel.clone(),
+    )?;
+    let result = execute(
+        deps.as_mut(),
+        mock_env(),
+        info_b.clone(),
+        msg_accept.clone(),
+    );
+    assert_eq!(
+        extract_error_msg(&result),
+        "you have not been proposed as the admin"
+    );
+    let result = execute(deps.as_mut(), mock_env(), info_a.clone(), msg_cancel);
+    assert_eq!(
+        extract_error_msg(&result),
+        "there is no pending admin proposal"
+    );
+
+    // accepting makes addr.b the admin, and addr.a loses its admin rights
+    let msg_propose = ExecuteMsg::ProposeAdmin {
+        new_admin: addr.b(),
+        expiration: None,
+        padding: None,
+    };
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        info_a.clone(),
+        msg_propose.clone(),
+    )?;
+    execute(deps.as_mut(), mock_env(), info_b, msg_accept)?;
+    let config = contr_conf_r(&deps.storage).load()?;
+    assert_eq!(config.admin, Some(addr.b()));
+    assert_eq!(config.pending_admin, None);
+    let result = execute(deps.as_mut(), mock_env(), info_a, msg_propose);
+    assert_eq!(extract_error_msg(&result), "This is an admin function");
+
+    Ok(())
+}
+
+#[test]
+fn test_time_locked_admin_actions() -> StdResult<()> {
+    // init addresses
+    let addr = init_addrs();
+
+    // instantiate with a one hour delay on admin actions
+    let mut deps = mock_dependencies();
+    let info_a = mock_info(addr.a().as_str(), &[]);
+    let init_msg = InstantiateMsg {
+        has_admin: true,
+        admin: None,
+        curators: vec![],
+        initial_tokens: vec![],
+        entropy: "seedentropy".to_string(),
+        payment_tokens: vec![],
+        treasury: None,
+        admin_action_delay: Some(3600),
+    };
+    instantiate(deps.as_mut(), mock_env(), info_a.clone(), init_msg)?;
+
+    // error: time-locked actions cannot be performed directly
+    let msg_add_curators = ExecuteMsg::AddCurators {
+        add_curators: vec![addr.b()],
+        padding: None,
+    };
+    let result = execute(deps.as_mut(), mock_env(), info_a.clone(), msg_add_curators);
+    assert!(extract_error_msg(&result).contains("time-locked"));
+    let msg_grant = ExecuteMsg::GrantRole {
+        role: Role::Pauser,
+        address: addr.d(),
+        padding: None,
+    };
+    let result = execute(deps.as_mut(), mock_env(), info_a.clone(), msg_grant);
+    assert!(extract_error_msg(&result).contains("time-locked"));
+
+    // error: queuing requires the action's role
+    let queue = |action: AdminAction| ExecuteMsg::QueueAdminAction {
+        action,
+        padding: None,
+    };
+    let info_b = mock_info(addr.b().as_str(), &[]);
+    let result = execute(
+        deps.as_mut(),
+        mock_env(),
+        info_b,
+        queue(AdminAction::AddCurators {
+            add_curators: vec![addr.b()],
+        }),
+    );
+    assert!(extract_error_msg(&result).contains("curator_manager role"));
+
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        info_a.clone(),
+        queue(AdminAction::AddCurators {
+            add_curators: vec![addr.b()],
+        }),
+    )?;
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        info_a.clone(),
+        queue(AdminAction::ChangeAdmin {
+            new_admin: addr.c(),
+        }),
+    )?;
+
+    // queued actions are public
+    match from_binary::<QueryAnswer>(&query(
+        deps.as_ref(),
+        mock_env(),
+        QueryMsg::QueuedAdminActions {},
+    )?)? {
+        QueryAnswer::QueuedAdminActions {
+            admin_action_delay,
+            actions,
+        } => {
+            assert_eq!(admin_action_delay, 3600);
+            assert_eq!(
+                actions.iter().map(|a| a.id).collect::<Vec<u64>>(),
+                vec![0, 1]
+            );
+            assert_eq!(
+                actions[0].executable_at,
+                mock_env().block.time.seconds() + 3600
+            );
+        }
+        _ => panic!("unexpected answer"),
+    }
+
+    // error: cannot execute before the delay has passed
+    let msg_exec = |action_id: u64| ExecuteMsg::ExecuteAdminAction {
+        action_id,
+        padding: None,
+    };
+    let result = execute(deps.as_mut(), mock_env(), info_a.clone(), msg_exec(0));
+    assert!(extract_error_msg(&result).contains("admin action 0 cannot be executed until"));
+
+    // roles are time-locked too
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        info_a.clone(),
+        queue(AdminAction::GrantRole {
+            role: Role::Pauser,
+            address: addr.d(),
+        }),
+    )?;
+
+    // cancel the admin change, and execute the curator addition after the delay
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        info_a.clone(),
+        ExecuteMsg::CancelAdminAction {
+            action_id: 1,
+            padding: None,
+        },
+    )?;
+    let mut env_later = mock_env();
+    env_later.block.time = env_later.block.time.plus_seconds(3600);
+    execute(
+        deps.as_mut(),
+        env_later.clone(),
+        info_a.clone(),
+        msg_exec(0),
+    )?;
+    let result = execute(
+        deps.as_mut(),
+        env_later.clone(),
+        info_a.clone(),
+        msg_exec(1),
+    );
+    assert_eq!(extract_error_msg(&result), "admin action 1 does not exist");
+    execute(deps.as_mut(), env_later, info_a.clone(), msg_exec(2))?;
+
+    let config = contr_conf_r(&deps.storage).load()?;
+    assert_eq!(config.curators, vec![addr.b()]);
+    assert_eq!(config.admin, Some(addr.a()));
+    assert_eq!(
+        config.roles,
+        vec![RoleHolder {
+            role: Role::Pauser,
+            address: addr.d(),
+        }]
+    );
+    assert!(admin_actions_r(&deps.storage).load()?.is_empty());
+
+    // error: a delay that overflows the block time cannot be queued
+    let mut deps = mock_dependencies();
+    let init_msg = InstantiateMsg {
+        has_admin: true,
+        admin: None,
+        curators: vec![],
+        initial_tokens: vec![],
+        entropy: "seedentropy".to_string(),
+        payment_tokens: vec![],
+        treasury: None,
+        admin_action_delay: Some(u64::MAX),
+    };
+    instantiate(deps.as_mut(), mock_env(), info_a.clone(), init_msg)?;
+    let result = execute(
+        deps.as_mut(),
+        mock_env(),
+        info_a,
+        queue(AdminAction::ChangeAdmin {
+            new_admin: addr.c(),
+        }),
+    );
+    assert_eq!(extract_error_msg(&result), "admin_action_delay is too long");
+
+    Ok(())
+}
+
+#[test]
+fn test_freeze() -> StdResult<()> {
+    // init addresses
+    let addr = init_addrs();
+
+    // instantiate
+    let (init_result, mut deps) = init_helper_default();
+    assert_eq!(init_result.unwrap(), Response::default());
+    let info_a = mock_info(addr.a().as_str(), &[]);
+    let info_b = mock_info(addr.b().as_str(), &[]);
+
+    // addr.a curates a freezable token_id, with 100 tokens for addr.b
+    let mut curate = CurateTokenId::default();
+    curate.token_info.token_id = "regulated".to_string();
+    curate.token_info.token_config = TknConfig::Fungible {
+        minters: vec![addr.a()],
+        decimals: 0,
+        public_total_supply: true,
+        enable_mint: true,
+        enable_burn: true,
+        minter_may_update_metadata: false,
+        minter_may_burn: false,
+        freezable: true,
+        transfers_locked_until: None,
+    };
+    curate.balances = vec![TokenIdBalance {
+        address: addr.b(),
+        amount: Uint256::from(100u128),
+    }];
+    let msg = ExecuteMsg::CurateTokenIds {
+        initial_tokens: vec![curate],
+        memo: None,
+        notify_receivers: None,
+        padding: None,
+    };
+    execute(deps.as_mut(), mock_env(), info_a.clone(), msg)?;
+
+    // error: only minters and the curator can freeze, and only freezable token_ids
+    let freeze = |token_id: &str| ExecuteMsg::Freeze {
+        token_id: token_id.to_string(),
+        address: addr.b(),
+        padding: None,
+    };
+    let result = execute(
+        deps.as_mut(),
+        mock_env(),
+        info_b.clone(),
+        freeze("regulated"),
+    );
+    assert!(extract_error_msg(&result).contains("Only minters and the curator"));
+    let result = execute(deps.as_mut(), mock_env(), info_a.clone(), freeze("0"));
+    assert_eq!(extract_error_msg(&result), "token_id 0 is not freezable");
+
+    // error: the contract's own balances, eg: escrowed tokens, cannot be frozen
+    let msg_freeze_contract = ExecuteMsg::Freeze {
+        token_id: "regulated".to_string(),
+        address: mock_env().contract.address,
+        padding: None,
+    };
+    let result = execute(
+        deps.as_mut(),
+        mock_env(),
+        info_a.clone(),
+        msg_freeze_contract,
+    );
+    assert_eq!(
+        extract_error_msg(&result),
+        "the contract's own balances cannot be frozen"
+    );
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        info_a.clone(),
+        freeze("regulated"),
+    )?;
+
+    // freeze is recorded in addr.b's history
+    let deps_ref = deps.as_ref();
+    let addr_b_canon = deps_ref.api.addr_canonicalize(addr.b().as_str())?;
+    let (txs, _) = txhistory::get_txs(deps_ref.api, deps_ref.storage, &addr_b_canon, 0, 1)?;
+    assert!(matches!(
+        &txs[0].action,
+        txhistory::TxAction::Freeze { frozen: true, frozen_by, .. } if frozen_by == &addr.a()
+    ));
+
+    // error: frozen balances cannot be transferred or burnt
+    let msg_trnsf = ExecuteMsg::Transfer {
+        token_id: "regulated".to_string(),
+        from: addr.b(),
+        recipient: addr.c(),
+        amount: Uint256::from(10u128),
+        memo: None,
+        decoys: None,
+        entropy: None,
+        padding: None,
+    };
+    let result = execute(deps.as_mut(), mock_env(), info_b.clone(), msg_trnsf.clone());
+    assert_eq!(
+        extract_error_msg(&result),
+        "addr1's balance of token_id regulated is frozen"
+    );
+    let msg_burn = ExecuteMsg::BurnTokens {
+        burn_tokens: vec![TokenAmount {
+            token_id: "regulated".to_string(),
+            balances: vec![TokenIdBalance {
+                address: addr.b(),
+                amount: Uint256::from(10u128),
+            }],
+        }],
+        memo: None,
+        padding: None,
+    };
+    let result = execute(deps.as_mut(), mock_env(), info_b.clone(), msg_burn);
+    assert!(extract_error_msg(&result).contains("is frozen"));
+
+    // error: frozen balances cannot be locked in the contract, eg: escrowed for an accomplice
+    let msg_escrow = ExecuteMsg::CreateEscrow {
+        token_id: "regulated".to_string(),
+        amount: Uint256::from(100u128),
+        beneficiary: addr.c(),
+        release: Expiration::AtHeight(mock_env().block.height + 1),
+        clawback: None,
+        memo: None,
+        padding: None,
+    };
+    let result = execute(deps.as_mut(), mock_env(), info_b.clone(), msg_escrow);
+    assert!(extract_error_msg(&result).contains("is frozen"));
+    let msg_swap = ExecuteMsg::CreateSwapOffer {
+        offer_token_id: "regulated".to_string(),
+        offer_amount: Uint256::from(100u128),
+        ask_token_id: "0".to_string(),
+        ask_amount: Uint256::from(1u128),
+        counterparty: None,
+        expiration: None,
+        padding: None,
+    };
+    let result = execute(deps.as_mut(), mock_env(), info_b.clone(), msg_swap);
+    assert!(extract_error_msg(&result).contains("is frozen"));
+    assert_eq!(
+        chk_bal(&deps.storage, "regulated", &addr.b()).unwrap(),
+        Uint256::from(100u128)
+    );
+
+    // unfrozen balances can move again
+    let msg_unfreeze = ExecuteMsg::Unfreeze {
+        token_id: "regulated".to_string(),
+        address: addr.b(),
+        padding: None,
+    };
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        info_a.clone(),
+        msg_unfreeze.clone(),
+    )?;
+    let result = execute(deps.as_mut(), mock_env(), info_a, msg_unfreeze);
+    assert!(extract_error_msg(&result).contains("is already unfrozen"));
+    execute(deps.as_mut(), mock_env(), info_b, msg_trnsf)?;
+    assert_eq!(
+        chk_bal(&deps.storage, "regulated", &addr.c()).unwrap(),
+        Uint256::from(10u128)
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_transfer_policy() -> StdResult<()> {
+    // init addresses
+    let addr = init_addrs();
+
+    // instantiate: addr.a curated token_id "0" and has 1000 tokens
+    let (init_result, mut deps) = init_helper_default();
+    assert_eq!(init_result.unwrap(), Response::default());
+    let info_a = mock_info(addr.a().as_str(), &[]);
+
+    let set_policy = |policy: TransferPolicy| ExecuteMsg::SetTransferPolicy {
+        token_id: "0".to_string(),
+        policy,
+        padding: None,
+    };
+    let info_b = mock_info(addr.b().as_str(), &[]);
+    let result = execute(
+        deps.as_mut(),
+        mock_env(),
+        info_b,
+        set_policy(TransferPolicy::Allowlist),
+    );
+    assert_eq!(
+        extract_error_msg(&result),
+        "only the curator of token_id 0 can change its transfer policy"
+    );
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        info_a.clone(),
+        set_policy(TransferPolicy::Allowlist),
+    )?;
+
+    // allowlist applies to both the sender and the recipient
+    let transfer = |amount: u128| ExecuteMsg::Transfer {
+        token_id: "0".to_string(),
+        from: addr.a(),
+        recipient: addr.b(),
+        amount: Uint256::from(amount),
+        memo: None,
+        decoys: None,
+        entropy: None,
+        padding: None,
+    };
+    let result = execute(deps.as_mut(), mock_env(), info_a.clone(), transfer(10));
+    assert_eq!(
+        extract_error_msg(&result),
+        "addr0 is not on the allowlist of token_id 0"
+    );
+    let msg_allowlist = ExecuteMsg::AddToAllowlist {
+        token_id: "0".to_string(),
+        addresses: vec![addr.a()],
+        padding: None,
+    };
+    execute(deps.as_mut(), mock_env(), info_a.clone(), msg_allowlist)?;
+    let result = execute(deps.as_mut(), mock_env(), info_a.clone(), transfer(10));
+    assert_eq!(
+        extract_error_msg(&result),
+        "addr1 is not on the allowlist of token_id 0"
+    );
+
+    let vks = generate_viewing_keys(&mut deps, mock_env(), info_a.clone(), vec![addr.a()])?;
+    let msg_q = QueryMsg::TransferAllowed {
+        token_id: "0".to_string(),
+        from: addr.a(),
+        key: vks.a(),
+        recipient: addr.b(),
+        amount: Uint256::from(10u128),
+    };
+    match from_binary::<QueryAnswer>(&query(deps.as_ref(), mock_env(), msg_q.clone())?)? {
+        QueryAnswer::TransferAllowed {
+            policy,
+            allowed,
+            reason,
+        } => {
+            assert_eq!(policy, TransferPolicy::Allowlist);
+            assert!(!allowed);
+            assert_eq!(
+                reason,
+                Some("addr1 is not on the allowlist of token_id 0".to_string())
+            );
+        }
+        _ => panic!("unexpected answer"),
+    }
+
+    // the query requires the viewing key of `from`, and the token_id must exist
+    let msg_q_wrong_key = QueryMsg::TransferAllowed {
+        token_id: "0".to_string(),
+        from: addr.a(),
+        key: "wrong key".to_string(),
+        recipient: addr.b(),
+        amount: Uint256::from(10u128),
+    };
+    match from_binary::<QueryAnswer>(&query(deps.as_ref(), mock_env(), msg_q_wrong_key)?)? {
+        QueryAnswer::ViewingKeyError { .. } => (),
+        _ => panic!("unexpected answer"),
+    }
+    let msg_q_no_token = QueryMsg::TransferAllowed {
+        token_id: "none".to_string(),
+        from: addr.a(),
+        key: vks.a(),
+        recipient: addr.b(),
+        amount: Uint256::from(10u128),
+    };
+    let result = query(deps.as_ref(), mock_env(), msg_q_no_token);
+    assert_eq!(extract_error_msg(&result), "token_id none does not exist");
+
+    let msg_allowlist = ExecuteMsg::AddToAllowlist {
+        token_id: "0".to_string(),
+        addresses: vec![addr.b()],
+        padding: None,
+    };
+    execute(deps.as_mut(), mock_env(), info_a.clone(), msg_allowlist)?;
+    execute(deps.as_mut(), mock_env(), info_a.clone(), transfer(10))?;
+
+    // curator approvals are used up by transfers
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        info_a.clone(),
+        set_policy(TransferPolicy::CuratorApproval),
+    )?;
+    let result = execute(deps.as_mut(), mock_env(), info_a.clone(), transfer(10));
+    assert!(extract_error_msg(&result).contains("has not approved this transfer"));
+    let msg_approve = ExecuteMsg::ApproveTransfer {
+        token_id: "0".to_string(),
+        from: addr.a(),
+        recipient: addr.b(),
+        amount: Uint256::from(15u128),
+        padding: None,
+    };
+    execute(deps.as_mut(), mock_env(), info_a.clone(), msg_approve)?;
+    match from_binary::<QueryAnswer>(&query(deps.as_ref(), mock_env(), msg_q)?)? {
+        QueryAnswer::TransferAllowed { allowed, .. } => assert!(allowed),
+        _ => panic!("unexpected answer"),
+    }
+    execute(deps.as_mut(), mock_env(), info_a.clone(), transfer(10))?;
+    let result = execute(deps.as_mut(), mock_env(), info_a.clone(), transfer(10));
+    assert!(extract_error_msg(&result).contains("has not approved this transfer"));
+    execute(deps.as_mut(), mock_env(), info_a.clone(), transfer(5))?;
+    assert_eq!(
+        chk_bal(&deps.storage, "0", &addr.b()).unwrap(),
+        Uint256::from(25u128)
+    );
+
+    // tokens locked in the contract need approval for the address they will be released to
+    let release_height = mock_env().block.height + 1;
+    let msg_escrow = ExecuteMsg::CreateEscrow {
+        token_id: "0".to_string(),
+        amount: Uint256::from(20u128),
+        beneficiary: addr.c(),
+        release: Expiration::AtHeight(release_height),
+        clawback: None,
+        memo: None,
+        padding: None,
+    };
+    let result = execute(
+        deps.as_mut(),
+        mock_env(),
+        info_a.clone(),
+        msg_escrow.clone(),
+    );
+    assert_eq!(
+        extract_error_msg(&result),
+        "the curator of token_id 0 has not approved this transfer from addr0 to addr2"
+    );
+    let msg_swap = ExecuteMsg::CreateSwapOffer {
+        offer_token_id: "0".to_string(),
+        offer_amount: Uint256::from(20u128),
+        ask_token_id: "0".to_string(),
+        ask_amount: Uint256::from(1u128),
+        counterparty: None,
+        expiration: None,
+        padding: None,
+    };
+    let result = execute(deps.as_mut(), mock_env(), info_a.clone(), msg_swap);
+    assert_eq!(
+        extract_error_msg(&result),
+        "transfers of token_id 0 need curator approval, so they must have a known recipient"
+    );
+    let msg_approve = ExecuteMsg::ApproveTransfer {
+        token_id: "0".to_string(),
+        from: addr.a(),
+        recipient: addr.c(),
+        amount: Uint256::from(20u128),
+        padding: None,
+    };
+    execute(deps.as_mut(), mock_env(), info_a.clone(), msg_approve)?;
+    execute(deps.as_mut(), mock_env(), info_a, msg_escrow)?;
+    let mut env_released = mock_env();
+    env_released.block.height = release_height;
+    let msg_claim = ExecuteMsg::ClaimEscrow {
+        escrow_id: 0,
+        padding: None,
+    };
+    let info_c = mock_info(addr.c().as_str(), &[]);
+    execute(deps.as_mut(), env_released, info_c, msg_claim)?;
+    assert_eq!(
+        chk_bal(&deps.storage, "0", &addr.c()).unwrap(),
+        Uint256::from(20u128)
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_transfers_locked_until() -> StdResult<()> {
+    // init addresses
+    let addr = init_addrs();
+
+    // instantiate
+    let (init_result, mut deps) = init_helper_default();
+    assert_eq!(init_result.unwrap(), Response::default());
+    let info_a = mock_info(addr.a().as_str(), &[]);
+    let launch = mock_env().block.time.seconds() + 1000;
+
+    // addr.a curates an allocation with 100 tokens for addr.a that cannot move until launch
+    let mut curate = CurateTokenId::default();
+    curate.token_info.token_id = "ido".to_string();
+    curate.token_info.token_config = TknConfig::Fungible {
+        minters: vec![addr.a()],
+        decimals: 0,
+        public_total_supply: true,
+        enable_mint: true,
+        enable_burn: true,
+        minter_may_update_metadata: false,
+        minter_may_burn: false,
+        freezable: false,
+        transfers_locked_until: Some(Expiration::AtTime(launch)),
+    };
+    curate.balances = vec![TokenIdBalance {
+        address: addr.a(),
+        amount: Uint256::from(100u128),
+    }];
+    let msg = ExecuteMsg::CurateTokenIds {
+        initial_tokens: vec![curate],
+        memo: None,
+        notify_receivers: None,
+        padding: None,
+    };
+    execute(deps.as_mut(), mock_env(), info_a.clone(), msg)?;
+
+    let msg_q = QueryMsg::TokenIdPublicInfo {
+        token_id: "ido".to_string(),
+    };
+    match from_binary::<QueryAnswer>(&query(deps.as_ref(), mock_env(), msg_q.clone())?)? {
+        QueryAnswer::TokenIdPublicInfo {
+            transfers_locked, ..
+        } => assert!(transfers_locked),
+        _ => panic!("unexpected answer"),
+    }
+
+    // error: transfers are locked, but minting and burning are not
+    let msg_trnsf = ExecuteMsg::Transfer {
+        token_id: "ido".to_string(),
+        from: addr.a(),
+        recipient: addr.b(),
+        amount: Uint256::from(10u128),
+        memo: None,
+        decoys: None,
+        entropy: None,
+        padding: None,
+    };
+    let result = execute(deps.as_mut(), mock_env(), info_a.clone(), msg_trnsf.clone());
+    assert!(extract_error_msg(&result).contains("transfers of token_id ido are locked until"));
+
+    // error: locked tokens cannot be escrowed either, and an escrow cannot be released immediately
+    let msg_escrow = |release: Expiration| ExecuteMsg::CreateEscrow {
+        token_id: "ido".to_string(),
+        amount: Uint256::from(10u128),
+        beneficiary: addr.b(),
+        release,
+        clawback: None,
+        memo: None,
+        padding: None,
+    };
+    let result = execute(
+        deps.as_mut(),
+        mock_env(),
+        info_a.clone(),
+        msg_escrow(Expiration::AtHeight(0)),
+    );
+    assert_eq!(
+        extract_error_msg(&result),
+        "escrow release has already passed: expiration height: 0"
+    );
+    let result = execute(
+        deps.as_mut(),
+        mock_env(),
+        info_a.clone(),
+        msg_escrow(Expiration::AtTime(launch)),
+    );
+    assert!(extract_error_msg(&result).contains("transfers of token_id ido are locked until"));
+    let token_amount = |address: Addr| TokenAmount {
+        token_id: "ido".to_string(),
+        balances: vec![TokenIdBalance {
+            address,
+            amount: Uint256::from(10u128),
+        }],
+    };
+    let msg_mint = ExecuteMsg::MintTokens {
+        mint_tokens: vec![token_amount(addr.b())],
+        memo: None,
+        notify_receivers: None,
+        padding: None,
+    };
+    execute(deps.as_mut(), mock_env(), info_a.clone(), msg_mint)?;
+    let msg_burn = ExecuteMsg::BurnTokens {
+        burn_tokens: vec![token_amount(addr.a())],
+        memo: None,
+        padding: None,
+    };
+    execute(deps.as_mut(), mock_env(), info_a.clone(), msg_burn)?;
+
+    // transfers are allowed after launch
+    let mut env_launch = mock_env();
+    env_launch.block.time = Timestamp::from_seconds(launch);
+    execute(deps.as_mut(), env_launch, info_a, msg_trnsf)?;
+    assert_eq!(
+        chk_bal(&deps.storage, "ido", &addr.b()).unwrap(),
+        Uint256::from(20u128)
+    );
+    match from_binary::<QueryAnswer>(&query(deps.as_ref(), mock_env(), msg_q)?)? {
+        QueryAnswer::TokenIdPublicInfo {
+            transfers_locked, ..
+        } => assert!(!transfers_locked),
+        _ => panic!("unexpected answer"),
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_freeze_metadata() -> StdResult<()> {
+    // init addresses
+    let addr = init_addrs();
+
+    // instantiate: addr.a is the minter of token_id "0", which lets minters update metadata
+    let (init_result, mut deps) = init_helper_default();
+    assert_eq!(init_result.unwrap(), Response::default());
+    let info_a = mock_info(addr.a().as_str(), &[]);
+
+    let msg_freeze = ExecuteMsg::FreezeMetadata {
+        token_id: "0".to_string(),
+        padding: None,
+    };
+    let info_b = mock_info(addr.b().as_str(), &[]);
+    let result = execute(deps.as_mut(), mock_env(), info_b, msg_freeze.clone());
+    assert!(extract_error_msg(&result).contains("Only minters and the curator"));
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        info_a.clone(),
+        msg_freeze.clone(),
+    )?;
+    let result = execute(deps.as_mut(), mock_env(), info_a.clone(), msg_freeze);
+    assert_eq!(
+        extract_error_msg(&result),
+        "the metadata of token_id 0 is already frozen"
+    );
+
+    // error: frozen metadata cannot be changed
+    let msg_metadata = ExecuteMsg::ChangeMetadata {
+        token_id: "0".to_string(),
+        public_metadata: Box::new(Some(Metadata {
+            token_uri: Some("new uri".to_string()),
+            extension: None,
+        })),
+        private_metadata: Box::new(None),
+    };
+    let result = execute(deps.as_mut(), mock_env(), info_a, msg_metadata);
+    assert_eq!(
+        extract_error_msg(&result),
+        "the metadata of token_id 0 is frozen"
+    );
+
+    // frozen metadata is public
+    let msg_q = QueryMsg::TokenIdPublicInfo {
+        token_id: "0".to_string(),
+    };
+    match from_binary::<QueryAnswer>(&query(deps.as_ref(), mock_env(), msg_q)?)? {
+        QueryAnswer::TokenIdPublicInfo { token_id_info, .. } => {
+            assert!(token_id_info.metadata_frozen);
+            assert_ne!(
+                token_id_info.public_metadata.unwrap().token_uri,
+                Some("new uri".to_string())
+            );
+        }
+        _ => panic!("unexpected answer"),
+    }
+
+    Ok(())
+}
@@ -6,6 +6,7 @@ use super::testhelpers::*;
 use super::super::{
     handles::*,
     msg::*,
+    notifications::NOTIFICATION_BLOCK_SIZE,
     queries::*,
     state::{expiration::*, permissions::*, state_structs::*, txhistory::*},
 };
@@ -34,6 +35,7 @@ fn test_q_init() -> StdResult<()> {
             admin,
             curators,
             all_token_ids,
+            ..
         } => {
             assert_eq!(&admin.unwrap(), &addr0);
             assert_eq!(&curators, &vec![addr0.clone()]);
@@ -86,6 +88,7 @@ fn test_query_tokenid_public_info_sanity() -> StdResult<()> {
             token_id_info,
             total_supply,
             owner,
+            ..
         } => {
             assert!(serde_json::to_string(&token_id_info)
                 .unwrap()
@@ -113,6 +116,8 @@ fn test_query_registered_code_hash() -> StdResult<()> {
     let info = mock_info(addr.a().as_str(), &[]);
     let msg_reg_receive = ExecuteMsg::RegisterReceive {
         code_hash: addr.a_hash(),
+        token_ids: None,
+        reject_unlisted_token_ids: None,
         padding: None,
     };
     execute(deps.as_mut(), mock_env(), info, msg_reg_receive)?;
@@ -120,7 +125,9 @@ fn test_query_registered_code_hash() -> StdResult<()> {
     let msg_q_code_hash = QueryMsg::RegisteredCodeHash { contract: addr.a() };
     let q_answer = from_binary::<QueryAnswer>(&query(deps.as_ref(), mock_env(), msg_q_code_hash)?)?;
     match q_answer {
-        QueryAnswer::RegisteredCodeHash { code_hash } => assert_eq!(code_hash, Some(addr.a_hash())),
+        QueryAnswer::RegisteredCodeHash { code_hash, .. } => {
+            assert_eq!(code_hash, Some(addr.a_hash()))
+        }
         _ => panic!("query error"),
     }
 
@@ -191,6 +198,8 @@ fn test_query_balance() -> StdResult<()> {
         view_private_metadata_expiry: None,
         transfer: Some(Uint256::from(1000u128)),
         transfer_expiry: None,
+        burn: None,
+        burn_expiry: None,
         padding: None,
     };
     execute(deps.as_mut(), mock_env(), info.clone(), msg_perm_1_wrong)?;
@@ -209,6 +218,8 @@ fn test_query_balance() -> StdResult<()> {
         view_private_metadata_expiry: None,
         transfer: None,
         transfer_expiry: None,
+        burn: None,
+        burn_expiry: None,
         padding: None,
     };
     execute(deps.as_mut(), env.clone(), info.clone(), msg_perm_1)?;
@@ -345,6 +356,7 @@ fn test_query_all_balance() -> StdResult<()> {
             }],
         }],
         memo: None,
+        notify_receivers: None,
         padding: None,
     };
     info.sender = addr.a();
@@ -380,6 +392,7 @@ fn test_query_all_balance() -> StdResult<()> {
     let msg_curate = ExecuteMsg::CurateTokenIds {
         initial_tokens: vec![curate0, curate1, curate2, curate3],
         memo: None,
+        notify_receivers: None,
         padding: None,
     };
     info.sender = addr.a();
@@ -523,6 +536,8 @@ fn test_query_transaction_history() -> StdResult<()> {
         recipient: addr.b(),
         amount: Uint256::from(10u128),
         memo: None,
+        decoys: None,
+        entropy: None,
         padding: None,
     };
     info.sender = addr.a();
@@ -623,6 +638,8 @@ fn test_query_permission() -> StdResult<()> {
         view_private_metadata_expiry: None,
         transfer: Some(Uint256::from(10u128)),
         transfer_expiry: None,
+        burn: None,
+        burn_expiry: None,
         padding: None,
     };
     execute(deps.as_mut(), mock_env(), info.clone(), msg0_perm_1)?;
@@ -664,6 +681,8 @@ fn test_query_permission() -> StdResult<()> {
                 view_pr_metadata_exp: Expiration::default(),
                 trfer_allowance_perm: Uint256::from(10u128),
                 trfer_allowance_exp: Expiration::default(),
+                burn_allowance_perm: Uint256::from(0u128),
+                burn_allowance_exp: Expiration::default(),
             }
         ),
         _ => panic!("query error"),
@@ -696,6 +715,8 @@ fn test_query_permission() -> StdResult<()> {
                 view_pr_metadata_exp: Expiration::default(),
                 trfer_allowance_perm: Uint256::from(10u128),
                 trfer_allowance_exp: Expiration::default(),
+                burn_allowance_perm: Uint256::from(0u128),
+                burn_allowance_exp: Expiration::default(),
             }
         ),
         _ => panic!("query error"),
@@ -729,6 +750,8 @@ fn test_query_all_permissions() -> StdResult<()> {
         view_private_metadata_expiry: None,
         transfer: None,
         transfer_expiry: None,
+        burn: None,
+        burn_expiry: None,
         padding: None,
     };
     execute(deps.as_mut(), mock_env(), info.clone(), msg0_perm_b)?;
@@ -743,6 +766,8 @@ fn test_query_all_permissions() -> StdResult<()> {
         view_private_metadata_expiry: None,
         transfer: None,
         transfer_expiry: None,
+        burn: None,
+        burn_expiry: None,
         padding: None,
     };
     execute(deps.as_mut(), mock_env(), info.clone(), msg0_perm_c)?;
@@ -757,6 +782,8 @@ fn test_query_all_permissions() -> StdResult<()> {
         view_private_metadata_expiry: None,
         transfer: Some(Uint256::from(100u128)),
         transfer_expiry: Some(Expiration::AtHeight(100u64)),
+        burn: None,
+        burn_expiry: None,
         padding: None,
     };
     execute(deps.as_mut(), mock_env(), info, msg0_perm_d)?;
@@ -905,3 +932,367 @@ fn test_query_tokenid_private_info_sanity() -> StdResult<()> {
 
     Ok(())
 }
+
+#[test]
+fn test_query_channel_info() -> StdResult<()> {
+    // init addresses
+    let addr = init_addrs();
+
+    // instantiate
+    let (_init_result, mut deps) = init_helper_default();
+
+    // generate viewing keys
+    let info = mock_info(addr.a().as_str(), &[]);
+    let vks = generate_viewing_keys(
+        &mut deps,
+        mock_env(),
+        info,
+        vec![addr.a(), addr.b(), addr.c()],
+    )?;
+
+    // list channels
+    let q_answer = from_binary::<QueryAnswer>(&query(
+        deps.as_ref(),
+        mock_env(),
+        QueryMsg::ListChannels {},
+    )?)?;
+    match q_answer {
+        QueryAnswer::ListChannels { channels } => {
+            assert_eq!(channels, vec!["transfer", "mint", "burn", "allowance"])
+        }
+        _ => panic!("query error"),
+    }
+
+    // unknown channel
+    let msg_q_channel = QueryMsg::ChannelInfo {
+        channels: vec!["unknown".to_string()],
+        viewer: addr.b(),
+        key: vks.b(),
+    };
+    let q_result = query(deps.as_ref(), mock_env(), msg_q_channel);
+    assert_eq!(
+        extract_error_msg(&q_result),
+        "channel unknown does not exist"
+    );
+
+    // before any transfer, counter == 0
+    let msg_q_channel = QueryMsg::ChannelInfo {
+        channels: vec!["transfer".to_string()],
+        viewer: addr.b(),
+        key: vks.b(),
+    };
+    let q_answer =
+        from_binary::<QueryAnswer>(&query(deps.as_ref(), mock_env(), msg_q_channel.clone())?)?;
+    let channel_info_0 = match q_answer {
+        QueryAnswer::ChannelInfo { channels } => channels[0].clone(),
+        _ => panic!("query error"),
+    };
+    assert_eq!(channel_info_0.counter.u64(), 0);
+
+    // addr.a transfers to addr.b: notification is emitted with the expected id
+    let msg = ExecuteMsg::Transfer {
+        token_id: "0".to_string(),
+        from: addr.a(),
+        recipient: addr.b(),
+        amount: Uint256::from(100u128),
+        memo: None,
+        decoys: None,
+        entropy: None,
+        padding: None,
+    };
+    let info = mock_info(addr.a().as_str(), &[]);
+    let response = execute(deps.as_mut(), mock_env(), info, msg)?;
+    let notification = response
+        .attributes
+        .iter()
+        .find(|attr| attr.key.starts_with("snip52:"))
+        .unwrap();
+    assert_eq!(
+        notification.key,
+        format!("snip52:{}", channel_info_0.next_id.to_base64())
+    );
+    assert!(!notification.encrypted);
+
+    // counter has incremented, and seed is unchanged
+    let q_answer = from_binary::<QueryAnswer>(&query(deps.as_ref(), mock_env(), msg_q_channel)?)?;
+    let channel_info_1 = match q_answer {
+        QueryAnswer::ChannelInfo { channels } => channels[0].clone(),
+        _ => panic!("query error"),
+    };
+    assert_eq!(channel_info_1.counter.u64(), 1);
+    assert_eq!(channel_info_1.seed, channel_info_0.seed);
+    assert_ne!(channel_info_1.next_id, channel_info_0.next_id);
+
+    // other addresses cannot query addr.b's channel info with their own viewing key
+    let msg_q_channel = QueryMsg::ChannelInfo {
+        channels: vec!["transfer".to_string()],
+        viewer: addr.b(),
+        key: vks.c(),
+    };
+    let q_answer = from_binary::<QueryAnswer>(&query(deps.as_ref(), mock_env(), msg_q_channel)?)?;
+    assert!(matches!(q_answer, QueryAnswer::ViewingKeyError { .. }));
+
+    Ok(())
+}
+
+#[test]
+fn test_notification_payloads() -> StdResult<()> {
+    // init addresses
+    let addr = init_addrs();
+
+    // instantiate
+    let (_init_result, mut deps) = init_helper_default();
+
+    // expected CBOR payloads, zero-padded to the block size
+    let api = deps.as_ref().api;
+    let (canonical_a, canonical_b, canonical_c) = (
+        api.addr_canonicalize(addr.a().as_str())?,
+        api.addr_canonicalize(addr.b().as_str())?,
+        api.addr_canonicalize(addr.c().as_str())?,
+    );
+    let bstr = |bytes: &[u8]| {
+        let mut buf = match bytes.len() {
+            len @ 0..=23 => vec![0x40 | len as u8],
+            len => vec![0x58, len as u8],
+        };
+        buf.extend_from_slice(bytes);
+        buf
+    };
+    let biguint = |value: u8| match value {
+        0 => vec![0xc2, 0x40],
+        _ => vec![0xc2, 0x41, value],
+    };
+    let token_id_0 = vec![0x61, b'0'];
+    let padded = |mut payload: Vec<u8>| {
+        payload.resize(NOTIFICATION_BLOCK_SIZE, 0);
+        payload
+    };
+
+    // addr.a mints 10 tokens to addr.b
+    let info_a = mock_info(addr.a().as_str(), &[]);
+    let msg = ExecuteMsg::MintTokens {
+        mint_tokens: vec![TokenAmount {
+            token_id: "0".to_string(),
+            balances: vec![TokenIdBalance {
+                address: addr.b(),
+                amount: Uint256::from(10u128),
+            }],
+        }],
+        memo: None,
+        notify_receivers: None,
+        padding: None,
+    };
+    let response = execute(deps.as_mut(), mock_env(), info_a, msg)?;
+    let expected = [
+        vec![0x83],
+        token_id_0.clone(),
+        biguint(10),
+        bstr(canonical_a.as_slice()),
+    ]
+    .concat();
+    assert_eq!(
+        decrypt_notification(&deps, &response, &addr.b(), "mint", 0)?,
+        padded(expected)
+    );
+
+    // addr.b grants addr.c a transfer allowance of 5 and a burn allowance of 3
+    let info_b = mock_info(addr.b().as_str(), &[]);
+    let msg = ExecuteMsg::GivePermission {
+        allowed_address: addr.c(),
+        token_id: "0".to_string(),
+        view_balance: None,
+        view_balance_expiry: None,
+        view_private_metadata: None,
+        view_private_metadata_expiry: None,
+        transfer: Some(Uint256::from(5u128)),
+        transfer_expiry: None,
+        burn: Some(Uint256::from(3u128)),
+        burn_expiry: None,
+        padding: None,
+    };
+    let response = execute(deps.as_mut(), mock_env(), info_b.clone(), msg)?;
+    let allowance = |transfer: u8, burn: u8| {
+        [
+            vec![0x84],
+            token_id_0.clone(),
+            bstr(canonical_b.as_slice()),
+            biguint(transfer),
+            biguint(burn),
+        ]
+        .concat()
+    };
+    assert_eq!(
+        decrypt_notification(&deps, &response, &addr.c(), "allowance", 0)?,
+        padded(allowance(5, 3))
+    );
+
+    // addr.c burns 2 of addr.b's tokens
+    let info_c = mock_info(addr.c().as_str(), &[]);
+    let msg = ExecuteMsg::BurnTokens {
+        burn_tokens: vec![TokenAmount {
+            token_id: "0".to_string(),
+            balances: vec![TokenIdBalance {
+                address: addr.b(),
+                amount: Uint256::from(2u128),
+            }],
+        }],
+        memo: None,
+        padding: None,
+    };
+    let response = execute(deps.as_mut(), mock_env(), info_c, msg)?;
+    let expected = [
+        vec![0x83],
+        token_id_0.clone(),
+        biguint(2),
+        bstr(canonical_c.as_slice()),
+    ]
+    .concat();
+    assert_eq!(
+        decrypt_notification(&deps, &response, &addr.b(), "burn", 0)?,
+        padded(expected)
+    );
+
+    // addr.b revokes addr.c's permission
+    let msg = ExecuteMsg::RevokePermission {
+        token_id: "0".to_string(),
+        owner: addr.b(),
+        allowed_address: addr.c(),
+        padding: None,
+    };
+    let response = execute(deps.as_mut(), mock_env(), info_b, msg)?;
+    assert_eq!(
+        decrypt_notification(&deps, &response, &addr.c(), "allowance", 1)?,
+        padded(allowance(0, 0))
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_query_permit_scopes() -> StdResult<()> {
+    // init addresses
+    let addr = init_addrs();
+
+    // instantiate
+    let (_init_result, mut deps) = init_helper_default();
+
+    let private_key = [7u8; 32];
+    let (balance_permit, account) = sign_permit(
+        &deps,
+        &private_key,
+        "balance",
+        vec![
+            Snip1155PermitPermission::Balance,
+            Snip1155PermitPermission::TokenIds(vec!["0".to_string()]),
+        ],
+    )?;
+
+    // addr.a transfers tokens to the permit signer
+    let msg = ExecuteMsg::Transfer {
+        token_id: "0".to_string(),
+        from: addr.a(),
+        recipient: account.clone(),
+        amount: Uint256::from(100u128),
+        memo: None,
+        decoys: None,
+        entropy: None,
+        padding: None,
+    };
+    let info = mock_info(addr.a().as_str(), &[]);
+    execute(deps.as_mut(), mock_env(), info, msg)?;
+
+    // balance permit can query balance of listed token_id
+    let msg = QueryMsg::WithPermit {
+        permit: balance_permit.clone(),
+        query: QueryWithPermit::Balance {
+            owner: account.clone(),
+            token_id: "0".to_string(),
+        },
+    };
+    let q_answer = from_binary::<QueryAnswer>(&query(deps.as_ref(), mock_env(), msg)?)?;
+    match q_answer {
+        QueryAnswer::Balance { amount } => assert_eq!(amount, Uint256::from(100u128)),
+        _ => panic!("query error"),
+    }
+
+    // ... but not of other token_ids
+    let msg = QueryMsg::WithPermit {
+        permit: balance_permit.clone(),
+        query: QueryWithPermit::Balance {
+            owner: account.clone(),
+            token_id: "1".to_string(),
+        },
+    };
+    let q_result = query(deps.as_ref(), mock_env(), msg);
+    assert_eq!(
+        extract_error_msg(&q_result),
+        "permit does not apply to token_id 1"
+    );
+
+    // ... and cannot query transaction history
+    let msg = QueryMsg::WithPermit {
+        permit: balance_permit,
+        query: QueryWithPermit::TransactionHistory {
+            page: None,
+            page_size: 10,
+        },
+    };
+    let q_result = query(deps.as_ref(), mock_env(), msg);
+    assert!(extract_error_msg(&q_result)
+        .contains("`History` or `Owner` permit required for transaction_history permit query"));
+
+    // history permit can query transaction history, but not balances
+    let (history_permit, _) = sign_permit(
+        &deps,
+        &private_key,
+        "history",
+        vec![Snip1155PermitPermission::History],
+    )?;
+    let msg = QueryMsg::WithPermit {
+        permit: history_permit.clone(),
+        query: QueryWithPermit::TransactionHistory {
+            page: None,
+            page_size: 10,
+        },
+    };
+    let q_answer = from_binary::<QueryAnswer>(&query(deps.as_ref(), mock_env(), msg)?)?;
+    match q_answer {
+        QueryAnswer::TransactionHistory { total, .. } => assert_eq!(total, 1),
+        _ => panic!("query error"),
+    }
+    let msg = QueryMsg::WithPermit {
+        permit: history_permit,
+        query: QueryWithPermit::AllBalances {
+            tx_history_page: None,
+            tx_history_page_size: None,
+        },
+    };
+    let q_result = query(deps.as_ref(), mock_env(), msg);
+    assert!(extract_error_msg(&q_result)
+        .contains("`Balance` or `Owner` permit required for all_balances permit query"));
+
+    // owner permit can perform all queries
+    let (owner_permit, _) = sign_permit(
+        &deps,
+        &private_key,
+        "owner",
+        vec![Snip1155PermitPermission::Owner],
+    )?;
+    let msg = QueryMsg::WithPermit {
+        permit: owner_permit,
+        query: QueryWithPermit::AllBalances {
+            tx_history_page: None,
+            tx_history_page_size: None,
+        },
+    };
+    let q_answer = from_binary::<QueryAnswer>(&query(deps.as_ref(), mock_env(), msg)?)?;
+    match q_answer {
+        QueryAnswer::AllBalances(balances) => {
+            assert_eq!(balances.len(), 1);
+            assert_eq!(balances[0].amount, Uint256::from(100u128));
+        }
+        _ => panic!("query error"),
+    }
+
+    Ok(())
+}
@@ -1,13 +1,25 @@
+use chacha20poly1305::{
+    aead::{generic_array::GenericArray, Aead, KeyInit, Payload},
+    ChaCha20Poly1305,
+};
 use cosmwasm_storage::ReadonlyPrefixedStorage;
-use secret_toolkit::viewing_key::{ViewingKey, ViewingKeyStore};
+use hmac::{Hmac, Mac};
+use secret_toolkit::{
+    crypto::{secp256k1::PrivateKey, sha_256},
+    permit::{validate, Permissions, Permit, PermitParams, PermitSignature, PubKey, SignedPermit},
+    viewing_key::{ViewingKey, ViewingKeyStore},
+};
 use serde::de::DeserializeOwned;
+use sha2::Sha256;
 use std::any::Any;
 
-use super::super::{handles::*, msg::*, state::state_structs::*, state::*};
+use super::super::{
+    handles::*, msg::*, notifications::NOTIFICATION_BLOCK_SIZE, state::state_structs::*, state::*,
+};
 
 use cosmwasm_std::{
-    from_binary, testing::*, to_binary, Addr, CosmosMsg, Env, MessageInfo, OwnedDeps, Response,
-    StdError, StdResult, Storage, Uint256, WasmMsg,
+    from_binary, testing::*, to_binary, Addr, Api, Binary, CosmosMsg, Env, MessageInfo, OwnedDeps,
+    Response, StdError, StdResult, Storage, Uint256, WasmMsg,
 };
 
 /////////////////////////////////////////////////////////////////////////////////
@@ -81,6 +93,9 @@ pub fn init_helper_default() -> (
         curators: vec![info.sender.clone()],
         initial_tokens: vec![CurateTokenId::default()],
         entropy: "seedentropy".to_string(),
+        payment_tokens: vec![],
+        treasury: None,
+        admin_action_delay: None,
     };
 
     (instantiate(deps.as_mut(), env, info, init_msg), deps)
@@ -143,6 +158,7 @@ pub fn curate_addtl_default(
     let msg = ExecuteMsg::CurateTokenIds {
         initial_tokens: vec![curate0a, curate1, curate2, curate2a],
         memo: None,
+        notify_receivers: None,
         padding: None,
     };
     execute(deps.as_mut(), env, info, msg)?;
@@ -244,6 +260,99 @@ pub fn generate_viewing_keys(
     Ok(Vks { vks })
 }
 
+/// signs a query permit for this contract with `private_key`. Returns the permit and the
+/// address of the signer
+pub fn sign_permit<T: Permissions>(
+    deps: &OwnedDeps<MockStorage, MockApi, MockQuerier>,
+    private_key: &[u8; 32],
+    permit_name: &str,
+    permissions: Vec<T>,
+) -> StdResult<(Permit<T>, Addr)> {
+    let params = PermitParams {
+        allowed_tokens: vec![mock_env().contract.address.to_string()],
+        permit_name: permit_name.to_string(),
+        chain_id: mock_env().block.chain_id,
+        permissions,
+    };
+    let signed_bytes = to_binary(&SignedPermit::from_params(&params))?;
+    let signature = deps
+        .api
+        .secp256k1_sign(signed_bytes.as_slice(), private_key)
+        .unwrap();
+    let pubkey = PrivateKey::parse(private_key)?
+        .pubkey()
+        .serialize_compressed();
+
+    let permit = Permit {
+        params,
+        signature: PermitSignature {
+            pub_key: PubKey {
+                r#type: "tendermint/PubKeySecp256k1".to_string(),
+                value: Binary::from(pubkey.as_slice()),
+            },
+            signature: Binary::from(signature),
+        },
+    };
+    let account = validate(
+        deps.as_ref(),
+        PREFIX_REVOKED_PERMITS,
+        &permit,
+        mock_env().contract.address.to_string(),
+        None,
+    )?;
+
+    Ok((permit, Addr::unchecked(account)))
+}
+
+/// decrypts the notification for `address` with the given channel `counter` in a response, as a
+/// client would, deriving the seed from the contract's prng seed. Returns the zero-padded CBOR
+/// payload
+pub fn decrypt_notification(
+    deps: &OwnedDeps<MockStorage, MockApi, MockQuerier>,
+    response: &Response,
+    address: &Addr,
+    channel: &str,
+    counter: u64,
+) -> StdResult<Vec<u8>> {
+    let hmac_sha256 = |key: &[u8], msg: &[u8]| -> [u8; 32] {
+        let mut mac = <Hmac<Sha256> as Mac>::new_from_slice(key).unwrap();
+        mac.update(msg);
+        mac.finalize().into_bytes().into()
+    };
+    let prng_seed = contr_conf_r(&deps.storage).load()?.prng_seed;
+    let secret = hmac_sha256(&prng_seed, b"snip52:internal_secret");
+    let canonical = deps.api.addr_canonicalize(address.as_str())?;
+    let seed = hmac_sha256(&secret, canonical.as_slice());
+    let id = hmac_sha256(&seed, format!("{}:{}", channel, counter).as_bytes());
+
+    let key = format!("snip52:{}", Binary::from(id.as_slice()).to_base64());
+    let attribute = response
+        .attributes
+        .iter()
+        .find(|attr| attr.key == key)
+        .ok_or_else(|| StdError::generic_err(format!("no notification with id {}", key)))?;
+    let data = Binary::from_base64(&attribute.value)?;
+
+    let mut nonce = [0u8; 12];
+    nonce.copy_from_slice(&sha_256(channel.as_bytes())[..12]);
+    for (n, c) in nonce[4..].iter_mut().zip(counter.to_be_bytes()) {
+        *n ^= c;
+    }
+    let cipher = ChaCha20Poly1305::new(GenericArray::from_slice(&seed));
+    let plaintext = cipher
+        .decrypt(
+            GenericArray::from_slice(&nonce),
+            Payload {
+                msg: data.as_slice(),
+                aad: &id,
+            },
+        )
+        .map_err(|_| StdError::generic_err("failed to decrypt notification"))?;
+
+    assert_eq!(plaintext.len() % NOTIFICATION_BLOCK_SIZE, 0);
+    Ok(plaintext)
+}
+
 /// Unfortunately only reads the sha_256 hash of the viewing key. Contract does not store viewing key
 pub fn read_viewing_key_hash(store: &dyn Storage, owner: &str) -> Option<Vec<u8>> {
     let vk_store = ReadonlyPrefixedStorage::new(store, ViewingKey::STORAGE_KEY);